@@ -18,8 +18,15 @@
 //!
 //! Provides Calculator struct for parsing string expressions to floats.
 
+use crate::calculator_cache::{execute, Instruction, SharedCache};
+use crate::calculator_definitions::VariableDefinition;
 use crate::{CalculatorError, CalculatorFloat};
-use std::collections::HashMap;
+use serde::de::{Deserializer, Error};
+use serde::ser::Serializer;
+use serde::{Deserialize, Serialize};
+use std::borrow::Borrow;
+use std::collections::{BTreeMap, HashMap};
+use std::convert::TryFrom;
 use std::fmt;
 use std::str::FromStr;
 use std::vec::Vec;
@@ -27,7 +34,7 @@ static ATOL: f64 = f64::EPSILON;
 
 /// Match name of function to number of arguments.
 /// Returns result with CalculatorError when function name is not known.
-fn function_argument_numbers(input: &str) -> Result<usize, CalculatorError> {
+pub(crate) fn function_argument_numbers(input: &str) -> Result<usize, CalculatorError> {
     match input {
         "sin" => Ok(1),
         "cos" => Ok(1),
@@ -75,7 +82,7 @@ fn function_argument_numbers(input: &str) -> Result<usize, CalculatorError> {
 }
 
 /// Match name of function with one argument to Rust function and return Result.
-fn function_1_argument(input: &str, arg0: f64) -> Result<f64, CalculatorError> {
+pub(crate) fn function_1_argument(input: &str, arg0: f64) -> Result<f64, CalculatorError> {
     match input {
         "sin" => Ok(arg0.sin()),
         "cos" => Ok(arg0.cos()),
@@ -130,7 +137,11 @@ fn function_1_argument(input: &str, arg0: f64) -> Result<f64, CalculatorError> {
 }
 
 /// Match name of function with two arguments to Rust function and return Result.
-fn function_2_arguments(input: &str, arg0: f64, arg1: f64) -> Result<f64, CalculatorError> {
+pub(crate) fn function_2_arguments(
+    input: &str,
+    arg0: f64,
+    arg1: f64,
+) -> Result<f64, CalculatorError> {
     match input {
         "atan2" => Ok(arg0.atan2(arg1)),
         "hypot" => Ok(arg0.hypot(arg1)),
@@ -143,11 +154,29 @@ fn function_2_arguments(input: &str, arg0: f64, arg1: f64) -> Result<f64, Calcul
     }
 }
 
+/// Return true when an infinite result of function `fct` is caused by a pole
+/// of the function rather than by an overflow.
+fn is_pole(fct: &str, arguments: &[f64]) -> bool {
+    match fct {
+        "log" | "log10" | "atanh" | "artanh" => true,
+        "pow" => arguments.first() == Some(&0.0),
+        _ => false,
+    }
+}
+
 /// Struct for parsing string expressions to floats.
 #[derive(Debug, Clone)]
 pub struct Calculator {
     ///  HashMap of variables in current Calculator
     pub variables: HashMap<String, f64>,
+    /// Variables defined by expressions that are evaluated when the variable is used
+    pub(crate) definitions: HashMap<String, VariableDefinition>,
+    /// Stack of local variable scopes, innermost scope last
+    scopes: Vec<HashMap<String, f64>>,
+    /// Return errors for NaN or infinite intermediate values when true
+    strict: bool,
+    /// Optional cache of compiled expressions
+    pub(crate) cache: Option<SharedCache>,
 }
 
 /// Define the default value of Calculator.
@@ -157,26 +186,211 @@ impl Default for Calculator {
     }
 }
 
+/// Version of the serialization format of Calculator.
+const CALCULATOR_FORMAT_VERSION: u32 = 1;
+
+/// Serialized form of Calculator.
+///
+/// Local scopes and the compiled expressions in the cache are not serialized.
+///
+/// # Fields
+///
+/// * `format_version` - Version of the serialization format
+/// * `variables` - Variables with a value
+/// * `expressions` - Source text of variables defined by expressions
+/// * `strict` - Whether strict mode is enabled
+/// * `cache_capacity` - Capacity of the expression cache or None when the cache is disabled
+///
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename = "Calculator")]
+struct CalculatorSerialize {
+    format_version: u32,
+    variables: BTreeMap<String, f64>,
+    expressions: BTreeMap<String, String>,
+    strict: bool,
+    cache_capacity: Option<usize>,
+}
+
+impl From<&Calculator> for CalculatorSerialize {
+    fn from(calculator: &Calculator) -> Self {
+        CalculatorSerialize {
+            format_version: CALCULATOR_FORMAT_VERSION,
+            variables: calculator
+                .variables
+                .iter()
+                .map(|(name, value)| (name.clone(), *value))
+                .collect(),
+            expressions: calculator
+                .iter_expressions()
+                .map(|(name, expression)| (name.to_string(), expression.to_string()))
+                .collect(),
+            strict: calculator.strict,
+            cache_capacity: calculator
+                .cache_statistics()
+                .map(|statistics| statistics.capacity),
+        }
+    }
+}
+
+impl TryFrom<CalculatorSerialize> for Calculator {
+    type Error = CalculatorError;
+
+    fn try_from(serialized: CalculatorSerialize) -> Result<Self, CalculatorError> {
+        if serialized.format_version > CALCULATOR_FORMAT_VERSION {
+            return Err(CalculatorError::UnsupportedFormatVersion {
+                version: serialized.format_version,
+                supported: CALCULATOR_FORMAT_VERSION,
+            });
+        }
+        let mut calculator = Calculator::new();
+        calculator.set_variables(serialized.variables);
+        for (name, expression) in serialized.expressions.iter() {
+            calculator.set_expression(name, expression)?;
+        }
+        calculator.strict = serialized.strict;
+        if let Some(capacity) = serialized.cache_capacity {
+            calculator.enable_cache(capacity);
+        }
+        Ok(calculator)
+    }
+}
+
+// Implementing serde serialization
+// writing directly to string.
+impl Serialize for Calculator {
+    // Serialization function for Calculator according to the CalculatorSerialize form.
+    //
+    // # Arguments
+    //
+    // * `self` - Calculator to be serialized
+    // * `serializer` - Serializer used for serialization
+    //
+    // # Returns
+    //
+    // `S::Ok` - Serialized instance of Calculator
+    // `S::Error` - Error in the serialization process
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        CalculatorSerialize::from(self).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Calculator {
+    // Deserialization function for Calculator.
+    //
+    // Definitions are checked for syntax errors and cycles and
+    // newer format versions are rejected.
+    //
+    // # Arguments
+    //
+    // * `deserializer` - Deserializer used for deserialization
+    //
+    // # Returns
+    //
+    // `Calculator` - Deserialized instance of Calculator
+    // `D::Error` - Error in the deserialization process
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let serialized = CalculatorSerialize::deserialize(deserializer)?;
+        Calculator::try_from(serialized).map_err(D::Error::custom)
+    }
+}
+
 impl Calculator {
     /// Create new Calculator.
     pub fn new() -> Self {
         Calculator {
             variables: HashMap::new(),
+            definitions: HashMap::new(),
+            scopes: Vec::new(),
+            strict: false,
+            cache: None,
         }
     }
+
+    /// Enable or disable strict mode for Calculator.
+    ///
+    /// In strict mode any NaN or infinite intermediate value returns
+    /// a DomainError or Overflow instead of propagating silently.
+    ///
+    /// # Arguments
+    ///
+    /// * `strict` - Whether strict mode is enabled
+    ///
+    pub fn set_strict(&mut self, strict: bool) {
+        self.strict = strict;
+    }
+
+    /// Return true when strict mode is enabled for Calculator.
+    pub fn is_strict(&self) -> bool {
+        self.strict
+    }
+
+    /// Open new local scope for variables.
+    ///
+    /// Until the scope is closed with pop_scope, set_variable and assignments in parsed
+    /// expressions only set variables in the innermost scope. Lookups check the scopes
+    /// from the innermost to the outermost before the variables of Calculator, so
+    /// local values also override variables defined by expressions.
+    pub fn push_scope(&mut self) {
+        self.scopes.push(HashMap::new());
+    }
+
+    /// Close innermost local scope and discard its variables.
+    ///
+    /// # Returns
+    ///
+    /// `Option<HashMap<String, f64>>` - Variables set in the closed scope or None when no scope is open
+    ///
+    pub fn pop_scope(&mut self) -> Option<HashMap<String, f64>> {
+        self.scopes.pop()
+    }
+
+    /// Return number of open local scopes.
+    pub fn scope_depth(&self) -> usize {
+        self.scopes.len()
+    }
+
     /// Set variable for Calculator.
     ///
+    /// When a local scope is open the variable is only set in the innermost scope.
+    ///
     /// # Arguments
     ///
     /// * `name` - Name of the variable
     /// * `value` - Float value of the variable
     ///
     pub fn set_variable(&mut self, name: &str, value: f64) {
-        self.variables.insert(name.to_string(), value);
+        match self.scopes.last_mut() {
+            Some(scope) => {
+                scope.insert(name.to_string(), value);
+            }
+            None => {
+                self.definitions.remove(name);
+                self.variables.insert(name.to_string(), value);
+            }
+        }
+    }
+
+    /// Return value of a variable from the innermost scope setting it, ignoring definitions.
+    pub(crate) fn stored_variable(&self, name: &str) -> Option<f64> {
+        self.scopes
+            .iter()
+            .rev()
+            .chain(std::iter::once(&self.variables))
+            .find_map(|scope| scope.get(name))
+            .copied()
     }
 
     /// Get variable for Calculator.
     ///
+    /// Variables defined by an expression are evaluated with the current values
+    /// of the variables they depend on.
+    ///
     /// # Arguments
     ///
     /// * `name` - Name of the variable
@@ -186,12 +400,111 @@ impl Calculator {
     /// `value` - Result
     ///
     pub fn get_variable(&self, name: &str) -> Result<f64, CalculatorError> {
-        Ok(*self
-            .variables
-            .get(name)
-            .ok_or(CalculatorError::VariableNotSet {
+        if let Some(value) = self.stored_variable(name) {
+            return Ok(value);
+        }
+        match self.definitions.get(name) {
+            Some(definition) => execute(
+                &mut ReadOnlyEvaluator { evaluator: self },
+                &definition.program,
+            ),
+            None => Err(CalculatorError::VariableNotSet {
                 name: name.to_string(),
-            })?)
+            }),
+        }
+    }
+
+    /// Remove variable value from Calculator.
+    ///
+    /// When a local scope is open the variable is only removed from the innermost scope.
+    /// Variables defined by expressions are removed with remove_expression.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name of the variable
+    ///
+    /// # Returns
+    ///
+    /// `Option<f64>` - Removed value or None when the variable was not set
+    ///
+    pub fn remove_variable(&mut self, name: &str) -> Option<f64> {
+        match self.scopes.last_mut() {
+            Some(scope) => scope.remove(name),
+            None => self.variables.remove(name),
+        }
+    }
+
+    /// Return true when a variable has a value or is defined by an expression.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name of the variable
+    ///
+    pub fn contains(&self, name: &str) -> bool {
+        self.scopes.iter().any(|scope| scope.contains_key(name))
+            || self.variables.contains_key(name)
+            || self.definitions.contains_key(name)
+    }
+
+    /// Remove all variables, definitions and local scopes from Calculator.
+    ///
+    /// Strict mode and the expression cache are kept.
+    pub fn clear(&mut self) {
+        self.variables.clear();
+        self.definitions.clear();
+        self.scopes.clear();
+    }
+
+    /// Return iterator over names and values of all variables with a value.
+    ///
+    /// Values set in open local scopes override the values of outer scopes.
+    /// Variables defined by expressions are not included.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, f64)> + '_ {
+        let mut visible: HashMap<&str, f64> = HashMap::new();
+        for scope in std::iter::once(&self.variables).chain(self.scopes.iter()) {
+            for (name, value) in scope.iter() {
+                visible.insert(name, *value);
+            }
+        }
+        visible.into_iter()
+    }
+
+    /// Set several variables for Calculator.
+    ///
+    /// # Arguments
+    ///
+    /// * `variables` - Pairs of variable names and values, for example a HashMap
+    ///
+    pub fn set_variables<I, K, V>(&mut self, variables: I)
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: Borrow<f64>,
+    {
+        for (name, value) in variables {
+            self.set_variable(name.as_ref(), *value.borrow());
+        }
+    }
+
+    /// Copy variables and definitions of another Calculator.
+    ///
+    /// Values are set with set_variable, definitions with set_expression.
+    /// Definitions copied before an error are kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - Calculator whose variables are copied
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - All variables have been copied
+    /// * `Err(CalculatorError::CircularDependency)` - A copied definition forms a cycle with existing definitions
+    pub fn extend(&mut self, other: &Calculator) -> Result<(), CalculatorError> {
+        self.set_variables(other.iter());
+        for (name, definition) in other.definitions.iter() {
+            self.set_expression(name, &definition.expression)?;
+        }
+        Ok(())
     }
 
     ///  Parse a string expression.
@@ -201,6 +514,14 @@ impl Calculator {
     /// * `expression` - Expression that is parsed
     ///
     pub fn parse_str(&mut self, expression: &str) -> Result<f64, CalculatorError> {
+        // Expressions that fail to compile are parsed directly to report the same error
+        if let Some(Ok(program)) = self
+            .cache
+            .as_mut()
+            .map(|c| c.get_mut().get_or_compile(expression))
+        {
+            return execute(self, &program);
+        }
         let mut parser = Parser::new(expression, self);
         let end_value = parser.evaluate_all_tokens()?;
         match end_value {
@@ -209,6 +530,60 @@ impl Calculator {
         }
     }
 
+    /// Execute a script of statements that may assign variables.
+    ///
+    /// Statements are separated by `;` and evaluated in order,
+    /// assignments such as `x=2*y` set variables of Calculator.
+    ///
+    /// # Arguments
+    ///
+    /// * `script` - Statements that are executed
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(f64)` - Value of the last statement
+    /// * `Err(CalculatorError)` - Error of the first failing statement
+    pub fn execute(&mut self, script: &str) -> Result<f64, CalculatorError> {
+        self.parse_str(script)
+    }
+
+    /// Evaluate a string expression without modifying Calculator.
+    ///
+    /// Only needs a shared reference, so one Calculator can evaluate expressions
+    /// from several threads. Use execute for scripts that assign variables.
+    ///
+    /// # Arguments
+    ///
+    /// * `expression` - Expression that is evaluated
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(f64)` - Value of the expression
+    /// * `Err(CalculatorError::AssignmentNotAllowed)` - Expression assigns a variable
+    /// * `Err(CalculatorError)` - Expression is invalid or evaluation failed
+    pub fn evaluate(&self, expression: &str) -> Result<f64, CalculatorError> {
+        let program = self.compiled(expression)?;
+        for instruction in program.iter() {
+            if let Instruction::Assign(name) = instruction {
+                return Err(CalculatorError::AssignmentNotAllowed { name: name.clone() });
+            }
+        }
+        execute(&mut ReadOnlyEvaluator { evaluator: self }, &program)
+    }
+
+    /// Evaluate a CalculatorFloat to float without modifying Calculator.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - Evaluated string CalculatorFloat or returns float value
+    ///
+    pub fn evaluate_float(&self, value: &CalculatorFloat) -> Result<f64, CalculatorError> {
+        match value {
+            CalculatorFloat::Float(x) => Ok(*x),
+            CalculatorFloat::Str(expression) => expression.evaluate_read_only(self),
+        }
+    }
+
     /// Parse a CalculatorFloat to float.
     ///
     /// # Arguments
@@ -218,7 +593,7 @@ impl Calculator {
     pub fn parse_get(&mut self, parse_variable: CalculatorFloat) -> Result<f64, CalculatorError> {
         match parse_variable {
             CalculatorFloat::Float(x) => Ok(x),
-            CalculatorFloat::Str(expression) => self.parse_str(&expression),
+            CalculatorFloat::Str(expression) => expression.evaluate(self),
         }
     }
 }
@@ -246,7 +621,7 @@ impl Calculator {
 /// * `Unrecognized` - No Token has been recognized in string
 ///
 #[derive(Debug, Clone, PartialEq)]
-enum Token {
+pub(crate) enum Token {
     Number(f64),
     Variable(String),
     Function(String),
@@ -295,12 +670,12 @@ impl fmt::Display for Token {
 
 /// Struct implementing Iterator trait to lex string
 /// to computational Tokens.
-struct TokenIterator<'a> {
+pub(crate) struct TokenIterator<'a> {
     // Save current expression as a slice of a string so we do not
     // need to copy but only modify (shorten) the slice.
     //
     /// * `current_expression` - Current str expression being lexed
-    current_expression: &'a str,
+    pub(crate) current_expression: &'a str,
 }
 
 // Implement the Iterator Trait for TokenIterator so it can be used as standard rust iterator.
@@ -315,32 +690,9 @@ where
         if self.current_expression.is_empty() {
             None
         } else {
-            // Loop to remove whitespace and comments
-            loop {
-                if self.current_expression.starts_with(' ') {
-                    let end = self
-                        .current_expression
-                        .char_indices()
-                        .find_map(|(ind, c)| if c.is_whitespace() { None } else { Some(ind) })
-                        .unwrap_or_else(|| self.current_expression.len());
-                    self.cut_current_expression(end);
-                    if self.current_expression.is_empty() {
-                        return Some(Token::EndOfString);
-                    }
-                    continue;
-                } else if self.current_expression.starts_with('#') {
-                    let end = self
-                        .current_expression
-                        .char_indices()
-                        .find_map(|(ind, c)| if c != '\u{000A}' { None } else { Some(ind + 1) })
-                        .unwrap_or_else(|| self.current_expression.len());
-                    self.cut_current_expression(end);
-                    if self.current_expression.is_empty() {
-                        return Some(Token::EndOfString);
-                    }
-                    continue;
-                }
-                break;
+            self.skip_whitespace_and_comments();
+            if self.current_expression.is_empty() {
+                return Some(Token::EndOfString);
             }
             // Test if head of current_expression is a letter char
             if self
@@ -363,28 +715,26 @@ where
                     })
                     .unwrap_or_else(|| self.current_expression.len());
                 // Get next token from TokenIterator with shortened expression
-                let next_token = if end >= self.current_expression.len() {
-                    TokenIterator {
-                        current_expression: "",
-                    }
-                    .next()
-                } else {
-                    TokenIterator {
-                        current_expression: &self.current_expression[end..],
-                    }
-                    .next()
+                let mut lookahead = TokenIterator {
+                    current_expression: if end >= self.current_expression.len() {
+                        ""
+                    } else {
+                        &self.current_expression[end..]
+                    },
                 };
+                let next_token = lookahead.next();
                 // Depending on next token currently lexed string current_expression[..end] creates different tokens
                 // Token contains current_expression[..end] for later processing
+                // Assign and BracketOpen are consumed including any whitespace before them
                 return Some(match next_token {
                     Some(Token::Assign) => {
                         let vs = self.current_expression[..end].to_owned();
-                        self.cut_current_expression(end + 1);
+                        self.current_expression = lookahead.current_expression;
                         Token::VariableAssign(vs)
                     }
                     Some(Token::BracketOpen) => {
                         let vs = self.current_expression[..end].to_owned();
-                        self.cut_current_expression(end + 1);
+                        self.current_expression = lookahead.current_expression;
                         Token::Function(vs)
                     }
                     _ => {
@@ -450,7 +800,7 @@ where
             };
             // Create symbol tokens
             let symbol = self.current_expression.chars().next().unwrap();
-            self.current_expression = &self.current_expression[1..];
+            self.current_expression = &self.current_expression[symbol.len_utf8()..];
             return Some(match symbol {
                 '+' => Token::Plus,
                 '-' => Token::Minus,
@@ -483,6 +833,29 @@ where
 
 // Helper methods not in standard iterator trait.
 impl<'a> TokenIterator<'a> {
+    // Remove leading whitespace and comments from the current expression.
+    pub(crate) fn skip_whitespace_and_comments(&mut self) {
+        loop {
+            if self.current_expression.starts_with(char::is_whitespace) {
+                let end = self
+                    .current_expression
+                    .char_indices()
+                    .find_map(|(ind, c)| if c.is_whitespace() { None } else { Some(ind) })
+                    .unwrap_or_else(|| self.current_expression.len());
+                self.cut_current_expression(end);
+            } else if self.current_expression.starts_with('#') {
+                let end = self
+                    .current_expression
+                    .char_indices()
+                    .find_map(|(ind, c)| if c != '\u{000A}' { None } else { Some(ind + 1) })
+                    .unwrap_or_else(|| self.current_expression.len());
+                self.cut_current_expression(end);
+            } else {
+                break;
+            }
+        }
+    }
+
     // Return the next token and the current token (in string form).
     fn next_token_and_str(&mut self) -> (Option<Token>, &'a str) {
         let next_token = self.next();
@@ -500,33 +873,245 @@ impl<'a> TokenIterator<'a> {
     }
 }
 
-/// Parse string to float using TokenIterator lexer.
+/// Backend used by the Parser to evaluate parsed expressions.
+///
+/// Provides the value type of the evaluation, variable access and
+/// all arithmetic operations and functions known to the Parser.
+pub(crate) trait Evaluator {
+    /// Type parsed expressions are evaluated to
+    type Value;
+
+    /// Return value of a number.
+    fn number(&self, value: f64) -> Result<Self::Value, CalculatorError>;
+
+    /// Return value of a number literal given by its text in the expression.
+    ///
+    /// Evaluators with higher precision than f64 can parse the literal themselves.
+    fn number_literal(&self, _literal: &str, value: f64) -> Result<Self::Value, CalculatorError> {
+        self.number(value)
+    }
+
+    /// Return value of a variable.
+    fn variable(&self, name: &str) -> Result<Self::Value, CalculatorError>;
+
+    /// Assign value to a variable and return the assigned value.
+    fn assign(&mut self, name: &str, value: Self::Value) -> Result<Self::Value, CalculatorError>;
+
+    /// Return lhs + rhs.
+    fn add(&self, lhs: Self::Value, rhs: Self::Value) -> Result<Self::Value, CalculatorError>;
+
+    /// Return lhs - rhs.
+    fn sub(&self, lhs: Self::Value, rhs: Self::Value) -> Result<Self::Value, CalculatorError>;
+
+    /// Return lhs * rhs.
+    fn mul(&self, lhs: Self::Value, rhs: Self::Value) -> Result<Self::Value, CalculatorError>;
+
+    /// Return lhs / rhs.
+    fn div(&self, lhs: Self::Value, rhs: Self::Value) -> Result<Self::Value, CalculatorError>;
+
+    /// Return lhs ^ rhs.
+    fn pow(&self, lhs: Self::Value, rhs: Self::Value) -> Result<Self::Value, CalculatorError>;
+
+    /// Return -value.
+    fn neg(&self, value: Self::Value) -> Result<Self::Value, CalculatorError>;
+
+    /// Return function with one argument applied to arg0.
+    fn function_1_argument(
+        &self,
+        fct: &str,
+        arg0: Self::Value,
+    ) -> Result<Self::Value, CalculatorError>;
+
+    /// Return function with two arguments applied to arg0 and arg1.
+    fn function_2_arguments(
+        &self,
+        fct: &str,
+        arg0: Self::Value,
+        arg1: Self::Value,
+    ) -> Result<Self::Value, CalculatorError>;
+}
+
+impl Calculator {
+    /// Check a value computed by function `fct` when strict mode is enabled.
+    fn check_finite(
+        &self,
+        fct: &str,
+        arguments: &[f64],
+        value: f64,
+    ) -> Result<f64, CalculatorError> {
+        if !self.strict || value.is_finite() {
+            Ok(value)
+        } else if value.is_nan() || is_pole(fct, arguments) {
+            Err(CalculatorError::DomainError {
+                fct: fct.to_string(),
+                arguments: arguments.to_vec(),
+            })
+        } else {
+            Err(CalculatorError::Overflow {
+                fct: fct.to_string(),
+                arguments: arguments.to_vec(),
+            })
+        }
+    }
+}
+
+/// Calculator evaluates expressions to f64 using its variables.
+impl Evaluator for Calculator {
+    type Value = f64;
+
+    fn number(&self, value: f64) -> Result<f64, CalculatorError> {
+        Ok(value)
+    }
+
+    fn variable(&self, name: &str) -> Result<f64, CalculatorError> {
+        self.get_variable(name)
+    }
+
+    fn assign(&mut self, name: &str, value: f64) -> Result<f64, CalculatorError> {
+        self.set_variable(name, value);
+        Ok(value)
+    }
+
+    fn add(&self, lhs: f64, rhs: f64) -> Result<f64, CalculatorError> {
+        self.check_finite("add", &[lhs, rhs], lhs + rhs)
+    }
+
+    fn sub(&self, lhs: f64, rhs: f64) -> Result<f64, CalculatorError> {
+        self.check_finite("sub", &[lhs, rhs], lhs - rhs)
+    }
+
+    fn mul(&self, lhs: f64, rhs: f64) -> Result<f64, CalculatorError> {
+        self.check_finite("mul", &[lhs, rhs], lhs * rhs)
+    }
+
+    fn div(&self, lhs: f64, rhs: f64) -> Result<f64, CalculatorError> {
+        if rhs == 0.0 {
+            return Err(CalculatorError::DivisionByZero);
+        }
+        self.check_finite("div", &[lhs, rhs], lhs / rhs)
+    }
+
+    fn pow(&self, lhs: f64, rhs: f64) -> Result<f64, CalculatorError> {
+        self.check_finite("pow", &[lhs, rhs], lhs.powf(rhs))
+    }
+
+    fn neg(&self, value: f64) -> Result<f64, CalculatorError> {
+        Ok(-value)
+    }
+
+    fn function_1_argument(&self, fct: &str, arg0: f64) -> Result<f64, CalculatorError> {
+        let value = function_1_argument(fct, arg0)?;
+        self.check_finite(fct, &[arg0], value)
+    }
+
+    fn function_2_arguments(
+        &self,
+        fct: &str,
+        arg0: f64,
+        arg1: f64,
+    ) -> Result<f64, CalculatorError> {
+        let value = function_2_arguments(fct, arg0, arg1)?;
+        self.check_finite(fct, &[arg0, arg1], value)
+    }
+}
+
+/// Evaluator using the variables of another evaluator without modifying them.
+///
+/// Assignments return an AssignmentNotAllowed error.
+pub(crate) struct ReadOnlyEvaluator<'a, E: Evaluator> {
+    pub(crate) evaluator: &'a E,
+}
+
+impl<E: Evaluator> Evaluator for ReadOnlyEvaluator<'_, E> {
+    type Value = E::Value;
+
+    fn number(&self, value: f64) -> Result<E::Value, CalculatorError> {
+        self.evaluator.number(value)
+    }
+
+    fn number_literal(&self, literal: &str, value: f64) -> Result<E::Value, CalculatorError> {
+        self.evaluator.number_literal(literal, value)
+    }
+
+    fn variable(&self, name: &str) -> Result<E::Value, CalculatorError> {
+        self.evaluator.variable(name)
+    }
+
+    fn assign(&mut self, name: &str, _value: E::Value) -> Result<E::Value, CalculatorError> {
+        Err(CalculatorError::AssignmentNotAllowed {
+            name: name.to_string(),
+        })
+    }
+
+    fn add(&self, lhs: E::Value, rhs: E::Value) -> Result<E::Value, CalculatorError> {
+        self.evaluator.add(lhs, rhs)
+    }
+
+    fn sub(&self, lhs: E::Value, rhs: E::Value) -> Result<E::Value, CalculatorError> {
+        self.evaluator.sub(lhs, rhs)
+    }
+
+    fn mul(&self, lhs: E::Value, rhs: E::Value) -> Result<E::Value, CalculatorError> {
+        self.evaluator.mul(lhs, rhs)
+    }
+
+    fn div(&self, lhs: E::Value, rhs: E::Value) -> Result<E::Value, CalculatorError> {
+        self.evaluator.div(lhs, rhs)
+    }
+
+    fn pow(&self, lhs: E::Value, rhs: E::Value) -> Result<E::Value, CalculatorError> {
+        self.evaluator.pow(lhs, rhs)
+    }
+
+    fn neg(&self, value: E::Value) -> Result<E::Value, CalculatorError> {
+        self.evaluator.neg(value)
+    }
+
+    fn function_1_argument(&self, fct: &str, arg0: E::Value) -> Result<E::Value, CalculatorError> {
+        self.evaluator.function_1_argument(fct, arg0)
+    }
+
+    fn function_2_arguments(
+        &self,
+        fct: &str,
+        arg0: E::Value,
+        arg1: E::Value,
+    ) -> Result<E::Value, CalculatorError> {
+        self.evaluator.function_2_arguments(fct, arg0, arg1)
+    }
+}
+
+/// Parse string to value using TokenIterator lexer.
 ///
 /// # Fields
 ///
 /// * `remaining_expression` - Expression that has not been parsed yet
 /// * `current_token` - Token that is currently parsed
-/// * `calculator` - Calculator that contains set variables
+/// * `current_str` - Part of the expression the current token was lexed from
+/// * `evaluator` - Evaluator that contains set variables and evaluates operations
 ///
-struct Parser<'a> {
+pub(crate) struct Parser<'a, E: Evaluator> {
     remaining_expression: &'a str,
     current_token: Token,
-    calculator: &'a mut Calculator,
+    current_str: &'a str,
+    evaluator: &'a mut E,
 }
-impl<'a, 'b> Parser<'a>
+impl<'a, 'b, E> Parser<'a, E>
 where
     'b: 'a,
+    E: Evaluator,
 {
     /// Initialize a new instance of Parser.
-    fn new(expression: &'a str, calculator: &'b mut Calculator) -> Self {
+    pub(crate) fn new(expression: &'a str, evaluator: &'b mut E) -> Self {
         let (next_token, next_str) = (TokenIterator {
             current_expression: expression,
         })
         .next_token_and_str();
         Parser {
             remaining_expression: next_str,
-            current_token: next_token.unwrap(),
-            calculator,
+            current_token: next_token.unwrap_or(Token::EndOfString),
+            current_str: &expression[..expression.len() - next_str.len()],
+            evaluator,
         }
     }
 
@@ -539,10 +1124,13 @@ where
         match next_token {
             None => {
                 self.current_token = Token::EndOfString;
+                self.current_str = "";
                 self.remaining_expression = "";
             }
             Some(t) => {
                 self.current_token = t;
+                self.current_str =
+                    &self.remaining_expression[..self.remaining_expression.len() - next_str.len()];
                 self.remaining_expression = next_str;
             }
         }
@@ -550,8 +1138,8 @@ where
 
     /// Evaluate all Tokens to real value, None (for not returning expressions)
     /// or return error.
-    fn evaluate_all_tokens(&mut self) -> Result<Option<f64>, CalculatorError> {
-        let mut current_value: Option<f64> = None;
+    pub(crate) fn evaluate_all_tokens(&mut self) -> Result<Option<E::Value>, CalculatorError> {
+        let mut current_value: Option<E::Value> = None;
         while self.current_token != Token::EndOfString {
             current_value = self.evaluate_init()?;
             while self.current_token == Token::EndOfExpression {
@@ -562,7 +1150,7 @@ where
     }
 
     /// Initialize the evaluation of an expression.
-    fn evaluate_init(&mut self) -> Result<Option<f64>, CalculatorError> {
+    fn evaluate_init(&mut self) -> Result<Option<E::Value>, CalculatorError> {
         if self.current_token == Token::EndOfExpression || self.current_token == Token::EndOfString
         {
             Err(CalculatorError::UnexpectedEndOfExpression)
@@ -571,50 +1159,46 @@ where
                 let vsnew = vs.to_owned();
                 self.next_token();
                 let res = self.evaluate_binary_1()?;
-                self.calculator.set_variable(&vsnew, res);
-                return Ok(Some(res));
+                return Ok(Some(self.evaluator.assign(&vsnew, res)?));
             }
             Ok(Some(self.evaluate_binary_1()?))
         }
     }
 
     /// Evaluate least preference binary expression (+, -).
-    fn evaluate_binary_1(&mut self) -> Result<f64, CalculatorError> {
+    fn evaluate_binary_1(&mut self) -> Result<E::Value, CalculatorError> {
         let mut res = self.evaluate_binary_2()?;
         while self.current_token == Token::Plus || self.current_token == Token::Minus {
             let bsum: bool = self.current_token == Token::Plus;
             self.next_token();
             let val = self.evaluate_binary_2()?;
             if bsum {
-                res += val;
+                res = self.evaluator.add(res, val)?;
             } else {
-                res -= val;
+                res = self.evaluator.sub(res, val)?;
             }
         }
         Ok(res)
     }
 
     /// Evaluate middle preference binary expression (*, /).
-    fn evaluate_binary_2(&mut self) -> Result<f64, CalculatorError> {
+    fn evaluate_binary_2(&mut self) -> Result<E::Value, CalculatorError> {
         let mut res = self.evaluate_binary_3()?;
         while self.current_token == Token::Multiply || self.current_token == Token::Divide {
             let bmul: bool = self.current_token == Token::Multiply;
             self.next_token();
             let val = self.evaluate_binary_3()?;
             if bmul {
-                res *= val;
+                res = self.evaluator.mul(res, val)?;
             } else {
-                if val == 0.0 {
-                    return Err(CalculatorError::DivisionByZero);
-                }
-                res /= val;
+                res = self.evaluator.div(res, val)?;
             }
         }
         Ok(res)
     }
 
     /// Evaluate least preference binary expression (^, !).
-    fn evaluate_binary_3(&mut self) -> Result<f64, CalculatorError> {
+    fn evaluate_binary_3(&mut self) -> Result<E::Value, CalculatorError> {
         let mut res = self.evaluate_unary()?;
         match self.current_token {
             Token::DoubleFactorial => {
@@ -627,7 +1211,8 @@ where
             }
             Token::Power => {
                 self.next_token();
-                res = res.powf(self.evaluate_unary()?);
+                let val = self.evaluate_unary()?;
+                res = self.evaluator.pow(res, val)?;
             }
             _ => (),
         }
@@ -635,23 +1220,28 @@ where
     }
 
     /// Handle any unary + or - signs.
-    fn evaluate_unary(&mut self) -> Result<f64, CalculatorError> {
-        let mut prefactor: f64 = 1.0;
+    fn evaluate_unary(&mut self) -> Result<E::Value, CalculatorError> {
+        let mut negate = false;
         match self.current_token {
             Token::Minus => {
                 self.next_token();
-                prefactor = -1.0;
+                negate = true;
             }
             Token::Plus => {
                 self.next_token();
             }
             _ => (),
         }
-        Ok(prefactor * self.evaluate()?)
+        let res = self.evaluate()?;
+        if negate {
+            self.evaluator.neg(res)
+        } else {
+            Ok(res)
+        }
     }
 
     /// Handle numbers, variables, functions and parentheses.
-    fn evaluate(&mut self) -> Result<f64, CalculatorError> {
+    fn evaluate(&mut self) -> Result<E::Value, CalculatorError> {
         match (*self).current_token {
             Token::BracketOpen => {
                 self.next_token();
@@ -669,13 +1259,15 @@ where
                 }
             }
             Token::Number(vf) => {
+                // Number literal is the last word after skipped whitespace and comments
+                let literal = self.current_str.split_whitespace().last().unwrap_or("");
                 self.next_token();
-                Ok(vf)
+                self.evaluator.number_literal(literal, vf)
             }
             Token::Variable(ref vs) => {
                 let vsnew = vs.to_owned();
                 self.next_token();
-                self.calculator.get_variable(&vsnew)
+                self.evaluator.variable(&vsnew)
             }
             Token::Function(ref vs) => {
                 let vsnew = vs.to_owned();
@@ -705,22 +1297,23 @@ where
                     });
                 }
                 self.next_token();
+                let mut arguments = heap.into_iter();
                 match number_arguments {
-                    1 => function_1_argument(
+                    1 => self.evaluator.function_1_argument(
                         &vsnew,
-                        *(heap
-                            .get(0)
-                            .ok_or(CalculatorError::NotEnoughFunctionArguments)?),
-                    ),
-                    2 => function_2_arguments(
-                        &vsnew,
-                        *(heap
-                            .get(0)
-                            .ok_or(CalculatorError::NotEnoughFunctionArguments)?),
-                        *(heap
-                            .get(1)
-                            .ok_or(CalculatorError::NotEnoughFunctionArguments)?),
+                        arguments
+                            .next()
+                            .ok_or(CalculatorError::NotEnoughFunctionArguments)?,
                     ),
+                    2 => {
+                        let arg0 = arguments
+                            .next()
+                            .ok_or(CalculatorError::NotEnoughFunctionArguments)?;
+                        let arg1 = arguments
+                            .next()
+                            .ok_or(CalculatorError::NotEnoughFunctionArguments)?;
+                        self.evaluator.function_2_arguments(&vsnew, arg0, arg1)
+                    }
                     _ => Err(CalculatorError::ParsingError {
                         msg: "Unsupported number of arguments.",
                     }),
@@ -742,6 +1335,11 @@ mod tests {
     use super::CalculatorFloat;
     use super::Token;
     use super::TokenIterator;
+    use crate::CalculatorError;
+    use serde_test::{assert_de_tokens_error, assert_ser_tokens, Token as SerdeToken};
+    use std::collections::HashMap;
+    use std::sync::Arc;
+    use std::thread;
 
     // Test the next function of the TokenIterator for an end of string Token
     #[test]
@@ -917,6 +1515,78 @@ mod tests {
         assert_eq!(next_token, Token::VariableAssign("test".to_owned()));
     }
 
+    // Test the TokenIterator consumes whitespace before = and ( after a variable name
+    #[test]
+    fn test_whitespace_lookahead() {
+        let mut t_iterator = TokenIterator {
+            current_expression: "a =2",
+        };
+        assert_eq!(
+            t_iterator.next().unwrap(),
+            Token::VariableAssign("a".to_owned())
+        );
+        assert_eq!(t_iterator.next().unwrap(), Token::Number(2.0));
+        let mut t_iterator2 = TokenIterator {
+            current_expression: "sin (0.5)",
+        };
+        assert_eq!(
+            t_iterator2.next().unwrap(),
+            Token::Function("sin".to_owned())
+        );
+        assert_eq!(t_iterator2.next().unwrap(), Token::Number(0.5));
+        let mut t_iterator3 = TokenIterator {
+            current_expression: "a +",
+        };
+        assert_eq!(t_iterator3.next().unwrap(), Token::Variable("a".to_owned()));
+        assert_eq!(t_iterator3.next().unwrap(), Token::Plus);
+    }
+
+    // Test the TokenIterator skips line breaks and tabs like spaces
+    #[test]
+    fn test_whitespace_line_breaks() {
+        let mut t_iterator = TokenIterator {
+            current_expression: "\n\t+",
+        };
+        assert_eq!(t_iterator.next().unwrap(), Token::Plus);
+        let mut t_iterator2 = TokenIterator {
+            current_expression: "a\n=",
+        };
+        assert_eq!(
+            t_iterator2.next().unwrap(),
+            Token::VariableAssign("a".to_owned())
+        );
+        let mut calculator = Calculator::new();
+        assert_eq!(calculator.parse_str("a =2"), Ok(2.0));
+        assert_eq!(calculator.parse_str("sin (0.5)"), Ok(0.5_f64.sin()));
+        assert_eq!(calculator.parse_str("x = (1 +\n 2)"), Ok(3.0));
+        assert_eq!(calculator.parse_str("x\t*\r\na"), Ok(6.0));
+    }
+
+    // Test parsing empty expressions returns an error instead of panicking
+    #[test]
+    fn test_empty_expression() {
+        let mut calculator = Calculator::new();
+        for expression in ["", " ", "# comment"].iter() {
+            assert_eq!(
+                calculator.parse_str(expression),
+                Err(CalculatorError::NoValueReturnedParsing)
+            );
+        }
+    }
+
+    // Test assignments and function calls with whitespace before = and (
+    #[test]
+    fn test_whitespace_assign_function() {
+        let mut calculator = Calculator::new();
+        assert_eq!(calculator.parse_str("x = 2"), Ok(2.0));
+        assert_eq!(calculator.parse_str("y  =  sin (0) + x"), Ok(2.0));
+        assert_eq!(calculator.get_variable("y"), Ok(2.0));
+        assert_eq!(
+            calculator.parse_str("atan2 (1 , 1)"),
+            Ok(std::f64::consts::FRAC_PI_4)
+        );
+    }
+
     // Test the next function of the TokenIterator for a function Token
     #[test]
     fn test_functions() {
@@ -946,7 +1616,7 @@ mod tests {
         calculator.set_variable("x", 0.1);
         assert_eq!(
             format!("{:?}", calculator),
-            "Calculator { variables: {\"x\": 0.1} }"
+            "Calculator { variables: {\"x\": 0.1}, definitions: {}, scopes: [], strict: false, cache: None }"
         );
     }
 
@@ -977,6 +1647,189 @@ mod tests {
         assert!(calculator.get_variable("test2").is_err());
     }
 
+    // Test local scopes of variables
+    #[test]
+    fn test_scopes() {
+        let mut calculator = Calculator::new();
+        calculator.set_variable("theta", 0.5);
+        calculator.set_variable("x", 1.0);
+        calculator.set_expression("phi", "2*theta").unwrap();
+        calculator.push_scope();
+        calculator.set_variable("theta", 2.0);
+        assert_eq!(calculator.parse_str("y=x+phi"), Ok(5.0));
+        calculator.push_scope();
+        assert_eq!(calculator.scope_depth(), 2);
+        assert_eq!(calculator.parse_str("theta=3;phi"), Ok(6.0));
+        assert_eq!(calculator.get_variable("y"), Ok(5.0));
+        let inner = calculator.pop_scope().unwrap();
+        assert_eq!(inner.get("theta"), Some(&3.0));
+        assert_eq!(calculator.get_variable("phi"), Ok(4.0));
+        calculator.pop_scope();
+        assert_eq!(calculator.scope_depth(), 0);
+        assert_eq!(calculator.pop_scope(), None);
+        assert_eq!(calculator.get_variable("phi"), Ok(1.0));
+        assert!(calculator.get_variable("y").is_err());
+        assert_eq!(calculator.get_expression("phi"), Some("2*theta"));
+    }
+
+    // Test management of several variables
+    #[test]
+    fn test_variable_management() {
+        let mut calculator = Calculator::new();
+        let mut parameters: HashMap<String, f64> = HashMap::new();
+        parameters.insert("theta".to_string(), 0.5);
+        parameters.insert("x".to_string(), 1.0);
+        calculator.set_variables(&parameters);
+        calculator.set_variables(vec![("y", 2.0)]);
+        calculator.set_expression("phi", "2*theta").unwrap();
+        assert!(calculator.contains("theta") && calculator.contains("phi"));
+        assert!(!calculator.contains("z"));
+        let mut variables: Vec<(&str, f64)> = calculator.iter().collect();
+        variables.sort_by(|a, b| a.0.cmp(b.0));
+        assert_eq!(variables, vec![("theta", 0.5), ("x", 1.0), ("y", 2.0)]);
+        assert_eq!(
+            calculator.iter_expressions().collect::<Vec<_>>(),
+            vec![("phi", "2*theta")]
+        );
+        calculator.push_scope();
+        calculator.set_variable("x", 3.0);
+        assert_eq!(
+            calculator.iter().find(|(name, _)| *name == "x"),
+            Some(("x", 3.0))
+        );
+        assert_eq!(calculator.remove_variable("x"), Some(3.0));
+        assert_eq!(calculator.remove_variable("x"), None);
+        calculator.pop_scope();
+        assert_eq!(calculator.remove_variable("x"), Some(1.0));
+        assert!(!calculator.contains("x"));
+
+        let mut other = Calculator::new();
+        other.set_variable("theta", 1.5);
+        other.set_expression("psi", "phi + 1").unwrap();
+        calculator.extend(&other).unwrap();
+        assert_eq!(calculator.get_variable("psi"), Ok(4.0));
+        let mut cyclic = Calculator::new();
+        cyclic.set_expression("theta", "psi").unwrap();
+        assert!(matches!(
+            calculator.extend(&cyclic),
+            Err(CalculatorError::CircularDependency { .. })
+        ));
+
+        assert_eq!(
+            calculator.remove_expression("psi"),
+            Some("phi + 1".to_string())
+        );
+        assert_eq!(calculator.remove_expression("psi"), None);
+        calculator.push_scope();
+        calculator.clear();
+        assert_eq!(calculator.scope_depth(), 0);
+        assert!(!calculator.contains("theta") && !calculator.contains("phi"));
+    }
+
+    // Test serialization and deserialization of Calculator
+    #[test]
+    fn test_serde() {
+        let mut calculator = Calculator::new();
+        calculator.set_variable("theta", 0.5);
+        calculator.set_expression("phi", "2*theta").unwrap();
+        calculator.set_strict(true);
+        calculator.enable_cache(8);
+        let tokens = [
+            SerdeToken::Struct {
+                name: "Calculator",
+                len: 5,
+            },
+            SerdeToken::Str("format_version"),
+            SerdeToken::U32(1),
+            SerdeToken::Str("variables"),
+            SerdeToken::Map { len: Some(1) },
+            SerdeToken::Str("theta"),
+            SerdeToken::F64(0.5),
+            SerdeToken::MapEnd,
+            SerdeToken::Str("expressions"),
+            SerdeToken::Map { len: Some(1) },
+            SerdeToken::Str("phi"),
+            SerdeToken::Str("2*theta"),
+            SerdeToken::MapEnd,
+            SerdeToken::Str("strict"),
+            SerdeToken::Bool(true),
+            SerdeToken::Str("cache_capacity"),
+            SerdeToken::Some,
+            SerdeToken::U64(8),
+            SerdeToken::StructEnd,
+        ];
+        assert_ser_tokens(&calculator, &tokens);
+        let json = serde_json::to_string(&calculator).unwrap();
+        let deserialized: Calculator = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized.get_variable("phi"), Ok(1.0));
+        assert_eq!(deserialized.get_expression("phi"), Some("2*theta"));
+        assert!(deserialized.is_strict());
+        assert_eq!(deserialized.cache_statistics().unwrap().capacity, 8);
+
+        let mut newer = tokens;
+        newer[2] = SerdeToken::U32(2);
+        assert_de_tokens_error::<Calculator>(
+            &newer,
+            "Format version 2 is newer than the supported version 1",
+        );
+        let mut cyclic = tokens;
+        cyclic[11] = SerdeToken::Str("phi + 1");
+        assert_de_tokens_error::<Calculator>(
+            &cyclic,
+            "Variable definitions form a cycle [\"phi\", \"phi\"]",
+        );
+    }
+
+    // Test evaluation without modifying Calculator
+    #[test]
+    fn test_evaluate() {
+        let mut calculator = Calculator::new();
+        calculator.set_variable("theta", 0.5);
+        assert_eq!(calculator.evaluate("2*theta + 1"), Ok(2.0));
+        assert_eq!(
+            calculator.evaluate("x=theta; x"),
+            Err(CalculatorError::AssignmentNotAllowed {
+                name: "x".to_string()
+            })
+        );
+        assert!(calculator.get_variable("x").is_err());
+        assert_eq!(
+            calculator.evaluate_float(&CalculatorFloat::from("theta*4")),
+            Ok(2.0)
+        );
+        assert_eq!(
+            calculator.evaluate_float(&CalculatorFloat::from(3)),
+            Ok(3.0)
+        );
+        assert_eq!(calculator.execute("x=theta; y=x*2; y"), Ok(1.0));
+        assert_eq!(calculator.evaluate("y"), Ok(1.0));
+        calculator.enable_cache(4);
+        assert_eq!(calculator.evaluate("x + y"), Ok(1.5));
+        assert_eq!(calculator.evaluate("x + y"), Ok(1.5));
+        assert_eq!(calculator.cache_statistics().unwrap().hits, 1);
+    }
+
+    // Test evaluation of a shared Calculator from several threads
+    #[test]
+    fn test_evaluate_threads() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<Calculator>();
+        let mut calculator = Calculator::new();
+        calculator.set_variable("theta", 0.25);
+        calculator.enable_cache(8);
+        let calculator = Arc::new(calculator);
+        let handles: Vec<_> = (0..4)
+            .map(|i| {
+                let calculator = Arc::clone(&calculator);
+                thread::spawn(move || calculator.evaluate(&format!("theta * {}", i)))
+            })
+            .collect();
+        for (i, handle) in handles.into_iter().enumerate() {
+            assert_eq!(handle.join().unwrap(), Ok(0.25 * i as f64));
+        }
+        assert_eq!(calculator.cache_statistics().unwrap().misses, 4);
+    }
+
     // Test parse_string for a variable Token
     #[test]
     fn test_parse_variable() {
@@ -1081,6 +1934,69 @@ mod tests {
         assert!(value.is_err());
     }
 
+    // Test that strict mode turns NaN and infinite intermediates into errors
+    #[test]
+    fn test_strict_mode() {
+        let mut calculator = Calculator::new();
+        assert!(!calculator.is_strict());
+        assert!(calculator.parse_str("sqrt(-1)").unwrap().is_nan());
+        assert!(calculator.parse_str("exp(1000)").unwrap().is_infinite());
+
+        calculator.set_strict(true);
+        assert!(calculator.is_strict());
+        assert_eq!(
+            calculator.parse_str("sqrt(-1)"),
+            Err(CalculatorError::DomainError {
+                fct: String::from("sqrt"),
+                arguments: vec![-1.0]
+            })
+        );
+        assert_eq!(
+            calculator.parse_str("log(0)"),
+            Err(CalculatorError::DomainError {
+                fct: String::from("log"),
+                arguments: vec![0.0]
+            })
+        );
+        assert_eq!(
+            calculator.parse_str("acos(2)"),
+            Err(CalculatorError::DomainError {
+                fct: String::from("acos"),
+                arguments: vec![2.0]
+            })
+        );
+        assert_eq!(
+            calculator.parse_str("exp(1000)"),
+            Err(CalculatorError::Overflow {
+                fct: String::from("exp"),
+                arguments: vec![1000.0]
+            })
+        );
+        assert_eq!(
+            calculator.parse_str("1e300 * 1e300"),
+            Err(CalculatorError::Overflow {
+                fct: String::from("mul"),
+                arguments: vec![1e300, 1e300]
+            })
+        );
+        assert_eq!(
+            calculator.parse_str("(-1)^0.5"),
+            Err(CalculatorError::DomainError {
+                fct: String::from("pow"),
+                arguments: vec![-1.0, 0.5]
+            })
+        );
+        assert_eq!(
+            calculator.parse_str("pow(0, -1)"),
+            Err(CalculatorError::DomainError {
+                fct: String::from("pow"),
+                arguments: vec![0.0, -1.0]
+            })
+        );
+        calculator.set_variable("x", 4.0);
+        assert_eq!(calculator.parse_str("sqrt(x) + log(x/4)").unwrap(), 2.0);
+    }
+
     // Testing that all functions get matched with the correct nummber of arguments (1 or 2)
     #[test]
     fn test_function_argument_numbers() {
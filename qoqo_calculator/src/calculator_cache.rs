@@ -0,0 +1,515 @@
+// Copyright © 2020-2021 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! calculator_cache module
+//!
+//! Provides an optional bounded cache of compiled string expressions for Calculator.
+
+use crate::calculator::{Evaluator, Parser};
+use crate::{Calculator, CalculatorError};
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// Single instruction of a compiled expression.
+///
+/// Compiled expressions are executed on a value stack in the order
+/// the Parser evaluates the original expression.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Instruction {
+    /// Push a number
+    Number(f64),
+    /// Push the value of a variable
+    Variable(String),
+    /// Assign the top of the stack to a variable
+    Assign(String),
+    /// Replace the two top values with their sum
+    Add,
+    /// Replace the two top values with their difference
+    Sub,
+    /// Replace the two top values with their product
+    Mul,
+    /// Replace the two top values with their quotient
+    Div,
+    /// Replace the two top values with their power
+    Pow,
+    /// Negate the top value
+    Neg,
+    /// Apply function with one argument to the top value
+    Function1(String),
+    /// Apply function with two arguments to the two top values
+    Function2(String),
+}
+
+/// Compiled form of a string expression.
+pub(crate) type Program = Arc<[Instruction]>;
+
+/// Evaluator recording the operations of an expression instead of computing them.
+///
+/// The Parser only hands out shared references for most operations,
+/// so instructions are collected in a RefCell.
+struct Compiler {
+    instructions: RefCell<Vec<Instruction>>,
+}
+
+impl Compiler {
+    /// Append instruction to the compiled expression.
+    fn push(&self, instruction: Instruction) -> Result<(), CalculatorError> {
+        self.instructions.borrow_mut().push(instruction);
+        Ok(())
+    }
+}
+
+impl Evaluator for Compiler {
+    type Value = ();
+
+    fn number(&self, value: f64) -> Result<(), CalculatorError> {
+        self.push(Instruction::Number(value))
+    }
+
+    fn variable(&self, name: &str) -> Result<(), CalculatorError> {
+        self.push(Instruction::Variable(name.to_string()))
+    }
+
+    fn assign(&mut self, name: &str, _value: ()) -> Result<(), CalculatorError> {
+        self.push(Instruction::Assign(name.to_string()))
+    }
+
+    fn add(&self, _lhs: (), _rhs: ()) -> Result<(), CalculatorError> {
+        self.push(Instruction::Add)
+    }
+
+    fn sub(&self, _lhs: (), _rhs: ()) -> Result<(), CalculatorError> {
+        self.push(Instruction::Sub)
+    }
+
+    fn mul(&self, _lhs: (), _rhs: ()) -> Result<(), CalculatorError> {
+        self.push(Instruction::Mul)
+    }
+
+    fn div(&self, _lhs: (), _rhs: ()) -> Result<(), CalculatorError> {
+        self.push(Instruction::Div)
+    }
+
+    fn pow(&self, _lhs: (), _rhs: ()) -> Result<(), CalculatorError> {
+        self.push(Instruction::Pow)
+    }
+
+    fn neg(&self, _value: ()) -> Result<(), CalculatorError> {
+        self.push(Instruction::Neg)
+    }
+
+    fn function_1_argument(&self, fct: &str, _arg0: ()) -> Result<(), CalculatorError> {
+        self.push(Instruction::Function1(fct.to_string()))
+    }
+
+    fn function_2_arguments(&self, fct: &str, _arg0: (), _arg1: ()) -> Result<(), CalculatorError> {
+        self.push(Instruction::Function2(fct.to_string()))
+    }
+}
+
+/// Compile string expression to a sequence of instructions.
+///
+/// # Arguments
+///
+/// * `expression` - Expression that is compiled
+///
+/// # Returns
+///
+/// * `Ok(Program)` - Compiled expression
+/// * `Err(CalculatorError)` - Expression is not syntactically valid
+pub(crate) fn compile(expression: &str) -> Result<Program, CalculatorError> {
+    let mut compiler = Compiler {
+        instructions: RefCell::new(Vec::new()),
+    };
+    let mut parser = Parser::new(expression, &mut compiler);
+    match parser.evaluate_all_tokens()? {
+        None => Err(CalculatorError::NoValueReturnedParsing),
+        Some(()) => Ok(compiler.instructions.into_inner().into()),
+    }
+}
+
+/// Hit and miss statistics of the expression cache of a Calculator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CacheStatistics {
+    /// Number of expressions found in the cache
+    pub hits: u64,
+    /// Number of expressions that had to be parsed
+    pub misses: u64,
+    /// Number of compiled expressions removed to stay within capacity
+    pub evictions: u64,
+    /// Number of compiled expressions currently in the cache
+    pub len: usize,
+    /// Maximum number of compiled expressions in the cache
+    pub capacity: usize,
+}
+
+/// Bounded cache of compiled expressions evicting the least recently used entry.
+///
+/// # Fields
+///
+/// * `capacity` - Maximum number of cached expressions
+/// * `entries` - Compiled expressions and the time of their last use
+/// * `recency` - Cached expressions ordered by the time of their last use
+/// * `clock` - Counter increased on every access
+/// * `hits` - Number of cache hits
+/// * `misses` - Number of cache misses
+/// * `evictions` - Number of evicted expressions
+///
+#[derive(Debug, Clone)]
+pub(crate) struct ExpressionCache {
+    capacity: usize,
+    entries: HashMap<String, (Program, u64)>,
+    recency: BTreeMap<u64, String>,
+    clock: u64,
+    hits: u64,
+    misses: u64,
+    evictions: u64,
+}
+
+impl ExpressionCache {
+    /// Create new empty ExpressionCache holding at most `capacity` expressions.
+    pub(crate) fn new(capacity: usize) -> Self {
+        ExpressionCache {
+            capacity,
+            entries: HashMap::new(),
+            recency: BTreeMap::new(),
+            clock: 0,
+            hits: 0,
+            misses: 0,
+            evictions: 0,
+        }
+    }
+
+    /// Return compiled expression, compiling and storing it on a miss.
+    ///
+    /// Expressions that fail to compile are not stored.
+    pub(crate) fn get_or_compile(&mut self, expression: &str) -> Result<Program, CalculatorError> {
+        self.clock += 1;
+        if let Some((program, last_used)) = self.entries.get_mut(expression) {
+            self.hits += 1;
+            self.recency.remove(last_used);
+            *last_used = self.clock;
+            self.recency.insert(self.clock, expression.to_string());
+            return Ok(program.clone());
+        }
+        self.misses += 1;
+        let program = compile(expression)?;
+        if self.capacity == 0 {
+            return Ok(program);
+        }
+        if self.entries.len() >= self.capacity {
+            if let Some((&oldest, _)) = self.recency.iter().next() {
+                if let Some(evicted) = self.recency.remove(&oldest) {
+                    self.entries.remove(&evicted);
+                    self.evictions += 1;
+                }
+            }
+        }
+        self.entries
+            .insert(expression.to_string(), (program.clone(), self.clock));
+        self.recency.insert(self.clock, expression.to_string());
+        Ok(program)
+    }
+
+    /// Remove all compiled expressions, keeping the statistics.
+    pub(crate) fn clear(&mut self) {
+        self.entries.clear();
+        self.recency.clear();
+    }
+
+    /// Return current statistics of the cache.
+    pub(crate) fn statistics(&self) -> CacheStatistics {
+        CacheStatistics {
+            hits: self.hits,
+            misses: self.misses,
+            evictions: self.evictions,
+            len: self.entries.len(),
+            capacity: self.capacity,
+        }
+    }
+}
+
+/// ExpressionCache that can be used through shared references of Calculator.
+#[derive(Debug)]
+pub(crate) struct SharedCache(Mutex<ExpressionCache>);
+
+impl SharedCache {
+    /// Create new empty SharedCache holding at most `capacity` expressions.
+    pub(crate) fn new(capacity: usize) -> Self {
+        SharedCache(Mutex::new(ExpressionCache::new(capacity)))
+    }
+
+    /// Return locked ExpressionCache.
+    ///
+    /// A panic while the cache was locked can at most leave an entry or a statistic
+    /// out of date, so a poisoned lock is used as is.
+    pub(crate) fn lock(&self) -> MutexGuard<'_, ExpressionCache> {
+        self.0
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Return ExpressionCache without locking.
+    pub(crate) fn get_mut(&mut self) -> &mut ExpressionCache {
+        self.0
+            .get_mut()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Clone for SharedCache {
+    fn clone(&self) -> Self {
+        SharedCache(Mutex::new(self.lock().clone()))
+    }
+}
+
+impl Calculator {
+    /// Enable cache of compiled expressions for Calculator.
+    ///
+    /// With the cache enabled every distinct expression string passed to
+    /// parse_str, parse_get or evaluate is parsed once and afterwards executed from its
+    /// compiled form. Variables are looked up when the compiled expression is
+    /// executed, so changing variables never invalidates the cache.
+    /// Enabling the cache again replaces the existing cache and its statistics.
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - Maximum number of cached expressions, the least recently used one is evicted first
+    ///
+    pub fn enable_cache(&mut self, capacity: usize) {
+        self.cache = Some(SharedCache::new(capacity));
+    }
+
+    /// Disable and drop cache of compiled expressions.
+    pub fn disable_cache(&mut self) {
+        self.cache = None;
+    }
+
+    /// Remove all compiled expressions from cache, keeping its statistics.
+    pub fn clear_cache(&mut self) {
+        if let Some(cache) = self.cache.as_mut() {
+            cache.get_mut().clear();
+        }
+    }
+
+    /// Return compiled expression, using the cache when it is enabled.
+    pub(crate) fn compiled(&self, expression: &str) -> Result<Program, CalculatorError> {
+        match self.cache.as_ref() {
+            Some(cache) => cache.lock().get_or_compile(expression),
+            None => compile(expression),
+        }
+    }
+
+    /// Return statistics of the expression cache or None when the cache is disabled.
+    pub fn cache_statistics(&self) -> Option<CacheStatistics> {
+        self.cache.as_ref().map(|cache| cache.lock().statistics())
+    }
+}
+
+/// Execute compiled expression with an evaluator.
+///
+/// # Arguments
+///
+/// * `evaluator` - Evaluator providing variables and operations
+/// * `program` - Compiled expression
+///
+/// # Returns
+///
+/// * `Ok(E::Value)` - Value of the last statement in the expression
+/// * `Err(CalculatorError)` - Error of the first failing operation
+pub(crate) fn execute<E>(
+    evaluator: &mut E,
+    program: &[Instruction],
+) -> Result<E::Value, CalculatorError>
+where
+    E: Evaluator,
+{
+    let mut stack: Vec<E::Value> = Vec::new();
+    for instruction in program {
+        let value = match instruction {
+            Instruction::Number(x) => evaluator.number(*x)?,
+            Instruction::Variable(name) => evaluator.variable(name)?,
+            Instruction::Assign(name) => {
+                let value = pop(&mut stack)?;
+                evaluator.assign(name, value)?
+            }
+            Instruction::Neg => {
+                let value = pop(&mut stack)?;
+                evaluator.neg(value)?
+            }
+            Instruction::Function1(fct) => {
+                let arg0 = pop(&mut stack)?;
+                Evaluator::function_1_argument(evaluator, fct, arg0)?
+            }
+            _ => {
+                let rhs = pop(&mut stack)?;
+                let lhs = pop(&mut stack)?;
+                match instruction {
+                    Instruction::Add => evaluator.add(lhs, rhs)?,
+                    Instruction::Sub => evaluator.sub(lhs, rhs)?,
+                    Instruction::Mul => evaluator.mul(lhs, rhs)?,
+                    Instruction::Div => evaluator.div(lhs, rhs)?,
+                    Instruction::Pow => evaluator.pow(lhs, rhs)?,
+                    Instruction::Function2(fct) => {
+                        Evaluator::function_2_arguments(evaluator, fct, lhs, rhs)?
+                    }
+                    _ => unreachable!("All other instructions are handled above"),
+                }
+            }
+        };
+        stack.push(value);
+    }
+    stack.pop().ok_or(CalculatorError::NoValueReturnedParsing)
+}
+
+/// Remove top value from stack of an executed compiled expression.
+fn pop<T>(stack: &mut Vec<T>) -> Result<T, CalculatorError> {
+    stack.pop().ok_or(CalculatorError::ParsingError {
+        msg: "Malformed compiled expression",
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{compile, CacheStatistics, Instruction};
+    use crate::{Calculator, CalculatorError, CalculatorFloat};
+
+    // Test compiled expressions match direct parsing
+    #[test]
+    fn compiled_matches_parsed() {
+        let expressions = [
+            "theta_0",
+            "2*theta_0",
+            "-theta_0^2 + 1e-3",
+            "sin(theta_0) * cos(2 / theta_0) - atan2(theta_0, 3)",
+            "a=theta_0*2; b=a+1; a*b",
+            "max(theta_0, -1) + sqrt(abs(-4))",
+            "(theta_0 + 1) / (theta_0 - 1)",
+        ];
+        let mut direct = Calculator::new();
+        direct.set_variable("theta_0", 0.3);
+        let mut cached = direct.clone();
+        cached.enable_cache(16);
+        for expression in expressions.iter() {
+            for _ in 0..2 {
+                assert_eq!(cached.parse_str(expression), direct.parse_str(expression));
+            }
+        }
+        assert_eq!(cached.get_variable("b"), direct.get_variable("b"));
+    }
+
+    // Test compiled expressions return the same errors as direct parsing
+    #[test]
+    fn compiled_errors() {
+        let expressions = [
+            "x + 1",
+            "1 / (theta_0 - theta_0)",
+            "sin(1",
+            "2 ++* y",
+            "log(0)",
+        ];
+        let mut direct = Calculator::new();
+        direct.set_variable("theta_0", 0.3);
+        direct.set_strict(true);
+        let mut cached = direct.clone();
+        cached.enable_cache(16);
+        for expression in expressions.iter() {
+            for _ in 0..2 {
+                assert_eq!(cached.parse_str(expression), direct.parse_str(expression));
+            }
+        }
+        assert_eq!(
+            cached.parse_str("x + 1"),
+            Err(CalculatorError::VariableNotSet {
+                name: "x".to_string()
+            })
+        );
+    }
+
+    // Test compiled instructions of an expression
+    #[test]
+    fn compile_instructions() {
+        let program = compile("a=-x*2").unwrap();
+        assert_eq!(
+            &program[..],
+            &[
+                Instruction::Variable("x".to_string()),
+                Instruction::Neg,
+                Instruction::Number(2.0),
+                Instruction::Mul,
+                Instruction::Assign("a".to_string()),
+            ]
+        );
+        assert!(compile("sin(x").is_err());
+    }
+
+    // Test cache statistics and variable updates between cache hits
+    #[test]
+    fn statistics() {
+        let mut calculator = Calculator::new();
+        assert_eq!(calculator.cache_statistics(), None);
+        calculator.enable_cache(4);
+        for i in 0..3 {
+            calculator.set_variable("theta_0", i as f64);
+            assert_eq!(
+                calculator.parse_get(CalculatorFloat::from("2*theta_0")),
+                Ok(2.0 * i as f64)
+            );
+        }
+        assert_eq!(calculator.parse_get(CalculatorFloat::from(1.5)), Ok(1.5));
+        assert!(calculator.parse_str("sin(").is_err());
+        assert_eq!(
+            calculator.cache_statistics(),
+            Some(CacheStatistics {
+                hits: 2,
+                misses: 2,
+                evictions: 0,
+                len: 1,
+                capacity: 4
+            })
+        );
+        calculator.clear_cache();
+        let statistics = calculator.cache_statistics().unwrap();
+        assert_eq!((statistics.len, statistics.hits), (0, 2));
+        calculator.disable_cache();
+        assert_eq!(calculator.cache_statistics(), None);
+        assert_eq!(calculator.parse_str("2*theta_0"), Ok(4.0));
+    }
+
+    // Test eviction of least recently used expression
+    #[test]
+    fn eviction() {
+        let mut calculator = Calculator::new();
+        calculator.enable_cache(2);
+        calculator.parse_str("1 + 1").unwrap();
+        calculator.parse_str("2 + 2").unwrap();
+        calculator.parse_str("1 + 1").unwrap();
+        calculator.parse_str("3 + 3").unwrap();
+        calculator.parse_str("1 + 1").unwrap();
+        calculator.parse_str("2 + 2").unwrap();
+        assert_eq!(
+            calculator.cache_statistics(),
+            Some(CacheStatistics {
+                hits: 2,
+                misses: 4,
+                evictions: 2,
+                len: 2,
+                capacity: 2
+            })
+        );
+        calculator.enable_cache(0);
+        assert_eq!(calculator.parse_str("1 + 1"), Ok(2.0));
+        let statistics = calculator.cache_statistics().unwrap();
+        assert_eq!((statistics.misses, statistics.len), (1, 0));
+    }
+}
@@ -195,17 +195,42 @@ impl CalculatorComplex {
         }
     }
 
+    /// Return CalculatorComplex constructed from polar coordinates r * exp(i * phi).
+    ///
+    /// # Arguments
+    ///
+    /// * `r` - Modulus given as type that can be converted to CalculatorFloat
+    /// * `phi` - Phase given as type that can be converted to CalculatorFloat
+    ///
+    pub fn from_polar<T1, T2>(r: T1, phi: T2) -> Self
+    where
+        CalculatorFloat: From<T1>,
+        CalculatorFloat: From<T2>,
+    {
+        let r = CalculatorFloat::from(r);
+        let phi = CalculatorFloat::from(phi);
+        Self {
+            re: r.clone() * phi.cos(),
+            im: r * phi.sin(),
+        }
+    }
+
+    /// Return polar coordinates (r, phi) of complex number x = r * exp(i * phi).
+    pub fn to_polar(&self) -> (CalculatorFloat, CalculatorFloat) {
+        (self.norm(), self.arg())
+    }
+
     /// Return phase of complex number x: arg(x).
     pub fn arg(&self) -> CalculatorFloat {
         self.im.atan2(&self.re)
     }
     /// Return square norm of complex number x: |x|^2=x.re^2+x.im^2.
     pub fn norm_sqr(&self) -> CalculatorFloat {
-        (self.re.clone() * &self.re) + (self.im.clone() * &self.im)
+        self.re.mul_ref(&self.re) + self.im.mul_ref(&self.im)
     }
     /// Return norm of complex number x: |x|=(x.re^2+x.im^2)^1/2.
     pub fn norm(&self) -> CalculatorFloat {
-        ((self.re.clone() * &self.re) + (self.im.clone() * &self.im)).sqrt()
+        self.norm_sqr().sqrt()
     }
     /// Return complex conjugate of x: x*=x.re-i*x.im.
     pub fn conj(&self) -> CalculatorComplex {
@@ -222,6 +247,186 @@ impl CalculatorComplex {
         let other_from = Self::from(other);
         self.re.isclose(other_from.re) && self.im.isclose(other_from.im)
     }
+
+    /// Return Complex<f64> value when both parts are numeric.
+    fn to_complex_float(&self) -> Option<Complex<f64>> {
+        match (&self.re, &self.im) {
+            (CalculatorFloat::Float(re), CalculatorFloat::Float(im)) => {
+                Some(Complex::new(*re, *im))
+            }
+            _ => None,
+        }
+    }
+
+    /// Return exponential function exp(x) for CalculatorComplex.
+    pub fn exp(&self) -> CalculatorComplex {
+        match self.to_complex_float() {
+            Some(x) => CalculatorComplex::from(x.exp()),
+            None => {
+                let modulus = self.re.exp();
+                CalculatorComplex {
+                    re: modulus.clone() * self.im.cos(),
+                    im: modulus * self.im.sin(),
+                }
+            }
+        }
+    }
+
+    /// Return principal natural logarithm ln(x) for CalculatorComplex.
+    pub fn ln(&self) -> CalculatorComplex {
+        match self.to_complex_float() {
+            Some(x) => CalculatorComplex::from(x.ln()),
+            None => CalculatorComplex {
+                re: self.norm().ln(),
+                im: self.arg(),
+            },
+        }
+    }
+
+    /// Return principal square root sqrt(x) for CalculatorComplex.
+    pub fn sqrt(&self) -> CalculatorComplex {
+        match self.to_complex_float() {
+            Some(x) => CalculatorComplex::from(x.sqrt()),
+            None => {
+                let half_arg = self.arg() / 2;
+                let modulus = self.norm().sqrt();
+                CalculatorComplex {
+                    re: modulus.clone() * half_arg.cos(),
+                    im: modulus * half_arg.sin(),
+                }
+            }
+        }
+    }
+
+    /// Return power x^y of CalculatorComplex with a real exponent.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - Exponent given as any type T for which CalculatorFloat::From<T> trait is implemented
+    ///
+    pub fn powf<T>(&self, other: T) -> CalculatorComplex
+    where
+        CalculatorFloat: From<T>,
+    {
+        self.powf_float(CalculatorFloat::from(other))
+    }
+
+    /// Return power x^y of CalculatorComplex with a complex exponent.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - Exponent given as any type T for which CalculatorComplex::From<T> trait is implemented
+    ///
+    pub fn powc<T>(&self, other: T) -> CalculatorComplex
+    where
+        CalculatorComplex: From<T>,
+    {
+        self.powc_complex(CalculatorComplex::from(other))
+    }
+
+    /// Return power x^y of CalculatorComplex with a real CalculatorFloat exponent.
+    fn powf_float(&self, exponent: CalculatorFloat) -> CalculatorComplex {
+        match (self.to_complex_float(), &exponent) {
+            (Some(x), CalculatorFloat::Float(y)) => CalculatorComplex::from(x.powf(*y)),
+            _ => {
+                let phase = self.arg() * &exponent;
+                let modulus = self.norm().powf(exponent);
+                CalculatorComplex {
+                    re: modulus.clone() * phase.cos(),
+                    im: modulus * phase.sin(),
+                }
+            }
+        }
+    }
+
+    /// Return power x^y of CalculatorComplex with a CalculatorComplex exponent.
+    fn powc_complex(&self, exponent: CalculatorComplex) -> CalculatorComplex {
+        match (self.to_complex_float(), exponent.to_complex_float()) {
+            (Some(x), Some(y)) => CalculatorComplex::from(x.powc(y)),
+            _ => (exponent * self.ln()).exp(),
+        }
+    }
+
+    /// Return sine function sin(x) for CalculatorComplex.
+    pub fn sin(&self) -> CalculatorComplex {
+        match self.to_complex_float() {
+            Some(x) => CalculatorComplex::from(x.sin()),
+            None => CalculatorComplex {
+                re: self.re.sin() * self.im.cosh(),
+                im: self.re.cos() * self.im.sinh(),
+            },
+        }
+    }
+
+    /// Return cosine function cos(x) for CalculatorComplex.
+    pub fn cos(&self) -> CalculatorComplex {
+        match self.to_complex_float() {
+            Some(x) => CalculatorComplex::from(x.cos()),
+            None => CalculatorComplex {
+                re: self.re.cos() * self.im.cosh(),
+                im: -(self.re.sin() * self.im.sinh()),
+            },
+        }
+    }
+
+    /// Return hyperbolic sine function sinh(x) for CalculatorComplex.
+    pub fn sinh(&self) -> CalculatorComplex {
+        match self.to_complex_float() {
+            Some(x) => CalculatorComplex::from(x.sinh()),
+            None => CalculatorComplex {
+                re: self.re.sinh() * self.im.cos(),
+                im: self.re.cosh() * self.im.sin(),
+            },
+        }
+    }
+
+    /// Return hyperbolic cosine function cosh(x) for CalculatorComplex.
+    pub fn cosh(&self) -> CalculatorComplex {
+        match self.to_complex_float() {
+            Some(x) => CalculatorComplex::from(x.cosh()),
+            None => CalculatorComplex {
+                re: self.re.cosh() * self.im.cos(),
+                im: self.re.sinh() * self.im.sin(),
+            },
+        }
+    }
+
+    /// Return sum of CalculatorComplex and CalculatorComplex reference.
+    pub(crate) fn add_ref(&self, other: &CalculatorComplex) -> CalculatorComplex {
+        CalculatorComplex {
+            re: self.re.add_ref(&other.re),
+            im: self.im.add_ref(&other.im),
+        }
+    }
+
+    /// Return difference of CalculatorComplex and CalculatorComplex reference.
+    pub(crate) fn sub_ref(&self, other: &CalculatorComplex) -> CalculatorComplex {
+        CalculatorComplex {
+            re: self.re.sub_ref(&other.re),
+            im: self.im.sub_ref(&other.im),
+        }
+    }
+
+    /// Return product of CalculatorComplex and CalculatorComplex reference.
+    pub(crate) fn mul_ref(&self, other: &CalculatorComplex) -> CalculatorComplex {
+        CalculatorComplex {
+            re: self.re.mul_ref(&other.re) - self.im.mul_ref(&other.im),
+            im: self.re.mul_ref(&other.im) + self.im.mul_ref(&other.re),
+        }
+    }
+
+    /// Return quotient of CalculatorComplex and CalculatorComplex reference.
+    ///
+    /// # Panics
+    ///
+    /// Panics on division by zero.
+    pub(crate) fn div_ref(&self, other: &CalculatorComplex) -> CalculatorComplex {
+        let norm = other.norm_sqr();
+        CalculatorComplex {
+            re: (self.re.mul_ref(&other.re) + self.im.mul_ref(&other.im)).div_ref(&norm),
+            im: (self.im.mul_ref(&other.re) - self.re.mul_ref(&other.im)).div_ref(&norm),
+        }
+    }
 }
 
 /// Implement `+` for CalculatorComplex and generic type `T`.
@@ -236,11 +441,7 @@ where
 {
     type Output = Self;
     fn add(self, other: T) -> Self {
-        let other_from = Self::from(other);
-        CalculatorComplex {
-            re: self.re + other_from.re,
-            im: self.im + other_from.im,
-        }
+        self.add_ref(&Self::from(other))
     }
 }
 
@@ -260,6 +461,22 @@ impl std::iter::Sum for CalculatorComplex {
     }
 }
 
+/// Implements multiplying over an iterator of CalculatorComplex
+///
+/// # Arguments
+///
+/// * `iter` - Any iterator over CalculatorComplex items
+///
+impl std::iter::Product for CalculatorComplex {
+    fn product<I: Iterator<Item = CalculatorComplex>>(iter: I) -> Self {
+        let mut product = CalculatorComplex::new(1, 0);
+        for i in iter {
+            product *= i;
+        }
+        product
+    }
+}
+
 /// Implement `+=` for CalculatorComplex and generic type `T`.
 ///
 /// # Arguments
@@ -271,11 +488,7 @@ where
     CalculatorComplex: From<T>,
 {
     fn add_assign(&mut self, other: T) {
-        let other_from = Self::from(other);
-        *self = CalculatorComplex {
-            re: &self.re + other_from.re,
-            im: &self.im + other_from.im,
-        }
+        *self = self.add_ref(&Self::from(other));
     }
 }
 
@@ -291,11 +504,7 @@ where
 {
     type Output = Self;
     fn sub(self, other: T) -> Self {
-        let other_from = Self::from(other);
-        CalculatorComplex {
-            re: self.re - other_from.re,
-            im: self.im - other_from.im,
-        }
+        self.sub_ref(&Self::from(other))
     }
 }
 /// Implement `-=` for CalculatorComplex and generic type `T`.
@@ -309,14 +518,57 @@ where
     CalculatorComplex: From<T>,
 {
     fn sub_assign(&mut self, other: T) {
-        let other_from = Self::from(other);
-        *self = CalculatorComplex {
-            re: self.re.clone() - other_from.re,
-            im: self.im.clone() - other_from.im,
-        }
+        *self = self.sub_ref(&Self::from(other));
     }
 }
 
+/// Implement arithmetic operators with a left hand side that can be converted to CalculatorComplex.
+///
+/// Covers primitive numbers, Complex<f64> and CalculatorFloat on the left of a CalculatorComplex
+/// and CalculatorComplex on the right of a CalculatorFloat, returning CalculatorComplex.
+macro_rules! impl_complex_lhs_ops {
+    ($lhs:ty, $rhs:ty) => {
+        impl ops::Add<$rhs> for $lhs {
+            type Output = CalculatorComplex;
+            fn add(self, other: $rhs) -> CalculatorComplex {
+                CalculatorComplex::from(self) + other
+            }
+        }
+
+        impl ops::Sub<$rhs> for $lhs {
+            type Output = CalculatorComplex;
+            fn sub(self, other: $rhs) -> CalculatorComplex {
+                CalculatorComplex::from(self) - other
+            }
+        }
+
+        impl ops::Mul<$rhs> for $lhs {
+            type Output = CalculatorComplex;
+            fn mul(self, other: $rhs) -> CalculatorComplex {
+                CalculatorComplex::from(self) * other
+            }
+        }
+
+        impl ops::Div<$rhs> for $lhs {
+            type Output = CalculatorComplex;
+            fn div(self, other: $rhs) -> CalculatorComplex {
+                CalculatorComplex::from(self) / other
+            }
+        }
+    };
+}
+
+impl_complex_lhs_ops!(f64, CalculatorComplex);
+impl_complex_lhs_ops!(i32, CalculatorComplex);
+impl_complex_lhs_ops!(u32, CalculatorComplex);
+impl_complex_lhs_ops!(Complex<f64>, CalculatorComplex);
+impl_complex_lhs_ops!(CalculatorFloat, CalculatorComplex);
+impl_complex_lhs_ops!(CalculatorFloat, &CalculatorComplex);
+impl_complex_lhs_ops!(CalculatorFloat, Complex<f64>);
+impl_complex_lhs_ops!(&CalculatorFloat, CalculatorComplex);
+impl_complex_lhs_ops!(&CalculatorFloat, &CalculatorComplex);
+impl_complex_lhs_ops!(&CalculatorFloat, Complex<f64>);
+
 /// Implement minus sign for CalculatorComplex.
 impl ops::Neg for CalculatorComplex {
     type Output = CalculatorComplex;
@@ -341,11 +593,7 @@ where
 {
     type Output = Self;
     fn mul(self, other: T) -> Self {
-        let other_from = Self::from(other);
-        CalculatorComplex {
-            re: self.re.clone() * &other_from.re - (self.im.clone() * &other_from.im),
-            im: self.re * &other_from.im + (self.im * &other_from.re),
-        }
+        self.mul_ref(&Self::from(other))
     }
 }
 /// Implement `*=` for CalculatorComplex and generic type `T`.
@@ -359,11 +607,7 @@ where
     CalculatorComplex: From<T>,
 {
     fn mul_assign(&mut self, other: T) {
-        let other_from = Self::from(other);
-        *self = CalculatorComplex {
-            re: self.re.clone() * &other_from.re - (self.im.clone() * &other_from.im),
-            im: self.re.clone() * &other_from.im + (self.im.clone() * &other_from.re),
-        }
+        *self = self.mul_ref(&Self::from(other));
     }
 }
 
@@ -379,12 +623,7 @@ where
 {
     type Output = Self;
     fn div(self, other: T) -> Self {
-        let other_from = Self::from(other);
-        let norm = other_from.norm_sqr();
-        CalculatorComplex {
-            re: (self.re.clone() * &other_from.re + (self.im.clone() * &other_from.im)) / &norm,
-            im: (-self.re * &other_from.im + (self.im * &other_from.re)) / &norm,
-        }
+        self.div_ref(&Self::from(other))
     }
 }
 /// Implement `*=` for CalculatorComplex and generic type `T`.
@@ -398,11 +637,82 @@ where
     CalculatorComplex: From<T>,
 {
     fn div_assign(&mut self, other: T) {
-        let other_from = Self::from(other);
-        let norm = other_from.norm_sqr();
-        *self = CalculatorComplex {
-            re: (self.re.clone() * &other_from.re + (self.im.clone() * &other_from.im)) / &norm,
-            im: (-self.re.clone() * &other_from.im + (self.im.clone() * &other_from.re)) / &norm,
+        *self = self.div_ref(&Self::from(other));
+    }
+}
+
+/// Implement `+` for &CalculatorComplex and generic type `T`.
+///
+/// # Arguments
+///
+/// * `other` - Any type T for which CalculatorComplex::From<T> trait is implemented
+///
+impl<T> ops::Add<T> for &CalculatorComplex
+where
+    CalculatorComplex: From<T>,
+{
+    type Output = CalculatorComplex;
+    fn add(self, other: T) -> CalculatorComplex {
+        self.add_ref(&CalculatorComplex::from(other))
+    }
+}
+
+/// Implement `-` for &CalculatorComplex and generic type `T`.
+///
+/// # Arguments
+///
+/// * `other` - Any type T for which CalculatorComplex::From<T> trait is implemented
+///
+impl<T> ops::Sub<T> for &CalculatorComplex
+where
+    CalculatorComplex: From<T>,
+{
+    type Output = CalculatorComplex;
+    fn sub(self, other: T) -> CalculatorComplex {
+        self.sub_ref(&CalculatorComplex::from(other))
+    }
+}
+
+/// Implement `*` for &CalculatorComplex and generic type `T`.
+///
+/// # Arguments
+///
+/// * `other` - Any type T for which CalculatorComplex::From<T> trait is implemented
+///
+impl<T> ops::Mul<T> for &CalculatorComplex
+where
+    CalculatorComplex: From<T>,
+{
+    type Output = CalculatorComplex;
+    fn mul(self, other: T) -> CalculatorComplex {
+        self.mul_ref(&CalculatorComplex::from(other))
+    }
+}
+
+/// Implement `/` for &CalculatorComplex and generic type `T`.
+///
+/// # Arguments
+///
+/// * `other` - Any type T for which CalculatorComplex::From<T> trait is implemented
+///
+impl<T> ops::Div<T> for &CalculatorComplex
+where
+    CalculatorComplex: From<T>,
+{
+    type Output = CalculatorComplex;
+    fn div(self, other: T) -> CalculatorComplex {
+        self.div_ref(&CalculatorComplex::from(other))
+    }
+}
+
+/// Implement minus sign for &CalculatorComplex.
+impl ops::Neg for &CalculatorComplex {
+    type Output = CalculatorComplex;
+
+    fn neg(self) -> CalculatorComplex {
+        CalculatorComplex {
+            re: -&self.re,
+            im: -&self.im,
         }
     }
 }
@@ -413,8 +723,8 @@ impl CalculatorComplex {
     pub fn recip(&self) -> CalculatorComplex {
         let norm = self.norm_sqr();
         CalculatorComplex {
-            re: self.re.clone() / &norm,
-            im: -self.im.clone() / &norm,
+            re: self.re.div_ref(&norm),
+            im: self.im.neg_ref().div_ref(&norm),
         }
     }
 }
@@ -423,6 +733,7 @@ impl CalculatorComplex {
 mod tests {
     use super::CalculatorComplex;
     use super::CalculatorFloat;
+    use crate::Calculator;
     use num_complex::Complex;
     use std::convert::TryFrom;
     use std::ops::Neg;
@@ -714,5 +1025,169 @@ mod tests {
         assert!(x1s == x2s);
         assert!(x2s == x1s);
     }
+
+    // Test the exponential function of CalculatorComplex for numeric and symbolic values
+    #[test]
+    fn exp() {
+        let x = CalculatorComplex::new(0.3, -1.2);
+        let c = Complex::new(0.3, -1.2);
+        assert_eq!(x.exp(), CalculatorComplex::from(c.exp()));
+        let phase = CalculatorComplex::new(0, "phi").exp();
+        assert_eq!(phase.re, CalculatorFloat::from("cos(phi)"));
+        assert_eq!(phase.im, CalculatorFloat::from("sin(phi)"));
+        let mut calculator = Calculator::new();
+        calculator.set_variable("a", 0.3);
+        calculator.set_variable("b", -1.2);
+        assert_complex_close(
+            &mut calculator,
+            CalculatorComplex::new("a", "b").exp(),
+            c.exp(),
+        );
+    }
+
+    // Test that the symbolic transcendental functions evaluate to the num_complex values
+    #[test]
+    fn transcendental_symbolic() {
+        let mut calculator = Calculator::new();
+        calculator.set_variable("a", -0.7);
+        calculator.set_variable("b", 0.4);
+        let x = CalculatorComplex::new("a", "b");
+        let c = Complex::new(-0.7, 0.4);
+        assert_complex_close(&mut calculator, x.ln(), c.ln());
+        assert_complex_close(&mut calculator, x.sqrt(), c.sqrt());
+        assert_complex_close(&mut calculator, x.powf(1.5), c.powf(1.5));
+        assert_complex_close(&mut calculator, x.powf("b"), c.powf(0.4));
+        assert_complex_close(
+            &mut calculator,
+            x.powc(CalculatorComplex::new(0.5, "a")),
+            c.powc(Complex::new(0.5, -0.7)),
+        );
+        assert_complex_close(&mut calculator, x.sin(), c.sin());
+        assert_complex_close(&mut calculator, x.cos(), c.cos());
+        assert_complex_close(&mut calculator, x.sinh(), c.sinh());
+        assert_complex_close(&mut calculator, x.cosh(), c.cosh());
+    }
+
+    // Test the transcendental functions of CalculatorComplex for numeric values
+    #[test]
+    fn transcendental_numeric() {
+        let x = CalculatorComplex::new(-0.7, 0.4);
+        let c = Complex::new(-0.7, 0.4);
+        assert_eq!(x.ln(), CalculatorComplex::from(c.ln()));
+        assert_eq!(x.sqrt(), CalculatorComplex::from(c.sqrt()));
+        assert_eq!(x.powf(1.5), CalculatorComplex::from(c.powf(1.5)));
+        assert_eq!(
+            x.powc(Complex::new(0.5, 2.0)),
+            CalculatorComplex::from(c.powc(Complex::new(0.5, 2.0)))
+        );
+        assert_eq!(x.sin(), CalculatorComplex::from(c.sin()));
+        assert_eq!(x.cos(), CalculatorComplex::from(c.cos()));
+        assert_eq!(x.sinh(), CalculatorComplex::from(c.sinh()));
+        assert_eq!(x.cosh(), CalculatorComplex::from(c.cosh()));
+        assert_eq!(
+            CalculatorComplex::new(-4, 0).sqrt(),
+            CalculatorComplex::new(0, 2)
+        );
+    }
+
+    /// Assert that symbolic CalculatorComplex evaluates close to expected value.
+    fn assert_complex_close(
+        calculator: &mut Calculator,
+        value: CalculatorComplex,
+        expected: Complex<f64>,
+    ) {
+        let re = calculator.parse_get(value.re).unwrap();
+        let im = calculator.parse_get(value.im).unwrap();
+        assert!((re - expected.re).abs() < 1e-12);
+        assert!((im - expected.im).abs() < 1e-12);
+    }
+
+    // Test the polar constructor and decomposition of CalculatorComplex
+    #[test]
+    fn polar() {
+        let x = CalculatorComplex::from_polar(2.0, std::f64::consts::FRAC_PI_2);
+        assert!(x.isclose(CalculatorComplex::new(0, 2)));
+        let (r, phi) = CalculatorComplex::new(3, 4).to_polar();
+        assert_eq!(r, CalculatorFloat::from(5));
+        assert_eq!(phi, CalculatorFloat::from(4.0_f64.atan2(3.0)));
+        let y = CalculatorComplex::from_polar("r", "phi");
+        assert_eq!(y.re, CalculatorFloat::from("(r * cos(phi))"));
+        assert_eq!(y.im, CalculatorFloat::from("(r * sin(phi))"));
+        let z = CalculatorComplex::from_polar(1, "phi");
+        assert_eq!(z, CalculatorComplex::new("cos(phi)", "sin(phi)"));
+        let mut calculator = Calculator::new();
+        calculator.set_variable("r", 1.5);
+        calculator.set_variable("phi", -2.0);
+        let (r, phi) = y.to_polar();
+        assert!((calculator.parse_get(r).unwrap() - 1.5).abs() < 1e-12);
+        assert!((calculator.parse_get(phi).unwrap() + 2.0).abs() < 1e-12);
+    }
+
+    // Test the product over an iterator of CalculatorComplex
+    #[test]
+    fn product() {
+        let values = vec![CalculatorComplex::new(0, 1), CalculatorComplex::new(2, 3)];
+        let product: CalculatorComplex = values.into_iter().product();
+        assert_eq!(product, CalculatorComplex::new(-3, 2));
+    }
+
+    // Test mixed arithmetic with CalculatorComplex on the right hand side
+    #[test]
+    fn mixed_lhs() {
+        let z = CalculatorComplex::new(1, 2);
+        assert_eq!(2.0 * z.clone(), CalculatorComplex::new(2, 4));
+        assert_eq!(2 + z.clone(), CalculatorComplex::new(3, 2));
+        assert_eq!(2u32 - z.clone(), CalculatorComplex::new(1, -2));
+        assert_eq!(5.0 / z.clone(), CalculatorComplex::new(1, -2));
+        assert_eq!(
+            Complex::new(0.0, 1.0) * z.clone(),
+            CalculatorComplex::new(-2, 1)
+        );
+        let x = CalculatorFloat::from("x");
+        assert_eq!(
+            x.clone() + z.clone(),
+            CalculatorComplex::new("(x + 1e0)", 2)
+        );
+        assert_eq!(x.clone() * &z, CalculatorComplex::new("x", "(x * 2e0)"));
+        assert_eq!(
+            CalculatorFloat::from(2) - Complex::new(1.0, 1.0),
+            CalculatorComplex::new(1, -1)
+        );
+        assert_eq!(
+            CalculatorFloat::from(2) / CalculatorComplex::I,
+            CalculatorComplex::new(0, -2)
+        );
+        assert_eq!(
+            z.clone() + x.clone(),
+            CalculatorComplex::new("(1e0 + x)", 2)
+        );
+        assert_eq!(z * Complex::new(0.0, 1.0), CalculatorComplex::new(-2, 1));
+    }
+
+    // Test that operators on references agree with operators on owned values
+    #[test]
+    fn reference_ops() {
+        let values = [
+            CalculatorComplex::new(0, 0),
+            CalculatorComplex::new(1, 0),
+            CalculatorComplex::new(2.5, -1),
+            CalculatorComplex::new("x", 1),
+            CalculatorComplex::new("x", "y"),
+        ];
+        for a in values.iter() {
+            for b in values[1..].iter() {
+                assert_eq!(a + b, a.clone() + b.clone());
+                assert_eq!(a - b, a.clone() - b.clone());
+                assert_eq!(a * b, a.clone() * b.clone());
+                assert_eq!(a / b, a.clone() / b.clone());
+                assert_eq!(a * b.clone(), a.clone() * b);
+            }
+            assert_eq!(-a, -a.clone());
+        }
+        let x = CalculatorFloat::from("x");
+        let z = CalculatorComplex::new(1, 2);
+        assert_eq!(&x * &z, x.clone() * z.clone());
+        assert_eq!(&x + z.clone(), x.clone() + z.clone());
+        assert_eq!(&x - Complex::new(1.0, 2.0), x - z);
+    }
 }
-// End of tests
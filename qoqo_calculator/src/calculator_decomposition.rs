@@ -0,0 +1,156 @@
+// Copyright © 2020-2021 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations underthe License.
+
+//! calculator_decomposition module
+//!
+//! Provides the Euler-angle decomposition of single-qubit unitaries with
+//! CalculatorComplex entries.
+
+use crate::{CalculatorComplex, CalculatorFloat};
+
+/// Return ZYZ Euler angles of a 2x2 unitary matrix.
+///
+/// The angles fulfill U = exp(i * alpha) * Rz(beta) * Ry(gamma) * Rz(delta) with
+/// Rz(theta) = diag(exp(-i * theta / 2), exp(i * theta / 2)) and
+/// Ry(theta) = [[cos(theta / 2), -sin(theta / 2)], [sin(theta / 2), cos(theta / 2)]].
+/// The global phase is fixed by the determinant, so the same formulas also hold when
+/// diagonal or off-diagonal entries vanish. Numeric matrices produce numeric angles,
+/// symbolic entries produce symbolic angles built from atan2, arg and norm.
+///
+/// # Arguments
+///
+/// * `matrix` - Unitary matrix given as rows of CalculatorComplex entries
+///
+/// # Returns
+///
+/// * `(alpha, beta, gamma, delta)` - Global phase and the three rotation angles
+pub fn zyz_decomposition(
+    matrix: &[[CalculatorComplex; 2]; 2],
+) -> (
+    CalculatorFloat,
+    CalculatorFloat,
+    CalculatorFloat,
+    CalculatorFloat,
+) {
+    let [[u00, u01], [u10, u11]] = matrix;
+    let determinant = u00.clone() * u11 - u01.clone() * u10;
+    let alpha = determinant.arg() / 2;
+    let arg_u10 = u10.arg();
+    let arg_u11 = u11.arg();
+    let beta = arg_u11.clone() + &arg_u10 - alpha.clone() * 2;
+    let delta = arg_u11 - arg_u10;
+    let gamma = u10.norm().atan2(u00.norm()) * 2;
+    (alpha, beta, gamma, delta)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::zyz_decomposition;
+    use crate::{Calculator, CalculatorComplex, CalculatorFloat};
+    use std::f64::consts::PI;
+
+    type Matrix = [[CalculatorComplex; 2]; 2];
+
+    /// Return matrix exp(i * alpha) * Rz(beta) * Ry(gamma) * Rz(delta).
+    fn rebuild(
+        alpha: &CalculatorFloat,
+        beta: &CalculatorFloat,
+        gamma: &CalculatorFloat,
+        delta: &CalculatorFloat,
+    ) -> Matrix {
+        let phase = |sign_beta: f64, sign_delta: f64| {
+            CalculatorComplex::from_polar(
+                1,
+                alpha.clone()
+                    + beta.clone() * (sign_beta / 2.0)
+                    + delta.clone() * (sign_delta / 2.0),
+            )
+        };
+        let cos = (gamma.clone() / 2).cos();
+        let sin = (gamma.clone() / 2).sin();
+        [
+            [phase(-1.0, -1.0) * &cos, phase(-1.0, 1.0) * (-sin.clone())],
+            [phase(1.0, -1.0) * sin, phase(1.0, 1.0) * cos],
+        ]
+    }
+
+    /// Assert that two matrices evaluate to the same numeric values.
+    fn assert_matrix_close(calculator: &mut Calculator, lhs: &Matrix, rhs: &Matrix) {
+        for (row_lhs, row_rhs) in lhs.iter().zip(rhs.iter()) {
+            for (l, r) in row_lhs.iter().zip(row_rhs.iter()) {
+                let re = calculator.parse_get(l.re.clone() - &r.re).unwrap();
+                let im = calculator.parse_get(l.im.clone() - &r.im).unwrap();
+                assert!(re.abs() < 1e-12 && im.abs() < 1e-12);
+            }
+        }
+    }
+
+    // Test round trip of numeric unitaries including vanishing entries
+    #[test]
+    fn round_trip_numeric() {
+        let mut calculator = Calculator::new();
+        let angles = vec![
+            (0.3, 1.1, 0.7, -2.4),
+            (-1.2, 2.9, 2.5, 0.4),
+            (0.5, 0.8, 0.0, 0.3),
+            (0.1, -0.6, PI, 1.7),
+            (2.0, 0.0, PI / 2.0, 0.0),
+        ];
+        for (a, b, g, d) in angles {
+            let matrix = rebuild(&a.into(), &b.into(), &g.into(), &d.into());
+            let (alpha, beta, gamma, delta) = zyz_decomposition(&matrix);
+            assert!(alpha.is_float() && beta.is_float() && gamma.is_float() && delta.is_float());
+            assert!(gamma.isclose(g));
+            let rebuilt = rebuild(&alpha, &beta, &gamma, &delta);
+            assert_matrix_close(&mut calculator, &matrix, &rebuilt);
+        }
+    }
+
+    // Test decomposition of standard gates
+    #[test]
+    fn gates() {
+        let mut calculator = Calculator::new();
+        let s = std::f64::consts::FRAC_1_SQRT_2;
+        let hadamard: Matrix = [
+            [CalculatorComplex::new(s, 0), CalculatorComplex::new(s, 0)],
+            [CalculatorComplex::new(s, 0), CalculatorComplex::new(-s, 0)],
+        ];
+        let (alpha, beta, gamma, delta) = zyz_decomposition(&hadamard);
+        assert!(gamma.isclose(PI / 2.0));
+        let rebuilt = rebuild(&alpha, &beta, &gamma, &delta);
+        assert_matrix_close(&mut calculator, &hadamard, &rebuilt);
+        let pauli_x: Matrix = [
+            [CalculatorComplex::ZERO, CalculatorComplex::ONE],
+            [CalculatorComplex::ONE, CalculatorComplex::ZERO],
+        ];
+        let (alpha, beta, gamma, delta) = zyz_decomposition(&pauli_x);
+        assert!(gamma.isclose(PI));
+        let rebuilt = rebuild(&alpha, &beta, &gamma, &delta);
+        assert_matrix_close(&mut calculator, &pauli_x, &rebuilt);
+    }
+
+    // Test round trip of symbolic unitary
+    #[test]
+    fn round_trip_symbolic() {
+        let matrix = rebuild(&"a".into(), &"b".into(), &"g".into(), &"d".into());
+        let (alpha, beta, gamma, delta) = zyz_decomposition(&matrix);
+        assert!(!gamma.is_float());
+        let rebuilt = rebuild(&alpha, &beta, &gamma, &delta);
+        let mut calculator = Calculator::new();
+        calculator.set_variable("a", 0.4);
+        calculator.set_variable("b", -1.3);
+        calculator.set_variable("g", 0.9);
+        calculator.set_variable("d", 2.2);
+        assert_matrix_close(&mut calculator, &matrix, &rebuilt);
+        assert!((calculator.parse_get(gamma).unwrap() - 0.9).abs() < 1e-12);
+    }
+}
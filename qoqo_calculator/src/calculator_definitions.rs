@@ -0,0 +1,273 @@
+// Copyright © 2020-2021 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! calculator_definitions module
+//!
+//! Provides variables of Calculator that are defined by expressions and evaluated lazily.
+
+use crate::calculator_cache::{compile, Instruction, Program};
+use crate::{Calculator, CalculatorError};
+use std::collections::{BTreeSet, HashSet};
+
+/// Definition of a variable by an expression.
+///
+/// # Fields
+///
+/// * `expression` - Expression defining the variable
+/// * `program` - Compiled expression
+/// * `dependencies` - Names of the variables used in the expression
+///
+#[derive(Debug, Clone)]
+pub(crate) struct VariableDefinition {
+    pub(crate) expression: String,
+    pub(crate) program: Program,
+    pub(crate) dependencies: BTreeSet<String>,
+}
+
+impl Calculator {
+    /// Define variable by an expression that is evaluated lazily.
+    ///
+    /// The expression is evaluated every time the variable is used, so the variable
+    /// follows all changes of the variables it depends on. Setting a value for the
+    /// variable with set_variable or an assignment replaces the definition.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name of the variable
+    /// * `expression` - Expression defining the variable
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - Variable has been defined
+    /// * `Err(CalculatorError::AssignmentNotAllowed)` - Expression assigns a variable
+    /// * `Err(CalculatorError::CircularDependency)` - Definition would make the variable depend on itself
+    /// * `Err(CalculatorError)` - Expression is not syntactically valid
+    pub fn set_expression(&mut self, name: &str, expression: &str) -> Result<(), CalculatorError> {
+        let program = compile(expression)?;
+        let mut dependencies = BTreeSet::new();
+        for instruction in program.iter() {
+            match instruction {
+                Instruction::Variable(variable) => {
+                    dependencies.insert(variable.clone());
+                }
+                Instruction::Assign(variable) => {
+                    return Err(CalculatorError::AssignmentNotAllowed {
+                        name: variable.clone(),
+                    })
+                }
+                _ => (),
+            }
+        }
+        if let Some(cycle) = self.find_cycle(name, &dependencies) {
+            return Err(CalculatorError::CircularDependency { cycle });
+        }
+        self.variables.remove(name);
+        self.definitions.insert(
+            name.to_string(),
+            VariableDefinition {
+                expression: expression.to_string(),
+                program,
+                dependencies,
+            },
+        );
+        Ok(())
+    }
+
+    /// Return expression defining a variable.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name of the variable
+    ///
+    /// # Returns
+    ///
+    /// `Option<&str>` - Expression or None when the variable is not defined by an expression
+    ///
+    pub fn get_expression(&self, name: &str) -> Option<&str> {
+        self.definitions
+            .get(name)
+            .map(|definition| definition.expression.as_str())
+    }
+
+    /// Remove definition of a variable by an expression.
+    ///
+    /// Variables whose definitions use the removed variable are kept and
+    /// fail to evaluate until the variable is set again.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name of the variable
+    ///
+    /// # Returns
+    ///
+    /// `Option<String>` - Removed expression or None when the variable was not defined by an expression
+    ///
+    pub fn remove_expression(&mut self, name: &str) -> Option<String> {
+        self.definitions
+            .remove(name)
+            .map(|definition| definition.expression)
+    }
+
+    /// Return iterator over names and expressions of all variables defined by expressions.
+    pub fn iter_expressions(&self) -> impl Iterator<Item = (&str, &str)> + '_ {
+        self.definitions
+            .iter()
+            .map(|(name, definition)| (name.as_str(), definition.expression.as_str()))
+    }
+
+    /// Return names of all variables depending directly or indirectly on a variable.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name of the variable
+    ///
+    /// # Returns
+    ///
+    /// `Vec<String>` - Sorted names of the variables whose definitions use the variable
+    ///
+    pub fn dependents(&self, name: &str) -> Vec<String> {
+        let mut dependents: BTreeSet<String> = BTreeSet::new();
+        let mut unvisited = vec![name];
+        while let Some(current) = unvisited.pop() {
+            for (defined, definition) in self.definitions.iter() {
+                if definition.dependencies.contains(current) && dependents.insert(defined.clone()) {
+                    unvisited.push(defined);
+                }
+            }
+        }
+        dependents.into_iter().collect()
+    }
+
+    /// Return cycle that defining `name` with `dependencies` would create.
+    fn find_cycle(&self, name: &str, dependencies: &BTreeSet<String>) -> Option<Vec<String>> {
+        let mut path = vec![name.to_string()];
+        let mut visited = HashSet::new();
+        for dependency in dependencies {
+            if self.visit(name, dependency, &mut path, &mut visited) {
+                return Some(path);
+            }
+        }
+        None
+    }
+
+    /// Depth-first search for `target` in the dependencies of `current`.
+    ///
+    /// Variables with a value are not followed, as their value takes
+    /// precedence over any definition.
+    fn visit<'a>(
+        &'a self,
+        target: &str,
+        current: &'a str,
+        path: &mut Vec<String>,
+        visited: &mut HashSet<&'a str>,
+    ) -> bool {
+        path.push(current.to_string());
+        if current == target {
+            return true;
+        }
+        if visited.insert(current) && !self.variables.contains_key(current) {
+            if let Some(definition) = self.definitions.get(current) {
+                for dependency in definition.dependencies.iter() {
+                    if self.visit(target, dependency, path, visited) {
+                        return true;
+                    }
+                }
+            }
+        }
+        path.pop();
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Calculator, CalculatorError};
+
+    // Test derived variables follow changes of base variables
+    #[test]
+    fn lazy_evaluation() {
+        let mut calculator = Calculator::new();
+        calculator.set_variable("theta", 0.5);
+        calculator.set_expression("phi", "2*theta").unwrap();
+        calculator.set_expression("psi", "phi + theta").unwrap();
+        assert_eq!(calculator.get_variable("phi"), Ok(1.0));
+        assert_eq!(calculator.parse_str("psi"), Ok(1.5));
+        calculator.set_variable("theta", 2.0);
+        assert_eq!(calculator.get_variable("phi"), Ok(4.0));
+        assert_eq!(calculator.parse_str("psi * 2"), Ok(12.0));
+        assert_eq!(calculator.get_expression("phi"), Some("2*theta"));
+        assert_eq!(calculator.get_expression("theta"), None);
+        assert_eq!(calculator.dependents("theta"), vec!["phi", "psi"]);
+        assert_eq!(calculator.dependents("psi"), Vec::<String>::new());
+    }
+
+    // Test definitions with unset variables and replacing definitions with values
+    #[test]
+    fn replace_definition() {
+        let mut calculator = Calculator::new();
+        calculator.set_expression("phi", "2*theta").unwrap();
+        assert_eq!(
+            calculator.get_variable("phi"),
+            Err(CalculatorError::VariableNotSet {
+                name: "theta".to_string()
+            })
+        );
+        calculator.set_variable("phi", 3.0);
+        assert_eq!(calculator.get_expression("phi"), None);
+        assert_eq!(calculator.get_variable("phi"), Ok(3.0));
+        calculator.set_expression("phi", "1 + 1").unwrap();
+        assert_eq!(calculator.parse_str("phi=5;phi"), Ok(5.0));
+        assert_eq!(calculator.get_expression("phi"), None);
+    }
+
+    // Test circular definitions are rejected
+    #[test]
+    fn circular_dependency() {
+        let mut calculator = Calculator::new();
+        assert_eq!(
+            calculator.set_expression("a", "a + 1"),
+            Err(CalculatorError::CircularDependency {
+                cycle: vec!["a".to_string(), "a".to_string()]
+            })
+        );
+        calculator.set_expression("a", "b * 2").unwrap();
+        calculator.set_expression("b", "sin(c)").unwrap();
+        assert_eq!(
+            calculator.set_expression("c", "1 + a"),
+            Err(CalculatorError::CircularDependency {
+                cycle: vec![
+                    "c".to_string(),
+                    "a".to_string(),
+                    "b".to_string(),
+                    "c".to_string()
+                ]
+            })
+        );
+        calculator.set_variable("b", 1.0);
+        calculator.set_expression("c", "1 + a").unwrap();
+        assert_eq!(calculator.get_variable("c"), Ok(3.0));
+    }
+
+    // Test invalid definitions are rejected
+    #[test]
+    fn invalid_definition() {
+        let mut calculator = Calculator::new();
+        assert_eq!(
+            calculator.set_expression("a", "b=1"),
+            Err(CalculatorError::AssignmentNotAllowed {
+                name: "b".to_string()
+            })
+        );
+        assert!(calculator.set_expression("a", "sin(1").is_err());
+        assert_eq!(calculator.get_expression("a"), None);
+    }
+}
@@ -0,0 +1,461 @@
+// Copyright © 2020-2021 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! calculator_dual module
+//!
+//! Provides forward-mode automatic differentiation of string expressions for Calculator
+//! using dual numbers.
+
+use crate::calculator::{Evaluator, Parser, ReadOnlyEvaluator};
+use crate::calculator_cache::execute;
+use crate::{Calculator, CalculatorError};
+use std::collections::HashMap;
+use std::f64::consts::{LN_10, LN_2};
+
+/// Return gradient * factor treating a vanishing gradient as exactly zero.
+///
+/// Avoids NaN contributions of infinite or undefined partial derivatives
+/// with respect to arguments that do not depend on any variable.
+fn scaled(gradient: f64, factor: f64) -> f64 {
+    if gradient == 0.0 {
+        0.0
+    } else {
+        gradient * factor
+    }
+}
+
+/// Return derivative of function with one argument at arg0.
+///
+/// # Arguments
+///
+/// * `fct` - Name of the function
+/// * `arg0` - Argument of the function
+/// * `value` - Value of the function at arg0
+///
+fn derivative_1_argument(fct: &str, arg0: f64, value: f64) -> Result<f64, CalculatorError> {
+    match fct {
+        "sin" => Ok(arg0.cos()),
+        "cos" => Ok(-arg0.sin()),
+        "abs" => Ok(if arg0 == 0.0 { 0.0 } else { arg0.signum() }),
+        "tan" => Ok(1.0 + value * value),
+        "acos" => Ok(-1.0 / (1.0 - arg0 * arg0).sqrt()),
+        "asin" => Ok(1.0 / (1.0 - arg0 * arg0).sqrt()),
+        "atan" => Ok(1.0 / (1.0 + arg0 * arg0)),
+        "cosh" => Ok(arg0.sinh()),
+        "sinh" => Ok(arg0.cosh()),
+        "tanh" => Ok(1.0 - value * value),
+        "acosh" | "arcosh" => Ok(1.0 / (arg0 * arg0 - 1.0).sqrt()),
+        "asinh" | "arsinh" => Ok(1.0 / (arg0 * arg0 + 1.0).sqrt()),
+        "atanh" | "artanh" => Ok(1.0 / (1.0 - arg0 * arg0)),
+        "exp" => Ok(value),
+        "exp2" => Ok(value * LN_2),
+        "expm1" => Ok(value + 1.0),
+        "log" => Ok(1.0 / arg0),
+        "log10" => Ok(1.0 / (arg0 * LN_10)),
+        "sqrt" => Ok(0.5 / value),
+        "cbrt" => Ok(1.0 / (3.0 * value * value)),
+        "fract" => Ok(1.0),
+        "ceil" | "floor" | "round" | "sign" | "delta" | "theta" => Ok(0.0),
+        _ => Err(CalculatorError::FunctionNotFound {
+            fct: fct.to_string(),
+        }),
+    }
+}
+
+/// Dual number holding a value and its gradient with respect to the chosen variables.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Dual {
+    /// Value of the expression
+    pub(crate) value: f64,
+    /// Partial derivatives of the expression
+    pub(crate) gradient: Vec<f64>,
+}
+
+impl Dual {
+    /// Return dual number with given value and gradient lhs_factor * lhs + rhs_factor * rhs.
+    fn combine(value: f64, lhs: &Dual, lhs_factor: f64, rhs: &Dual, rhs_factor: f64) -> Dual {
+        Dual {
+            value,
+            gradient: lhs
+                .gradient
+                .iter()
+                .zip(rhs.gradient.iter())
+                .map(|(l, r)| scaled(*l, lhs_factor) + scaled(*r, rhs_factor))
+                .collect(),
+        }
+    }
+
+    /// Return dual number with given value and gradient factor * self.
+    fn chain(&self, value: f64, factor: f64) -> Dual {
+        Dual {
+            value,
+            gradient: self.gradient.iter().map(|g| scaled(*g, factor)).collect(),
+        }
+    }
+}
+
+/// Evaluator for forward-mode automatic differentiation.
+///
+/// Values are computed by the Calculator, so strict mode applies to the
+/// evaluation as it does for [Calculator::parse_str].
+///
+/// # Fields
+///
+/// * `calculator` - Calculator providing variables and evaluating values
+/// * `variables` - Variables the gradient is computed for
+/// * `assigned` - Dual numbers of variables assigned during the evaluation
+///
+struct DualEvaluator<'a> {
+    calculator: &'a mut Calculator,
+    variables: &'a [&'a str],
+    assigned: HashMap<String, Dual>,
+}
+
+impl<'a> DualEvaluator<'a> {
+    /// Return dual number of a constant value.
+    fn constant(&self, value: f64) -> Dual {
+        Dual {
+            value,
+            gradient: vec![0.0; self.variables.len()],
+        }
+    }
+}
+
+impl<'a> Evaluator for DualEvaluator<'a> {
+    type Value = Dual;
+
+    fn number(&self, value: f64) -> Result<Dual, CalculatorError> {
+        Ok(self.constant(value))
+    }
+
+    fn variable(&self, name: &str) -> Result<Dual, CalculatorError> {
+        if let Some(value) = self.assigned.get(name) {
+            return Ok(value.clone());
+        }
+        let value = match self.calculator.stored_variable(name) {
+            Some(value) => value,
+            None => {
+                // Variables defined by expressions are differentiated through their definition
+                if let Some(definition) = self.calculator.definitions.get(name) {
+                    return execute(
+                        &mut ReadOnlyEvaluator { evaluator: self },
+                        &definition.program,
+                    );
+                }
+                self.calculator.get_variable(name)?
+            }
+        };
+        let mut value = self.constant(value);
+        for (index, variable) in self.variables.iter().enumerate() {
+            if *variable == name {
+                value.gradient[index] = 1.0;
+            }
+        }
+        Ok(value)
+    }
+
+    fn assign(&mut self, name: &str, value: Dual) -> Result<Dual, CalculatorError> {
+        self.calculator.set_variable(name, value.value);
+        self.assigned.insert(name.to_string(), value.clone());
+        Ok(value)
+    }
+
+    fn add(&self, lhs: Dual, rhs: Dual) -> Result<Dual, CalculatorError> {
+        let value = self.calculator.add(lhs.value, rhs.value)?;
+        Ok(Dual::combine(value, &lhs, 1.0, &rhs, 1.0))
+    }
+
+    fn sub(&self, lhs: Dual, rhs: Dual) -> Result<Dual, CalculatorError> {
+        let value = self.calculator.sub(lhs.value, rhs.value)?;
+        Ok(Dual::combine(value, &lhs, 1.0, &rhs, -1.0))
+    }
+
+    fn mul(&self, lhs: Dual, rhs: Dual) -> Result<Dual, CalculatorError> {
+        let value = self.calculator.mul(lhs.value, rhs.value)?;
+        Ok(Dual::combine(value, &lhs, rhs.value, &rhs, lhs.value))
+    }
+
+    fn div(&self, lhs: Dual, rhs: Dual) -> Result<Dual, CalculatorError> {
+        let value = self.calculator.div(lhs.value, rhs.value)?;
+        Ok(Dual::combine(
+            value,
+            &lhs,
+            1.0 / rhs.value,
+            &rhs,
+            -value / rhs.value,
+        ))
+    }
+
+    fn pow(&self, lhs: Dual, rhs: Dual) -> Result<Dual, CalculatorError> {
+        let value = self.calculator.pow(lhs.value, rhs.value)?;
+        Ok(Dual::combine(
+            value,
+            &lhs,
+            rhs.value * lhs.value.powf(rhs.value - 1.0),
+            &rhs,
+            value * lhs.value.ln(),
+        ))
+    }
+
+    fn neg(&self, value: Dual) -> Result<Dual, CalculatorError> {
+        Ok(value.chain(-value.value, -1.0))
+    }
+
+    fn function_1_argument(&self, fct: &str, arg0: Dual) -> Result<Dual, CalculatorError> {
+        let value = self.calculator.function_1_argument(fct, arg0.value)?;
+        let derivative = derivative_1_argument(fct, arg0.value, value)?;
+        Ok(arg0.chain(value, derivative))
+    }
+
+    fn function_2_arguments(
+        &self,
+        fct: &str,
+        arg0: Dual,
+        arg1: Dual,
+    ) -> Result<Dual, CalculatorError> {
+        if fct == "pow" {
+            return self.pow(arg0, arg1);
+        }
+        let value = self
+            .calculator
+            .function_2_arguments(fct, arg0.value, arg1.value)?;
+        let (x, y) = (arg0.value, arg1.value);
+        let (factor0, factor1) = match fct {
+            "atan2" => {
+                let norm = x * x + y * y;
+                (y / norm, -x / norm)
+            }
+            "hypot" => (x / value, y / value),
+            "max" => {
+                if x >= y {
+                    (1.0, 0.0)
+                } else {
+                    (0.0, 1.0)
+                }
+            }
+            "min" => {
+                if x <= y {
+                    (1.0, 0.0)
+                } else {
+                    (0.0, 1.0)
+                }
+            }
+            _ => {
+                return Err(CalculatorError::FunctionNotFound {
+                    fct: fct.to_string(),
+                })
+            }
+        };
+        Ok(Dual::combine(value, &arg0, factor0, &arg1, factor1))
+    }
+}
+
+impl Calculator {
+    /// Parse a string expression and compute its gradient in the same pass.
+    ///
+    /// The gradient is computed with forward-mode automatic differentiation
+    /// using dual numbers. Assignments in the expression set the variable values
+    /// like [Calculator::parse_str] and carry their gradient to later statements.
+    ///
+    /// Variables defined by expressions are differentiated through their definition,
+    /// they can not be chosen as variables of the gradient themselves because they
+    /// depend on other variables. Such requests return an error instead of a zero gradient.
+    ///
+    /// # Arguments
+    ///
+    /// * `expression` - Expression that is parsed
+    /// * `variables` - Variables the gradient is computed for
+    ///
+    /// # Returns
+    ///
+    /// * `Ok((f64, Vec<f64>))` - Value of the expression and partial derivatives in the order of `variables`
+    /// * `Err(CalculatorError::DerivedVariableGradient)` - One of `variables` is defined by an expression
+    ///
+    pub fn parse_with_gradient(
+        &mut self,
+        expression: &str,
+        variables: &[&str],
+    ) -> Result<(f64, Vec<f64>), CalculatorError> {
+        if let Some(name) = variables.iter().find(|name| {
+            self.stored_variable(name).is_none() && self.definitions.contains_key(**name)
+        }) {
+            return Err(CalculatorError::DerivedVariableGradient {
+                name: name.to_string(),
+            });
+        }
+        let mut evaluator = DualEvaluator {
+            calculator: self,
+            variables,
+            assigned: HashMap::new(),
+        };
+        let mut parser = Parser::new(expression, &mut evaluator);
+        match parser.evaluate_all_tokens()? {
+            None => Err(CalculatorError::NoValueReturnedParsing),
+            Some(x) => Ok((x.value, x.gradient)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Calculator, CalculatorError};
+
+    // Compare gradient with central finite differences
+    fn assert_gradient_close(expression: &str, x: f64) {
+        let mut calculator = Calculator::new();
+        calculator.set_variable("x", x);
+        let (value, gradient) = calculator.parse_with_gradient(expression, &["x"]).unwrap();
+        calculator.set_variable("x", x);
+        assert!((value - calculator.parse_str(expression).unwrap()).abs() < 1e-14);
+        let step = 1e-6;
+        calculator.set_variable("x", x + step);
+        let upper = calculator.parse_str(expression).unwrap();
+        calculator.set_variable("x", x - step);
+        let lower = calculator.parse_str(expression).unwrap();
+        let finite_difference = (upper - lower) / (2.0 * step);
+        assert!(
+            (gradient[0] - finite_difference).abs() < 1e-6 * (1.0 + finite_difference.abs()),
+            "{}: {} != {}",
+            expression,
+            gradient[0],
+            finite_difference
+        );
+    }
+
+    // Test gradient of arithmetic operations
+    #[test]
+    fn test_arithmetic() {
+        let mut calculator = Calculator::new();
+        calculator.set_variable("theta", 2.0);
+        calculator.set_variable("phi", 3.0);
+        calculator.set_variable("c", 5.0);
+        let (value, gradient) = calculator
+            .parse_with_gradient("theta * phi + c / theta - phi^2", &["theta", "phi"])
+            .unwrap();
+        assert_eq!(value, 2.0 * 3.0 + 5.0 / 2.0 - 9.0);
+        assert_eq!(gradient, vec![3.0 - 5.0 / 4.0, 2.0 - 6.0]);
+        let (_, gradient) = calculator
+            .parse_with_gradient("-theta", &["phi", "theta", "c"])
+            .unwrap();
+        assert_eq!(gradient, vec![0.0, -1.0, 0.0]);
+        calculator.set_variable("theta", -3.0);
+        let (value, gradient) = calculator
+            .parse_with_gradient("theta^2", &["theta"])
+            .unwrap();
+        assert_eq!(value, 9.0);
+        assert_eq!(gradient, vec![-6.0]);
+        assert_gradient_close("x^x", 1.3);
+        assert_gradient_close("2^x / (x + 3)", 0.7);
+    }
+
+    // Test gradient of the built-in functions
+    #[test]
+    fn test_functions() {
+        for function in &[
+            "sin", "cos", "tan", "abs", "atan", "cosh", "sinh", "tanh", "asinh", "arsinh", "exp",
+            "exp2", "expm1", "cbrt", "ceil", "floor", "fract", "round", "sign", "delta", "theta",
+            "acos", "asin", "atanh", "artanh",
+        ] {
+            assert_gradient_close(&format!("{}(x)", function), 0.3);
+            assert_gradient_close(&format!("{}(x)", function), -0.6);
+        }
+        for function in &["acosh", "arcosh", "log", "log10", "sqrt"] {
+            assert_gradient_close(&format!("{}(x)", function), 2.3);
+        }
+        for function in &["atan2", "hypot", "pow", "max", "min"] {
+            assert_gradient_close(&format!("{}(x, 2 - x^2)", function), 0.4);
+            assert_gradient_close(&format!("{}(3 * x, 1 + x)", function), 0.8);
+        }
+    }
+
+    // Test that assignments carry the gradient to later statements
+    #[test]
+    fn test_assignment() {
+        let mut calculator = Calculator::new();
+        calculator.set_variable("theta", 0.5);
+        let (value, gradient) = calculator
+            .parse_with_gradient("a=2*theta; sin(a)", &["theta"])
+            .unwrap();
+        assert_eq!(value, 1.0_f64.sin());
+        assert_eq!(gradient, vec![2.0 * 1.0_f64.cos()]);
+        assert_eq!(calculator.get_variable("a").unwrap(), 1.0);
+    }
+
+    // Test errors of gradient evaluation
+    #[test]
+    fn test_errors() {
+        let mut calculator = Calculator::new();
+        calculator.set_variable("x", 0.0);
+        assert_eq!(
+            calculator.parse_with_gradient("y + x", &["x"]),
+            Err(CalculatorError::VariableNotSet {
+                name: "y".to_string()
+            })
+        );
+        assert_eq!(
+            calculator.parse_with_gradient("1 / x", &["x"]),
+            Err(CalculatorError::DivisionByZero)
+        );
+        calculator.set_strict(true);
+        assert_eq!(
+            calculator.parse_with_gradient("log(x)", &["x"]),
+            Err(CalculatorError::DomainError {
+                fct: "log".to_string(),
+                arguments: vec![0.0]
+            })
+        );
+    }
+
+    // Test gradient through variables defined by expressions
+    #[test]
+    fn test_defined_variables() {
+        let mut calculator = Calculator::new();
+        calculator.set_variable("x", 0.5);
+        calculator.set_variable("z", 3.0);
+        calculator.set_expression("y", "2 * x").unwrap();
+        calculator.set_expression("w", "y * sin(x) + z").unwrap();
+        let (value, gradient) = calculator.parse_with_gradient("y", &["x"]).unwrap();
+        assert_eq!(value, 1.0);
+        assert_eq!(gradient, vec![2.0]);
+        let (value, gradient) = calculator.parse_with_gradient("w", &["x", "z"]).unwrap();
+        assert_eq!(value, 0.5_f64.sin() + 3.0);
+        assert!((gradient[0] - (2.0 * 0.5_f64.sin() + 0.5_f64.cos())).abs() < 1e-15);
+        assert_eq!(gradient[1], 1.0);
+        calculator.push_scope();
+        calculator.set_variable("y", 4.0);
+        let (value, gradient) = calculator.parse_with_gradient("y", &["x"]).unwrap();
+        assert_eq!(value, 4.0);
+        assert_eq!(gradient, vec![0.0]);
+        // Overridden in the scope y is an independent variable
+        let (_, gradient) = calculator.parse_with_gradient("y", &["y"]).unwrap();
+        assert_eq!(gradient, vec![1.0]);
+    }
+
+    // Test gradient with respect to variables defined by expressions is rejected
+    #[test]
+    fn test_derived_variable_gradient() {
+        let mut calculator = Calculator::new();
+        calculator.set_variable("theta", 0.5);
+        calculator.set_expression("phi", "2 * theta").unwrap();
+        let error = Err(CalculatorError::DerivedVariableGradient {
+            name: "phi".to_string(),
+        });
+        assert_eq!(calculator.parse_with_gradient("phi", &["phi"]), error);
+        assert_eq!(
+            calculator.parse_with_gradient("theta", &["theta", "phi"]),
+            error
+        );
+        let (value, gradient) = calculator.parse_with_gradient("phi", &["theta"]).unwrap();
+        assert_eq!(value, 1.0);
+        assert_eq!(gradient, vec![2.0]);
+    }
+}
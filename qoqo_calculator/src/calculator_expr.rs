@@ -0,0 +1,857 @@
+// Copyright © 2020-2021 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations underthe License.
+
+//! calculator_expr module
+//!
+//! Provides CalculatorExpr, symbolic expressions stored as a shared tree.
+//!
+//! CalculatorExpr is the representation of symbolic CalculatorFloat::Str values.
+//! Building an expression only allocates one node per operation and clones are cheap,
+//! so long sums do not copy the already built expression.
+//! Evaluating the tree only parses the symbolic leaves given in string form.
+//! The string representation and serialization match the former string form of CalculatorFloat.
+
+use crate::calculator::{function_1_argument, function_2_arguments, Evaluator, ReadOnlyEvaluator};
+use crate::{Calculator, CalculatorError, CalculatorFloat};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::borrow::Cow;
+use std::fmt;
+use std::ops;
+use std::sync::Arc;
+
+static ATOL: f64 = f64::EPSILON;
+
+/// Binary operators of the expression tree.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum BinaryOperator {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Pow,
+}
+
+impl BinaryOperator {
+    /// Return symbol of operator used in the string representation.
+    fn symbol(self) -> &'static str {
+        match self {
+            BinaryOperator::Add => "+",
+            BinaryOperator::Sub => "-",
+            BinaryOperator::Mul => "*",
+            BinaryOperator::Div => "/",
+            BinaryOperator::Pow => "^",
+        }
+    }
+}
+
+/// Node of the expression tree.
+enum Node {
+    /// Numeric value
+    Number(f64),
+    /// Symbolic expression in string form that is parsed on evaluation
+    Symbol(String),
+    /// Negation
+    Neg(CalculatorExpr),
+    /// Binary operation
+    Binary(BinaryOperator, CalculatorExpr, CalculatorExpr),
+    /// Function of the Calculator function table with one argument
+    Function1(Cow<'static, str>, CalculatorExpr),
+    /// Function of the Calculator function table with two arguments
+    Function2(Cow<'static, str>, CalculatorExpr, CalculatorExpr),
+}
+
+/// Struct CalculatorExpr.
+///
+/// Symbolic expression stored as tree with reference counted nodes.
+/// Numeric subexpressions are folded when the expression is built.
+///
+#[derive(Clone)]
+pub struct CalculatorExpr(Arc<Node>);
+
+/// Implement Drop for CalculatorExpr.
+///
+/// Subexpressions that are not shared are taken out of the tree and dropped one after another,
+/// so dropping deeply nested expressions cannot overflow the stack.
+///
+impl Drop for CalculatorExpr {
+    fn drop(&mut self) {
+        let mut unshared: Vec<CalculatorExpr> = Vec::new();
+        if let Some(node) = Arc::get_mut(&mut self.0) {
+            node.take_children(&mut unshared);
+        }
+        while let Some(mut expr) = unshared.pop() {
+            if let Some(node) = Arc::get_mut(&mut expr.0) {
+                node.take_children(&mut unshared);
+            }
+        }
+    }
+}
+
+impl Node {
+    /// Move the subexpressions of the node to a stack, leaving a numeric leaf behind.
+    fn take_children(&mut self, stack: &mut Vec<CalculatorExpr>) {
+        match std::mem::replace(self, Node::Number(0.0)) {
+            Node::Number(_) | Node::Symbol(_) => (),
+            Node::Neg(x) | Node::Function1(_, x) => stack.push(x),
+            Node::Binary(_, x, y) | Node::Function2(_, x, y) => {
+                stack.push(x);
+                stack.push(y);
+            }
+        }
+    }
+}
+
+/// Implement Debug trait for CalculatorExpr using the string representation.
+impl fmt::Debug for CalculatorExpr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("CalculatorExpr")
+            .field(&self.to_string())
+            .finish()
+    }
+}
+
+/// Implement PartialEq for CalculatorExpr.
+///
+/// Numeric values are compared as floats and symbolic expressions by their string representation.
+/// Shared trees compare equal without being traversed.
+impl PartialEq for CalculatorExpr {
+    fn eq(&self, other: &Self) -> bool {
+        if Arc::ptr_eq(&self.0, &other.0) {
+            return true;
+        }
+        match (self.value(), other.value()) {
+            (Some(x), Some(y)) => x == y,
+            (None, None) => self.to_string() == other.to_string(),
+            _ => false,
+        }
+    }
+}
+
+/// Initialize CalculatorExpr from CalculatorFloat.
+///
+/// # Returns
+///
+/// * `CalculatorExpr`
+///
+impl From<CalculatorFloat> for CalculatorExpr {
+    fn from(item: CalculatorFloat) -> Self {
+        match item {
+            CalculatorFloat::Float(x) => CalculatorExpr::number(x),
+            CalculatorFloat::Str(x) => x,
+        }
+    }
+}
+
+/// Initialize CalculatorExpr from the types that can be cast to CalculatorFloat.
+///
+/// A generic implementation would overlap with the conversion of CalculatorExpr into CalculatorFloat.
+macro_rules! impl_from_via_calculator_float {
+    ($($source:ty),*) => {
+        $(
+            impl From<$source> for CalculatorExpr {
+                fn from(item: $source) -> Self {
+                    CalculatorExpr::from(CalculatorFloat::from(item))
+                }
+            }
+        )*
+    };
+}
+
+impl_from_via_calculator_float!(
+    &CalculatorFloat,
+    f64,
+    &f64,
+    i32,
+    &i32,
+    u32,
+    &u32,
+    String,
+    &String,
+    &str
+);
+
+/// Initialize CalculatorExpr from CalculatorExpr reference &CalculatorExpr.
+///
+/// # Returns
+///
+/// * `CalculatorExpr`
+///
+impl<'a> From<&'a CalculatorExpr> for CalculatorExpr {
+    fn from(item: &'a CalculatorExpr) -> Self {
+        item.clone()
+    }
+}
+
+/// Turn CalculatorExpr into CalculatorFloat.
+///
+/// # Returns
+///
+/// * `CalculatorFloat` - Float for numeric expressions, Str otherwise
+///
+impl From<CalculatorExpr> for CalculatorFloat {
+    fn from(item: CalculatorExpr) -> Self {
+        match item.value() {
+            Some(x) => CalculatorFloat::Float(x),
+            None => CalculatorFloat::Str(item),
+        }
+    }
+}
+
+/// Turn CalculatorExpr reference into CalculatorFloat.
+///
+/// # Returns
+///
+/// * `CalculatorFloat` - Float for numeric expressions, Str otherwise
+///
+impl<'a> From<&'a CalculatorExpr> for CalculatorFloat {
+    fn from(item: &'a CalculatorExpr) -> Self {
+        CalculatorFloat::from(item.clone())
+    }
+}
+
+/// Implement Display trait for CalculatorExpr.
+///
+/// Produces the same string as the corresponding CalculatorFloat operations
+///
+impl fmt::Display for CalculatorExpr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Write iteratively so deeply nested expressions cannot overflow the stack
+        let mut pieces: Vec<DisplayPiece> = vec![DisplayPiece::Expr(self)];
+        while let Some(piece) = pieces.pop() {
+            match piece {
+                DisplayPiece::Text(text) => f.write_str(text)?,
+                DisplayPiece::Operator(op) => write!(f, " {} ", op.symbol())?,
+                DisplayPiece::Expr(expr) => match expr.0.as_ref() {
+                    Node::Number(x) => write!(f, "{:e}", x)?,
+                    Node::Symbol(x) => f.write_str(x)?,
+                    Node::Neg(x) => {
+                        f.write_str("(-")?;
+                        pieces.push(DisplayPiece::Text(")"));
+                        pieces.push(DisplayPiece::Expr(x));
+                    }
+                    Node::Binary(op, lhs, rhs) => {
+                        f.write_str("(")?;
+                        pieces.push(DisplayPiece::Text(")"));
+                        pieces.push(DisplayPiece::Expr(rhs));
+                        pieces.push(DisplayPiece::Operator(*op));
+                        pieces.push(DisplayPiece::Expr(lhs));
+                    }
+                    Node::Function1(fct, x) => {
+                        write!(f, "{}(", fct)?;
+                        pieces.push(DisplayPiece::Text(")"));
+                        pieces.push(DisplayPiece::Expr(x));
+                    }
+                    Node::Function2(fct, x, y) => {
+                        write!(f, "{}(", fct)?;
+                        pieces.push(DisplayPiece::Text(")"));
+                        pieces.push(DisplayPiece::Expr(y));
+                        pieces.push(DisplayPiece::Text(", "));
+                        pieces.push(DisplayPiece::Expr(x));
+                    }
+                },
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Pending piece of the string representation of a CalculatorExpr.
+enum DisplayPiece<'a> {
+    /// Write the subexpression
+    Expr(&'a CalculatorExpr),
+    /// Write a binary operator surrounded by spaces
+    Operator(BinaryOperator),
+    /// Write fixed text
+    Text(&'static str),
+}
+
+/// Serialize CalculatorExpr like the equivalent CalculatorFloat.
+impl Serialize for CalculatorExpr {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        CalculatorFloat::from(self).serialize(serializer)
+    }
+}
+
+/// Deserialize CalculatorExpr from the serialized form of a CalculatorFloat.
+impl<'de> Deserialize<'de> for CalculatorExpr {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        CalculatorFloat::deserialize(deserializer).map(CalculatorExpr::from)
+    }
+}
+
+impl CalculatorExpr {
+    /// Return CalculatorExpr of a numeric value.
+    fn number(value: f64) -> Self {
+        CalculatorExpr(Arc::new(Node::Number(value)))
+    }
+
+    /// Return CalculatorExpr of a symbolic expression in string form.
+    ///
+    /// The expression is parsed when the CalculatorExpr is evaluated.
+    pub(crate) fn symbol(expression: String) -> Self {
+        CalculatorExpr(Arc::new(Node::Symbol(expression)))
+    }
+
+    /// Return Some(f64) when CalculatorExpr is a numeric value.
+    fn value(&self) -> Option<f64> {
+        match self.0.as_ref() {
+            Node::Number(x) => Some(*x),
+            _ => None,
+        }
+    }
+
+    /// Return CalculatorExpr for binary operation after folding numeric values.
+    ///
+    /// # Panics
+    ///
+    /// Panics on division by a numeric zero.
+    pub(crate) fn binary(op: BinaryOperator, lhs: CalculatorExpr, rhs: CalculatorExpr) -> Self {
+        match (op, lhs.value(), rhs.value()) {
+            (BinaryOperator::Add, Some(x), Some(y)) => Self::number(x + y),
+            (BinaryOperator::Add, Some(0.0), None) => rhs,
+            (BinaryOperator::Add, None, Some(0.0)) => lhs,
+            (BinaryOperator::Sub, Some(x), Some(y)) => Self::number(x - y),
+            (BinaryOperator::Sub, Some(0.0), None) => -rhs,
+            (BinaryOperator::Sub, None, Some(0.0)) => lhs,
+            (BinaryOperator::Mul, Some(x), Some(y)) => Self::number(x * y),
+            (BinaryOperator::Mul, Some(x), None) | (BinaryOperator::Mul, None, Some(x))
+                if x == 0.0 =>
+            {
+                Self::number(0.0)
+            }
+            (BinaryOperator::Mul, Some(x), None) if (x - 1.0).abs() < ATOL => rhs,
+            (BinaryOperator::Mul, None, Some(y)) if (y - 1.0).abs() < ATOL => lhs,
+            (BinaryOperator::Div, _, Some(0.0)) => panic!("Division by zero"),
+            (BinaryOperator::Div, Some(x), Some(y)) => Self::number(x / y),
+            (BinaryOperator::Div, Some(0.0), None) => Self::number(0.0),
+            (BinaryOperator::Div, None, Some(y)) if (y - 1.0).abs() < ATOL => lhs,
+            (BinaryOperator::Pow, Some(x), Some(y)) => Self::number(x.powf(y)),
+            _ => CalculatorExpr(Arc::new(Node::Binary(op, lhs, rhs))),
+        }
+    }
+
+    /// Return function with one argument applied to expression, folding numeric values.
+    ///
+    /// # Panics
+    ///
+    /// Panics when a numeric value is folded with a function that is not in the function table.
+    pub(crate) fn function_1_argument<F>(&self, fct: F) -> Self
+    where
+        F: Into<Cow<'static, str>>,
+    {
+        let fct = fct.into();
+        match self.value() {
+            Some(x) => Self::number(
+                function_1_argument(&fct, x).expect("Function is part of the function table"),
+            ),
+            None => CalculatorExpr(Arc::new(Node::Function1(fct, self.clone()))),
+        }
+    }
+
+    /// Return function with two arguments applied to expressions, folding numeric values.
+    ///
+    /// # Panics
+    ///
+    /// Panics when numeric values are folded with a function that is not in the function table.
+    pub(crate) fn function_2_arguments<F>(&self, fct: F, other: CalculatorExpr) -> Self
+    where
+        F: Into<Cow<'static, str>>,
+    {
+        let fct = fct.into();
+        match (self.value(), other.value()) {
+            (Some(x), Some(y)) => Self::number(
+                function_2_arguments(&fct, x, y).expect("Function is part of the function table"),
+            ),
+            _ => CalculatorExpr(Arc::new(Node::Function2(fct, self.clone(), other))),
+        }
+    }
+
+    /// Return True when CalculatorExpr does not contain symbolic expression.
+    pub fn is_float(&self) -> bool {
+        self.value().is_some()
+    }
+
+    /// Return square root of CalculatorExpr.
+    pub fn sqrt(&self) -> CalculatorExpr {
+        self.function_1_argument("sqrt")
+    }
+
+    /// Return exponential function exp(x) for CalculatorExpr.
+    pub fn exp(&self) -> CalculatorExpr {
+        self.function_1_argument("exp")
+    }
+
+    /// Return natural logarithm log(x) for CalculatorExpr.
+    pub fn ln(&self) -> CalculatorExpr {
+        self.function_1_argument("log")
+    }
+
+    /// Return sine function sin(x) for CalculatorExpr.
+    pub fn sin(&self) -> CalculatorExpr {
+        self.function_1_argument("sin")
+    }
+
+    /// Return cosine function cos(x) for CalculatorExpr.
+    pub fn cos(&self) -> CalculatorExpr {
+        self.function_1_argument("cos")
+    }
+
+    /// Return tangent function tan(x) for CalculatorExpr.
+    pub fn tan(&self) -> CalculatorExpr {
+        self.function_1_argument("tan")
+    }
+
+    /// Return arccosine function acos(x) for CalculatorExpr.
+    pub fn acos(&self) -> CalculatorExpr {
+        self.function_1_argument("acos")
+    }
+
+    /// Return arcsine function asin(x) for CalculatorExpr.
+    pub fn asin(&self) -> CalculatorExpr {
+        self.function_1_argument("asin")
+    }
+
+    /// Return arctangent function atan(x) for CalculatorExpr.
+    pub fn atan(&self) -> CalculatorExpr {
+        self.function_1_argument("atan")
+    }
+
+    /// Return absolute value abs(x) for CalculatorExpr.
+    pub fn abs(&self) -> CalculatorExpr {
+        self.function_1_argument("abs")
+    }
+
+    /// Return signum value sign(x) for CalculatorExpr.
+    pub fn signum(&self) -> CalculatorExpr {
+        self.function_1_argument("sign")
+    }
+
+    /// Return atan2 for CalculatorExpr and generic type `T`.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - Any type T for which CalculatorExpr::From<T> trait is implemented
+    ///
+    pub fn atan2<T>(&self, other: T) -> CalculatorExpr
+    where
+        CalculatorExpr: From<T>,
+    {
+        self.function_2_arguments("atan2", CalculatorExpr::from(other))
+    }
+
+    /// Return Power for CalculatorExpr and generic type `T`.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - Any type T for which CalculatorExpr::From<T> trait is implemented
+    ///
+    pub fn powf<T>(&self, other: T) -> CalculatorExpr
+    where
+        CalculatorExpr: From<T>,
+    {
+        Self::binary(
+            BinaryOperator::Pow,
+            self.clone(),
+            CalculatorExpr::from(other),
+        )
+    }
+
+    /// Evaluate expression with the variables of a Calculator.
+    ///
+    /// The tree is evaluated directly, only symbolic leaves given in string form are parsed.
+    ///
+    /// # Arguments
+    ///
+    /// * `calculator` - Calculator providing the variable values
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(f64)` - Value of the expression
+    /// * `Err(CalculatorError)` - Evaluating the expression failed
+    pub fn evaluate(&self, calculator: &mut Calculator) -> Result<f64, CalculatorError> {
+        self.evaluate_with(calculator, |calculator, expression| {
+            calculator.parse_str(expression)
+        })
+    }
+
+    /// Evaluate expression with the variables of a Calculator without modifying the Calculator.
+    ///
+    /// Symbolic leaves containing assignments are rejected.
+    ///
+    /// # Arguments
+    ///
+    /// * `calculator` - Calculator providing the variable values
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(f64)` - Value of the expression
+    /// * `Err(CalculatorError)` - Evaluating the expression failed
+    pub fn evaluate_read_only(&self, calculator: &Calculator) -> Result<f64, CalculatorError> {
+        let mut evaluator = ReadOnlyEvaluator {
+            evaluator: calculator,
+        };
+        self.evaluate_with(&mut evaluator, |evaluator, expression| {
+            evaluator.evaluator.evaluate(expression)
+        })
+    }
+
+    /// Evaluate expression with an Evaluator, evaluating symbolic leaves with `symbol`.
+    fn evaluate_with<E, F>(&self, evaluator: &mut E, mut symbol: F) -> Result<f64, CalculatorError>
+    where
+        E: Evaluator<Value = f64>,
+        F: FnMut(&mut E, &str) -> Result<f64, CalculatorError>,
+    {
+        // Evaluate iteratively so deeply nested expressions cannot overflow the stack
+        let mut tasks: Vec<EvaluationTask> = vec![EvaluationTask::Visit(self)];
+        let mut values: Vec<f64> = Vec::new();
+        while let Some(task) = tasks.pop() {
+            match task {
+                EvaluationTask::Visit(expr) => match expr.0.as_ref() {
+                    Node::Number(x) => values.push(*x),
+                    Node::Symbol(x) => values.push(symbol(evaluator, x)?),
+                    Node::Neg(x) | Node::Function1(_, x) => {
+                        tasks.push(EvaluationTask::Apply(expr.0.as_ref()));
+                        tasks.push(EvaluationTask::Visit(x));
+                    }
+                    Node::Binary(_, x, y) | Node::Function2(_, x, y) => {
+                        tasks.push(EvaluationTask::Apply(expr.0.as_ref()));
+                        tasks.push(EvaluationTask::Visit(y));
+                        tasks.push(EvaluationTask::Visit(x));
+                    }
+                },
+                EvaluationTask::Apply(node) => {
+                    let value = match node {
+                        Node::Neg(_) => {
+                            let value = pop_value(&mut values)?;
+                            evaluator.neg(value)?
+                        }
+                        Node::Function1(fct, _) => {
+                            let value = pop_value(&mut values)?;
+                            evaluator.function_1_argument(fct, value)?
+                        }
+                        Node::Binary(op, _, _) => {
+                            let rhs = pop_value(&mut values)?;
+                            let lhs = pop_value(&mut values)?;
+                            match op {
+                                BinaryOperator::Add => evaluator.add(lhs, rhs)?,
+                                BinaryOperator::Sub => evaluator.sub(lhs, rhs)?,
+                                BinaryOperator::Mul => evaluator.mul(lhs, rhs)?,
+                                BinaryOperator::Div => evaluator.div(lhs, rhs)?,
+                                BinaryOperator::Pow => evaluator.pow(lhs, rhs)?,
+                            }
+                        }
+                        Node::Function2(fct, _, _) => {
+                            let arg1 = pop_value(&mut values)?;
+                            let arg0 = pop_value(&mut values)?;
+                            evaluator.function_2_arguments(fct, arg0, arg1)?
+                        }
+                        Node::Number(_) | Node::Symbol(_) => {
+                            unreachable!("Leaves are evaluated when visited")
+                        }
+                    };
+                    values.push(value);
+                }
+            }
+        }
+        pop_value(&mut values)
+    }
+}
+
+/// Pending step of the evaluation of a CalculatorExpr.
+enum EvaluationTask<'a> {
+    /// Evaluate the subexpression
+    Visit(&'a CalculatorExpr),
+    /// Apply the operation of the node to the values of its evaluated operands
+    Apply(&'a Node),
+}
+
+/// Remove top value from the values of an evaluated CalculatorExpr.
+fn pop_value(values: &mut Vec<f64>) -> Result<f64, CalculatorError> {
+    values.pop().ok_or(CalculatorError::NoValueReturnedParsing)
+}
+
+/// Implement `+` (add) for CalculatorExpr and generic type `T`.
+///
+/// # Arguments
+///
+/// * `other` - Any type T for which CalculatorExpr::From<T> trait is implemented
+///
+impl<T> ops::Add<T> for CalculatorExpr
+where
+    CalculatorExpr: From<T>,
+{
+    type Output = Self;
+    fn add(self, other: T) -> Self {
+        Self::binary(BinaryOperator::Add, self, Self::from(other))
+    }
+}
+
+/// Implement `+=` (add) for CalculatorExpr and generic type `T`.
+///
+/// # Arguments
+///
+/// * `other` - Any type T for which CalculatorExpr::From<T> trait is implemented
+///
+impl<T> ops::AddAssign<T> for CalculatorExpr
+where
+    CalculatorExpr: From<T>,
+{
+    fn add_assign(&mut self, other: T) {
+        *self = Self::binary(BinaryOperator::Add, self.clone(), Self::from(other));
+    }
+}
+
+/// Implements summing over an iterator of CalculatorExpr
+///
+/// # Arguments
+///
+/// * `iter` - Any iterator over CalculatorExpr items
+///
+impl std::iter::Sum for CalculatorExpr {
+    fn sum<I: Iterator<Item = CalculatorExpr>>(iter: I) -> Self {
+        iter.fold(CalculatorExpr::from(0), |sum, x| sum + x)
+    }
+}
+
+/// Implement `-` (subtract) for CalculatorExpr and generic type `T`.
+///
+/// # Arguments
+///
+/// * `other` - Any type T for which CalculatorExpr::From<T> trait is implemented
+///
+impl<T> ops::Sub<T> for CalculatorExpr
+where
+    CalculatorExpr: From<T>,
+{
+    type Output = Self;
+    fn sub(self, other: T) -> Self {
+        Self::binary(BinaryOperator::Sub, self, Self::from(other))
+    }
+}
+
+/// Implement `-=` (subtract) for CalculatorExpr and generic type `T`.
+///
+/// # Arguments
+///
+/// * `other` - Any type T for which CalculatorExpr::From<T> trait is implemented
+///
+impl<T> ops::SubAssign<T> for CalculatorExpr
+where
+    CalculatorExpr: From<T>,
+{
+    fn sub_assign(&mut self, other: T) {
+        *self = Self::binary(BinaryOperator::Sub, self.clone(), Self::from(other));
+    }
+}
+
+/// Implement `*` (multiply) for CalculatorExpr and generic type `T`.
+///
+/// # Arguments
+///
+/// * `other` - Any type T for which CalculatorExpr::From<T> trait is implemented
+///
+impl<T> ops::Mul<T> for CalculatorExpr
+where
+    CalculatorExpr: From<T>,
+{
+    type Output = Self;
+    fn mul(self, other: T) -> Self {
+        Self::binary(BinaryOperator::Mul, self, Self::from(other))
+    }
+}
+
+/// Implement `*=` (multiply) for CalculatorExpr and generic type `T`.
+///
+/// # Arguments
+///
+/// * `other` - Any type T for which CalculatorExpr::From<T> trait is implemented
+///
+impl<T> ops::MulAssign<T> for CalculatorExpr
+where
+    CalculatorExpr: From<T>,
+{
+    fn mul_assign(&mut self, other: T) {
+        *self = Self::binary(BinaryOperator::Mul, self.clone(), Self::from(other));
+    }
+}
+
+/// Implement `/` (divide) for CalculatorExpr and generic type `T`.
+///
+/// # Arguments
+///
+/// * `other` - Any type T for which CalculatorExpr::From<T> trait is implemented
+///
+/// # Panics
+///
+/// Panics on division by zero.
+/// Division by zero is only detected when other is a numeric value
+///
+impl<T> ops::Div<T> for CalculatorExpr
+where
+    CalculatorExpr: From<T>,
+{
+    type Output = Self;
+    fn div(self, other: T) -> Self {
+        Self::binary(BinaryOperator::Div, self, Self::from(other))
+    }
+}
+
+/// Implement `/=` (divide) for CalculatorExpr and generic type `T`.
+///
+/// # Arguments
+///
+/// * `other` - Any type T for which CalculatorExpr::From<T> trait is implemented
+///
+/// # Panics
+///
+/// Panics on division by zero.
+///
+impl<T> ops::DivAssign<T> for CalculatorExpr
+where
+    CalculatorExpr: From<T>,
+{
+    fn div_assign(&mut self, other: T) {
+        *self = Self::binary(BinaryOperator::Div, self.clone(), Self::from(other));
+    }
+}
+
+/// Implement minus sign for CalculatorExpr.
+impl ops::Neg for CalculatorExpr {
+    type Output = CalculatorExpr;
+
+    fn neg(self) -> Self {
+        match self.value() {
+            Some(x) => Self::number(-x),
+            None => CalculatorExpr(Arc::new(Node::Neg(self))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CalculatorExpr;
+    use crate::{Calculator, CalculatorError, CalculatorFloat};
+    use serde_test::{assert_tokens, Configure, Token};
+
+    // Test that the string representation matches the CalculatorFloat operations
+    #[test]
+    fn display_matches_calculator_float() {
+        let xf = CalculatorFloat::from("x");
+        let yf = CalculatorFloat::from("y");
+        let x = CalculatorExpr::from("x");
+        let y = CalculatorExpr::from("y");
+        let float_result = ((xf.clone() + 1.5) * &yf - 2) / (-xf.clone()).sin() + 0
+            - yf.atan2(&xf).powf(2)
+            + (CalculatorFloat::from(0) - &xf) * 1
+            + xf.sqrt().exp().cos().abs();
+        let expr_result = ((x.clone() + 1.5) * &y - 2) / (-x.clone()).sin() + 0
+            - y.atan2(&x).powf(2)
+            + (CalculatorExpr::from(0) - &x) * 1
+            + x.sqrt().exp().cos().abs();
+        assert_eq!(expr_result.to_string(), float_result.to_string());
+        assert_eq!(CalculatorFloat::from(expr_result), float_result);
+    }
+
+    // Test constant folding of numeric subexpressions
+    #[test]
+    fn constant_folding() {
+        let x = CalculatorExpr::from(2) * 3 + CalculatorExpr::from(1).atan2(1);
+        assert!(x.is_float());
+        assert_eq!(
+            CalculatorFloat::from(x),
+            CalculatorFloat::from(6.0 + 1.0_f64.atan2(1.0))
+        );
+        let y = CalculatorExpr::from("y") * CalculatorExpr::from(0);
+        assert_eq!(y, CalculatorExpr::from(0));
+        let z = CalculatorExpr::from("z");
+        assert_eq!(z.clone() * 1 + 0 - 0, z);
+    }
+
+    // Test panic of division by zero
+    #[test]
+    #[should_panic]
+    fn div_zero() {
+        let _ = CalculatorExpr::from("x") / 0;
+    }
+
+    // Test that long sums are evaluated without parsing the whole expression
+    #[test]
+    fn evaluate_sum() {
+        let terms: Vec<CalculatorExpr> = (0..1000).map(|i| CalculatorExpr::from("x") * i).collect();
+        let sum: CalculatorExpr = terms.into_iter().sum();
+        let mut calculator = Calculator::new();
+        calculator.set_variable("x", 0.5);
+        assert_eq!(sum.evaluate(&mut calculator).unwrap(), 0.5 * 499500.0);
+        let shared = sum.clone() * sum.clone();
+        assert_eq!(
+            shared.evaluate(&mut calculator).unwrap(),
+            (0.5_f64 * 499500.0).powi(2)
+        );
+    }
+
+    // Test evaluation agrees with parsing the string representation
+    #[test]
+    fn evaluate() {
+        let x = CalculatorExpr::from("x");
+        let expr = (x.clone().sin() + x.clone().powf("y")).ln() / x.atan2(2);
+        let mut calculator = Calculator::new();
+        calculator.set_variable("x", 0.7);
+        calculator.set_variable("y", 1.5);
+        let expected = calculator.parse_str(&expr.to_string()).unwrap();
+        assert_eq!(expr.evaluate(&mut calculator).unwrap(), expected);
+        let unset = CalculatorExpr::from("z") + 1;
+        assert_eq!(
+            unset.evaluate(&mut calculator),
+            Err(CalculatorError::VariableNotSet {
+                name: "z".to_string()
+            })
+        );
+    }
+
+    // Test building, evaluating, formatting and dropping expressions with 10^5 terms
+    #[test]
+    fn deep_expression() {
+        let mut sum = CalculatorExpr::from(0);
+        for _ in 0..100_000 {
+            sum += "x";
+        }
+        let mut calculator = Calculator::new();
+        calculator.set_variable("x", 0.5);
+        assert_eq!(sum.evaluate(&mut calculator).unwrap(), 50_000.0);
+        let string = sum.to_string();
+        assert_eq!(string.len(), 100_000 * 6 - 5);
+        assert!(string
+            .trim_start_matches('(')
+            .starts_with("x + x) + x) + x)"));
+        assert!(format!("{:?}", sum).starts_with("CalculatorExpr(\"(((("));
+        let shared = sum.clone();
+        drop(sum);
+        let nested = (0..100_000).fold(shared, |expr, _| -expr.sin());
+        assert!(nested.to_string().ends_with(")))"));
+        drop(nested);
+    }
+
+    // Test serialization/deserialization in the format of CalculatorFloat
+    #[test]
+    fn ser_de() {
+        let x = CalculatorExpr::from("x") + 1;
+        assert_tokens(&x.readable(), &[Token::String("(x + 1e0)")]);
+        let y = CalculatorExpr::from(2.5);
+        assert_tokens(&y.readable(), &[Token::F64(2.5)]);
+    }
+}
@@ -0,0 +1,626 @@
+// Copyright © 2020-2021 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! calculator_files module
+//!
+//! Provides loading and saving of Calculator variables from JSON, TOML and CSV files.
+
+use crate::{Calculator, CalculatorError};
+use serde::de::{Deserializer, IgnoredAny, MapAccess, SeqAccess, Visitor};
+use serde::Deserialize;
+use std::cell::Cell;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::marker::PhantomData;
+use std::path::Path;
+use std::rc::Rc;
+
+/// Supported formats of parameter files.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum FileFormat {
+    Json,
+    Toml,
+    Csv,
+}
+
+/// Value of a parameter in a file, either a number or an expression.
+#[derive(Debug, Clone, PartialEq)]
+enum ParameterValue {
+    Number(f64),
+    Expression(String),
+}
+
+/// Parameter of a file with the line (starting at 1) where it is defined.
+#[derive(Debug, Clone, PartialEq)]
+struct Parameter {
+    key: String,
+    line: Option<usize>,
+    value: ParameterValue,
+}
+
+/// Value of a JSON or TOML map entry.
+///
+/// None when the value is neither a number nor a string, so that
+/// invalid values can be reported together with their key.
+struct FileValue(Option<ParameterValue>);
+
+impl<'de> Deserialize<'de> for FileValue {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct ValueVisitor;
+        impl<'de> Visitor<'de> for ValueVisitor {
+            type Value = FileValue;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("number or expression")
+            }
+
+            fn visit_i64<E>(self, value: i64) -> Result<FileValue, E> {
+                Ok(FileValue(Some(ParameterValue::Number(value as f64))))
+            }
+
+            fn visit_u64<E>(self, value: u64) -> Result<FileValue, E> {
+                Ok(FileValue(Some(ParameterValue::Number(value as f64))))
+            }
+
+            fn visit_f64<E>(self, value: f64) -> Result<FileValue, E> {
+                Ok(FileValue(Some(ParameterValue::Number(value))))
+            }
+
+            fn visit_str<E>(self, value: &str) -> Result<FileValue, E> {
+                Ok(FileValue(Some(ParameterValue::Expression(
+                    value.to_string(),
+                ))))
+            }
+
+            fn visit_bool<E>(self, _value: bool) -> Result<FileValue, E> {
+                Ok(FileValue(None))
+            }
+
+            fn visit_unit<E>(self) -> Result<FileValue, E> {
+                Ok(FileValue(None))
+            }
+
+            fn visit_seq<A>(self, mut access: A) -> Result<FileValue, A::Error>
+            where
+                A: SeqAccess<'de>,
+            {
+                while access.next_element::<IgnoredAny>()?.is_some() {}
+                Ok(FileValue(None))
+            }
+
+            fn visit_map<A>(self, mut access: A) -> Result<FileValue, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                while access.next_entry::<IgnoredAny, IgnoredAny>()?.is_some() {}
+                Ok(FileValue(None))
+            }
+        }
+        deserializer.deserialize_any(ValueVisitor)
+    }
+}
+
+/// Key of a JSON or TOML map.
+trait FileKey {
+    /// Return byte offset of the key in the file, if the key knows it.
+    fn offset(&self) -> Option<usize>;
+
+    /// Return name of the key.
+    fn into_name(self) -> String;
+}
+
+impl FileKey for String {
+    fn offset(&self) -> Option<usize> {
+        None
+    }
+
+    fn into_name(self) -> String {
+        self
+    }
+}
+
+impl FileKey for toml::Spanned<String> {
+    fn offset(&self) -> Option<usize> {
+        Some(self.start())
+    }
+
+    fn into_name(self) -> String {
+        self.into_inner()
+    }
+}
+
+/// Entries of a JSON or TOML map in file order with the line of their key.
+type FileEntries = Vec<(String, Option<usize>, FileValue)>;
+
+/// Visitor collecting the entries of a JSON or TOML map in file order.
+///
+/// # Fields
+///
+/// * `text` - Content of the file
+/// * `position` - Number of bytes the deserializer has read, used for keys without offset
+///
+struct EntriesVisitor<'a, K> {
+    text: &'a str,
+    position: Option<Rc<Cell<usize>>>,
+    key: PhantomData<K>,
+}
+
+impl<'de, 'a, K> Visitor<'de> for EntriesVisitor<'a, K>
+where
+    K: FileKey + Deserialize<'de>,
+{
+    type Value = FileEntries;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("map of parameter names to numbers or expressions")
+    }
+
+    fn visit_map<A>(self, mut access: A) -> Result<FileEntries, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut entries = Vec::new();
+        while let Some(key) = access.next_key::<K>()? {
+            // The reader is positioned right after the closing quote of the key
+            let offset = key.offset().or_else(|| {
+                self.position
+                    .as_ref()
+                    .map(|position| position.get().saturating_sub(1))
+            });
+            let line = offset.map(|offset| line_of_offset(self.text, offset));
+            let value = access.next_value::<FileValue>()?;
+            entries.push((key.into_name(), line, value));
+        }
+        Ok(entries)
+    }
+}
+
+/// Reader of a file content that counts the bytes read.
+///
+/// The JSON deserializer reads unbuffered input one byte at a time,
+/// so the count is the position of the deserializer in the file.
+struct CountingReader<'a> {
+    remaining: &'a [u8],
+    position: Rc<Cell<usize>>,
+}
+
+impl io::Read for CountingReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = self.remaining.read(buf)?;
+        self.position.set(self.position.get() + read);
+        Ok(read)
+    }
+}
+
+/// Return entries of a JSON map with the lines of their keys.
+fn json_entries(path: &Path, text: &str) -> Result<FileEntries, CalculatorError> {
+    let position = Rc::new(Cell::new(0));
+    let reader = CountingReader {
+        remaining: text.as_bytes(),
+        position: position.clone(),
+    };
+    let mut deserializer = serde_json::Deserializer::from_reader(reader);
+    let visitor: EntriesVisitor<String> = EntriesVisitor {
+        text,
+        position: Some(position),
+        key: PhantomData,
+    };
+    deserializer
+        .deserialize_map(visitor)
+        .and_then(|entries| deserializer.end().map(|_| entries))
+        .map_err(|err| file_error(path, Some(err.line()), None, err))
+}
+
+/// Return entries of a TOML map with the lines of their keys.
+fn toml_entries(path: &Path, text: &str) -> Result<FileEntries, CalculatorError> {
+    let mut deserializer = toml::Deserializer::new(text);
+    let visitor: EntriesVisitor<toml::Spanned<String>> = EntriesVisitor {
+        text,
+        position: None,
+        key: PhantomData,
+    };
+    deserializer
+        .deserialize_map(visitor)
+        .map_err(|err| file_error(path, err.line_col().map(|(line, _)| line + 1), None, err))
+}
+
+/// Return parameters, rejecting values that are neither numbers nor strings.
+fn into_parameters(path: &Path, entries: FileEntries) -> Result<Vec<Parameter>, CalculatorError> {
+    entries
+        .into_iter()
+        .map(|(key, line, value)| match value {
+            FileValue(Some(value)) => Ok(Parameter { key, line, value }),
+            FileValue(None) => Err(file_error(
+                path,
+                line,
+                Some(&key),
+                "value is neither a number nor an expression",
+            )),
+        })
+        .collect()
+}
+
+/// Return CalculatorError for a parameter file.
+fn file_error(
+    path: &Path,
+    line: Option<usize>,
+    key: Option<&str>,
+    msg: impl fmt::Display,
+) -> CalculatorError {
+    CalculatorError::FileError {
+        file: path.display().to_string(),
+        line,
+        key: key.map(|key| key.to_string()),
+        msg: msg.to_string(),
+    }
+}
+
+/// Return format of a parameter file from its extension.
+fn file_format(path: &Path) -> Result<FileFormat, CalculatorError> {
+    match path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .map(|extension| extension.to_ascii_lowercase())
+        .as_deref()
+    {
+        Some("json") => Ok(FileFormat::Json),
+        Some("toml") => Ok(FileFormat::Toml),
+        Some("csv") => Ok(FileFormat::Csv),
+        _ => Err(file_error(
+            path,
+            None,
+            None,
+            "unknown file format, expected extension json, toml or csv",
+        )),
+    }
+}
+
+/// Return line (starting at 1) of a byte offset in a file.
+fn line_of_offset(text: &str, offset: usize) -> usize {
+    let offset = offset.min(text.len());
+    text.as_bytes()[..offset]
+        .iter()
+        .filter(|byte| **byte == b'\n')
+        .count()
+        + 1
+}
+
+impl Calculator {
+    /// Load variables from a JSON, TOML or CSV file.
+    ///
+    /// JSON and TOML files contain a single map from variable names to numbers or
+    /// expressions. CSV files contain a header row of variable names and exactly one
+    /// row of values, use load_parameter_sets for files with several rows.
+    /// Expressions are evaluated in file order and can use previously loaded variables.
+    /// When loading fails, no variable of Calculator is changed.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path of the file, the format is chosen by the extension
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - All variables have been loaded
+    /// * `Err(CalculatorError::FileError)` - Reading, parsing or evaluating the file failed
+    pub fn load_variables<P: AsRef<Path>>(&mut self, path: P) -> Result<(), CalculatorError> {
+        let path = path.as_ref();
+        let format = file_format(path)?;
+        let text = fs::read_to_string(path).map_err(|err| file_error(path, None, None, err))?;
+        let parameters = match format {
+            FileFormat::Json => into_parameters(path, json_entries(path, &text)?)?,
+            FileFormat::Toml => into_parameters(path, toml_entries(path, &text)?)?,
+            FileFormat::Csv => {
+                let mut rows = read_csv(path, &text)?;
+                if rows.len() != 1 {
+                    return Err(file_error(
+                        path,
+                        None,
+                        None,
+                        format!("expected one row of values, found {}", rows.len()),
+                    ));
+                }
+                rows.remove(0)
+            }
+        };
+        let mut loaded = self.clone();
+        loaded.apply_parameters(path, parameters)?;
+        *self = loaded;
+        Ok(())
+    }
+
+    /// Load sets of variables from a CSV file, for example for parameter sweeps.
+    ///
+    /// The first row of the file contains the variable names and every following row
+    /// one set of values or expressions, which are evaluated from left to right.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path of the CSV file
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<Calculator>)` - One copy of Calculator with the loaded variables for every row
+    /// * `Err(CalculatorError::FileError)` - Reading, parsing or evaluating the file failed
+    pub fn load_parameter_sets<P: AsRef<Path>>(
+        &self,
+        path: P,
+    ) -> Result<Vec<Calculator>, CalculatorError> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path).map_err(|err| file_error(path, None, None, err))?;
+        read_csv(path, &text)?
+            .into_iter()
+            .map(|parameters| {
+                let mut loaded = self.clone();
+                loaded.apply_parameters(path, parameters)?;
+                Ok(loaded)
+            })
+            .collect()
+    }
+
+    /// Save variables to a JSON, TOML or CSV file.
+    ///
+    /// Writes the names and values of all variables with a value sorted by name,
+    /// CSV files get a header row and one row of values.
+    /// JSON has no representation of infinite values and NaN, saving them to JSON fails.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path of the file, the format is chosen by the extension
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - Variables have been saved
+    /// * `Err(CalculatorError::FileError)` - A value cannot be represented, serializing or writing the file failed
+    pub fn save_variables<P: AsRef<Path>>(&self, path: P) -> Result<(), CalculatorError> {
+        let path = path.as_ref();
+        let variables: BTreeMap<&str, f64> = self.iter().collect();
+        let text = match file_format(path)? {
+            FileFormat::Json => {
+                if let Some((key, value)) = variables.iter().find(|(_, value)| !value.is_finite()) {
+                    return Err(file_error(
+                        path,
+                        None,
+                        Some(key),
+                        format!("value {} cannot be written to JSON", value),
+                    ));
+                }
+                serde_json::to_string_pretty(&variables)
+                    .map_err(|err| file_error(path, None, None, err))?
+            }
+            FileFormat::Toml => {
+                toml::to_string(&variables).map_err(|err| file_error(path, None, None, err))?
+            }
+            FileFormat::Csv => {
+                let mut writer = csv::Writer::from_writer(Vec::new());
+                writer
+                    .write_record(variables.keys())
+                    .and_then(|_| {
+                        writer.write_record(variables.values().map(|value| value.to_string()))
+                    })
+                    .map_err(|err| file_error(path, None, None, err))?;
+                let bytes = writer
+                    .into_inner()
+                    .map_err(|err| file_error(path, None, None, err))?;
+                String::from_utf8(bytes).map_err(|err| file_error(path, None, None, err))?
+            }
+        };
+        fs::write(path, text).map_err(|err| file_error(path, None, None, err))
+    }
+
+    /// Set parameters in order, evaluating expressions with the variables set so far.
+    fn apply_parameters(
+        &mut self,
+        path: &Path,
+        parameters: Vec<Parameter>,
+    ) -> Result<(), CalculatorError> {
+        for Parameter { key, line, value } in parameters {
+            let value = match value {
+                ParameterValue::Number(x) => x,
+                ParameterValue::Expression(expression) => self
+                    .evaluate(&expression)
+                    .map_err(|err| file_error(path, line, Some(&key), err))?,
+            };
+            self.set_variable(&key, value);
+        }
+        Ok(())
+    }
+}
+
+/// Return parameters of the rows of a CSV file.
+fn read_csv(path: &Path, text: &str) -> Result<Vec<Vec<Parameter>>, CalculatorError> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_reader(text.as_bytes());
+    let header: Vec<String> = reader
+        .headers()
+        .map_err(|err| file_error(path, Some(1), None, err))?
+        .iter()
+        .map(|name| name.to_string())
+        .collect();
+    let mut rows = Vec::new();
+    for record in reader.records() {
+        let record = record.map_err(|err| {
+            let line = err.position().map(|position| position.line() as usize);
+            file_error(path, line, None, err)
+        })?;
+        let line = record.position().map(|position| position.line() as usize);
+        let parameters = header
+            .iter()
+            .zip(record.iter())
+            .map(|(name, field)| {
+                let value = match field.parse::<f64>() {
+                    Ok(x) => ParameterValue::Number(x),
+                    Err(_) => ParameterValue::Expression(field.to_string()),
+                };
+                Parameter {
+                    key: name.clone(),
+                    line,
+                    value,
+                }
+            })
+            .collect();
+        rows.push(parameters);
+    }
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Calculator, CalculatorError};
+    use std::fs;
+    use std::path::PathBuf;
+
+    /// Return path of a file with `content` in the temporary directory.
+    fn write_file(name: &str, content: &str) -> PathBuf {
+        let path =
+            std::env::temp_dir().join(format!("qoqo_calculator_{}_{}", std::process::id(), name));
+        fs::write(&path, content).unwrap();
+        path
+    }
+
+    // Test loading JSON and TOML maps with expressions in file order
+    #[test]
+    fn load_json_toml() {
+        let json = write_file(
+            "load.json",
+            "{\n  \"theta\": 0.5,\n  \"phi\": \"2*theta\",\n  \"n\": 3\n}",
+        );
+        let toml = write_file("load.toml", "theta = 0.5\nphi = \"2*theta\"\nn = 3\n");
+        for path in [json, toml].iter() {
+            let mut calculator = Calculator::new();
+            calculator.load_variables(path).unwrap();
+            assert_eq!(calculator.get_variable("theta"), Ok(0.5));
+            assert_eq!(calculator.get_variable("phi"), Ok(1.0));
+            assert_eq!(calculator.get_variable("n"), Ok(3.0));
+            fs::remove_file(path).unwrap();
+        }
+    }
+
+    // Test errors include file, line and key and leave Calculator unchanged
+    #[test]
+    fn load_errors() {
+        let json = write_file("error.json", "{\n  \"theta\": 0.5,\n  \"phi\": \"2*x\"\n}");
+        let mut calculator = Calculator::new();
+        assert_eq!(
+            calculator.load_variables(&json),
+            Err(CalculatorError::FileError {
+                file: json.display().to_string(),
+                line: Some(3),
+                key: Some("phi".to_string()),
+                msg: "Variable \"x\" not set.".to_string()
+            })
+        );
+        assert!(!calculator.contains("theta"));
+        fs::remove_file(&json).unwrap();
+
+        // Lines come from the position of the key, not from a text search for its name
+        let json = write_file(
+            "duplicate.json",
+            "{\n  \"phi\": 1,\n  \"x\": \"phi\",\n  \"phi\": \"2*y\"\n}",
+        );
+        match calculator.load_variables(&json) {
+            Err(CalculatorError::FileError { line, key, .. }) => {
+                assert_eq!((line, key), (Some(4), Some("phi".to_string())))
+            }
+            _ => panic!("Expected FileError"),
+        }
+        fs::remove_file(&json).unwrap();
+
+        let toml = write_file("error.toml", "theta = 0.5\n'phi' = [1]\n");
+        match calculator.load_variables(&toml) {
+            Err(CalculatorError::FileError { line, key, .. }) => {
+                assert_eq!((line, key), (Some(2), Some("phi".to_string())))
+            }
+            _ => panic!("Expected FileError"),
+        }
+        fs::remove_file(&toml).unwrap();
+
+        let unknown = write_file("error.yaml", "theta: 0.5");
+        assert!(matches!(
+            calculator.load_variables(&unknown),
+            Err(CalculatorError::FileError { .. })
+        ));
+        fs::remove_file(&unknown).unwrap();
+    }
+
+    // Test loading parameter sets from CSV rows
+    #[test]
+    fn load_csv() {
+        let path = write_file("sweep.csv", "theta, phi\n0.5, 2*theta\n1.0, theta+x\n");
+        let mut calculator = Calculator::new();
+        calculator.set_variable("x", 1.0);
+        let sets = calculator.load_parameter_sets(&path).unwrap();
+        assert_eq!(sets.len(), 2);
+        assert_eq!(sets[0].get_variable("phi"), Ok(1.0));
+        assert_eq!(sets[1].get_variable("phi"), Ok(2.0));
+        assert!(matches!(
+            calculator.load_variables(&path),
+            Err(CalculatorError::FileError { .. })
+        ));
+        calculator.remove_variable("x");
+        match calculator.load_parameter_sets(&path) {
+            Err(CalculatorError::FileError { line, key, .. }) => {
+                assert_eq!((line, key), (Some(3), Some("phi".to_string())))
+            }
+            _ => panic!("Expected FileError"),
+        }
+        fs::remove_file(&path).unwrap();
+    }
+
+    // Test round trip of saved variables
+    #[test]
+    fn save_load() {
+        let mut calculator = Calculator::new();
+        calculator.set_variable("theta", 0.25);
+        calculator.set_variable("phi", -1.5);
+        for extension in ["json", "toml", "csv"].iter() {
+            let path = write_file(&format!("save.{}", extension), "");
+            calculator.save_variables(&path).unwrap();
+            let mut loaded = Calculator::new();
+            loaded.load_variables(&path).unwrap();
+            let mut variables: Vec<(&str, f64)> = loaded.iter().collect();
+            variables.sort_by(|a, b| a.0.cmp(b.0));
+            assert_eq!(variables, vec![("phi", -1.5), ("theta", 0.25)]);
+            fs::remove_file(&path).unwrap();
+        }
+
+        // Infinite values and NaN round trip in TOML and CSV but cannot be written to JSON
+        calculator.set_variable("phi", f64::NEG_INFINITY);
+        calculator.set_variable("theta", f64::NAN);
+        for extension in ["toml", "csv"].iter() {
+            let path = write_file(&format!("non_finite.{}", extension), "");
+            calculator.save_variables(&path).unwrap();
+            let mut loaded = Calculator::new();
+            loaded.load_variables(&path).unwrap();
+            assert_eq!(loaded.get_variable("phi"), Ok(f64::NEG_INFINITY));
+            assert!(loaded.get_variable("theta").unwrap().is_nan());
+            fs::remove_file(&path).unwrap();
+        }
+        let path = write_file("non_finite.json", "");
+        assert_eq!(
+            calculator.save_variables(&path),
+            Err(CalculatorError::FileError {
+                file: path.display().to_string(),
+                line: None,
+                key: Some("phi".to_string()),
+                msg: "value -inf cannot be written to JSON".to_string()
+            })
+        );
+        assert_eq!(fs::read_to_string(&path).unwrap(), "");
+        fs::remove_file(&path).unwrap();
+    }
+}
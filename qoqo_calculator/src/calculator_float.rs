@@ -15,7 +15,8 @@
 //! Provides CalculatorFloat enum and methods for parsing and evaluating
 //! mathematical expressions in string form to float.
 
-use crate::CalculatorError;
+use crate::calculator_expr::BinaryOperator;
+use crate::{Calculator, CalculatorError, CalculatorExpr, SyntaxProblem};
 use serde::de::{Deserializer, Error, Visitor};
 use serde::ser::Serializer;
 use serde::{Deserialize, Serialize};
@@ -32,14 +33,26 @@ static RTOL: f64 = 1e-8;
 /// # Variants
 ///
 /// * `Float` - f64 value
-/// * `Str` - String instance
+/// * `Str` - Symbolic expression
 ///
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Clone, PartialEq)]
 pub enum CalculatorFloat {
     /// Floating point value
     Float(f64),
-    /// Symbolic expression in String form
-    Str(String),
+    /// Symbolic expression stored as shared expression tree
+    Str(CalculatorExpr),
+}
+
+/// Implement Debug trait for CalculatorFloat.
+///
+/// Symbolic expressions are shown in string form.
+impl fmt::Debug for CalculatorFloat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CalculatorFloat::Float(x) => f.debug_tuple("Float").field(x).finish(),
+            CalculatorFloat::Str(x) => f.debug_tuple("Str").field(&x.to_string()).finish(),
+        }
+    }
 }
 
 // Implementing serde serialization
@@ -65,16 +78,19 @@ impl Serialize for CalculatorFloat {
         if human_readable {
             match self {
                 CalculatorFloat::Float(x) => serializer.serialize_f64(*x),
-                CalculatorFloat::Str(x) => serializer.serialize_str(x),
+                CalculatorFloat::Str(x) => serializer.serialize_str(&x.to_string()),
             }
         } else {
             match self {
                 CalculatorFloat::Float(x) => {
                     serializer.serialize_newtype_variant("CalculatorFloat", 0, "Float", x)
                 }
-                CalculatorFloat::Str(x) => {
-                    serializer.serialize_newtype_variant("CalculatorFloat", 1, "Str", x)
-                }
+                CalculatorFloat::Str(x) => serializer.serialize_newtype_variant(
+                    "CalculatorFloat",
+                    1,
+                    "Str",
+                    &x.to_string(),
+                ),
             }
         }
     }
@@ -278,7 +294,7 @@ impl<'de> Deserialize<'de> for CalculatorFloat {
                         ),
                         (Variant::Str, variant) => Result::map(
                             serde::de::VariantAccess::newtype_variant::<String>(variant),
-                            CalculatorFloat::Str,
+                            |x| CalculatorFloat::Str(CalculatorExpr::symbol(x)),
                         ),
                     }
                 }
@@ -376,7 +392,7 @@ impl From<String> for CalculatorFloat {
     fn from(item: String) -> Self {
         let f = f64::from_str(item.as_str());
         match f {
-            Err(_) => CalculatorFloat::Str(item),
+            Err(_) => CalculatorFloat::Str(CalculatorExpr::symbol(item)),
             Ok(x) => CalculatorFloat::Float(x),
         }
     }
@@ -392,7 +408,7 @@ impl From<&String> for CalculatorFloat {
     fn from(item: &String) -> Self {
         let f = f64::from_str(item.as_str());
         match f {
-            Err(_) => CalculatorFloat::Str(item.clone()),
+            Err(_) => CalculatorFloat::Str(CalculatorExpr::symbol(item.clone())),
             Ok(x) => CalculatorFloat::Float(x),
         }
     }
@@ -408,12 +424,40 @@ impl From<&str> for CalculatorFloat {
     fn from(item: &str) -> Self {
         let f = f64::from_str(item);
         match f {
-            Err(_) => CalculatorFloat::Str(String::from(item)),
+            Err(_) => CalculatorFloat::Str(CalculatorExpr::symbol(String::from(item))),
             Ok(x) => CalculatorFloat::Float(x),
         }
     }
 }
 
+/// Parse CalculatorFloat from a string, rejecting malformed expressions.
+///
+/// Unlike `From<&str>`, which accepts any string, symbolic values are
+/// validated with Calculator::validate before they are accepted.
+///
+/// # Returns
+///
+/// * `Ok(CalculatorFloat::Float)` - String is a float
+/// * `Ok(CalculatorFloat::Str)` - String is a syntactically valid expression
+/// * `Err(CalculatorError::InvalidExpression)` - String contains syntax problems
+///
+impl FromStr for CalculatorFloat {
+    type Err = CalculatorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Ok(x) = f64::from_str(s) {
+            return Ok(CalculatorFloat::Float(x));
+        }
+        match Calculator::validate(s) {
+            Ok(()) => Ok(CalculatorFloat::Str(CalculatorExpr::symbol(s.to_string()))),
+            Err(problems) => Err(CalculatorError::InvalidExpression {
+                expression: s.to_string(),
+                problems,
+            }),
+        }
+    }
+}
+
 /// Try turning CalculatorFloat into f64 float.
 ///
 /// # Returns
@@ -430,7 +474,9 @@ impl TryFrom<CalculatorFloat> for f64 {
     fn try_from(value: CalculatorFloat) -> Result<Self, Self::Error> {
         match value {
             CalculatorFloat::Float(x) => Ok(x),
-            CalculatorFloat::Str(x) => Err(CalculatorError::FloatSymbolicNotConvertable { val: x }),
+            CalculatorFloat::Str(x) => {
+                Err(CalculatorError::FloatSymbolicNotConvertable { val: x.to_string() })
+            }
         }
     }
 }
@@ -509,7 +555,7 @@ impl CalculatorFloat {
     pub fn sqrt(&self) -> CalculatorFloat {
         match self {
             CalculatorFloat::Float(f) => CalculatorFloat::Float(f.sqrt()),
-            CalculatorFloat::Str(s) => CalculatorFloat::Str(format!("sqrt({})", s)),
+            CalculatorFloat::Str(s) => CalculatorFloat::Str(s.function_1_argument("sqrt")),
         }
     }
     /// Return atan2 for CalculatorFloat and generic type `T`.
@@ -522,16 +568,11 @@ impl CalculatorFloat {
     where
         CalculatorFloat: From<T>,
     {
-        let other_from = Self::from(other);
-        match self {
-            Self::Float(x) => match other_from {
-                Self::Float(y) => CalculatorFloat::Float(x.atan2(y)),
-                Self::Str(y) => Self::Str(format!("atan2({:e}, {})", x, &y)),
-            },
-            Self::Str(x) => match other_from {
-                Self::Float(y) => Self::Str(format!("atan2({}, {:e})", x, y)),
-                Self::Str(y) => Self::Str(format!("atan2({}, {})", x, &y)),
-            },
+        match (self, Self::from(other)) {
+            (Self::Float(x), Self::Float(y)) => CalculatorFloat::Float(x.atan2(y)),
+            (x, y) => <Self as From<CalculatorExpr>>::from(
+                CalculatorExpr::from(x).function_2_arguments("atan2", CalculatorExpr::from(y)),
+            ),
         }
     }
 
@@ -545,16 +586,13 @@ impl CalculatorFloat {
     where
         CalculatorFloat: From<T>,
     {
-        let other_from = Self::from(other);
-        match self {
-            Self::Float(x) => match other_from {
-                Self::Float(y) => CalculatorFloat::Float(x.powf(y)),
-                Self::Str(y) => Self::Str(format!("({:e} ^ {})", x, &y)),
-            },
-            Self::Str(x) => match other_from {
-                Self::Float(y) => Self::Str(format!("({} ^ {:e})", x, y)),
-                Self::Str(y) => Self::Str(format!("({} ^ {})", x, &y)),
-            },
+        match (self, Self::from(other)) {
+            (Self::Float(x), Self::Float(y)) => CalculatorFloat::Float(x.powf(y)),
+            (x, y) => <Self as From<CalculatorExpr>>::from(CalculatorExpr::binary(
+                BinaryOperator::Pow,
+                CalculatorExpr::from(x),
+                CalculatorExpr::from(y),
+            )),
         }
     }
 
@@ -562,42 +600,42 @@ impl CalculatorFloat {
     pub fn exp(&self) -> CalculatorFloat {
         match self {
             Self::Float(x) => CalculatorFloat::Float(x.exp()),
-            Self::Str(y) => Self::Str(format!("exp({})", y)),
+            Self::Str(y) => Self::Str(y.function_1_argument("exp")),
         }
     }
     /// Return sine function sin(x) for CalculatorFloat.
     pub fn sin(&self) -> CalculatorFloat {
         match self {
             Self::Float(x) => CalculatorFloat::Float(x.sin()),
-            Self::Str(y) => Self::Str(format!("sin({})", y)),
+            Self::Str(y) => Self::Str(y.function_1_argument("sin")),
         }
     }
     /// Return cosine function cos(x) for CalculatorFloat.
     pub fn cos(&self) -> CalculatorFloat {
         match self {
             Self::Float(x) => CalculatorFloat::Float(x.cos()),
-            Self::Str(y) => Self::Str(format!("cos({})", y)),
+            Self::Str(y) => Self::Str(y.function_1_argument("cos")),
         }
     }
     /// Return arccosine function acos(x) for CalculatorFloat.
     pub fn acos(&self) -> CalculatorFloat {
         match self {
             Self::Float(x) => CalculatorFloat::Float(x.acos()),
-            Self::Str(y) => Self::Str(format!("acos({})", y)),
+            Self::Str(y) => Self::Str(y.function_1_argument("acos")),
         }
     }
     /// Return absolute value abs(x) for CalculatorFloat.
     pub fn abs(&self) -> CalculatorFloat {
         match self {
             Self::Float(x) => CalculatorFloat::Float(x.abs()),
-            Self::Str(y) => Self::Str(format!("abs({})", y)),
+            Self::Str(y) => Self::Str(y.function_1_argument("abs")),
         }
     }
     /// Return signum value sign(x) for CalculatorFloat.
     pub fn signum(&self) -> CalculatorFloat {
         match self {
             Self::Float(x) => CalculatorFloat::Float(x.signum()),
-            Self::Str(y) => Self::Str(format!("sign({})", y)),
+            Self::Str(y) => Self::Str(y.function_1_argument("sign")),
         }
     }
     /// Return True if self value is close to other value.
@@ -609,10 +647,10 @@ impl CalculatorFloat {
         match self {
             Self::Float(x) => match other_from {
                 Self::Float(y) => (x - y).abs() <= (ATOL + RTOL * y.abs()),
-                Self::Str(y) => format!("{:e}", x) == y,
+                Self::Str(y) => format!("{:e}", x) == y.to_string(),
             },
             Self::Str(x) => match other_from {
-                Self::Float(y) => x == &format!("{:e}", y),
+                Self::Float(y) => x.to_string() == format!("{:e}", y),
                 Self::Str(y) => x == &y,
             },
         }
@@ -622,7 +660,22 @@ impl CalculatorFloat {
     pub fn float(&self) -> Result<&f64, CalculatorError> {
         match self {
             Self::Float(x) => Ok(x),
-            Self::Str(x) => Err(CalculatorError::FloatSymbolicNotConvertable { val: x.clone() }),
+            Self::Str(x) => {
+                Err(CalculatorError::FloatSymbolicNotConvertable { val: x.to_string() })
+            }
+        }
+    }
+
+    /// Validate the syntax of a symbolic CalculatorFloat without evaluating it.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - Value is a float or a syntactically valid expression
+    /// * `Err(Vec<SyntaxProblem>)` - All syntax problems of the expression
+    pub fn validate(&self) -> Result<(), Vec<SyntaxProblem>> {
+        match self {
+            Self::Float(_) => Ok(()),
+            Self::Str(x) => Calculator::validate(&x.to_string()),
         }
     }
 
@@ -630,7 +683,331 @@ impl CalculatorFloat {
     pub fn recip(&self) -> CalculatorFloat {
         match self {
             Self::Float(x) => Self::Float(x.recip()),
-            Self::Str(y) => Self::Str(format!("(1 / {})", y)),
+            // The numerator is kept as symbol to write `1` instead of `1e0`
+            Self::Str(y) => Self::Str(CalculatorExpr::binary(
+                BinaryOperator::Div,
+                CalculatorExpr::symbol(String::from("1")),
+                y.clone(),
+            )),
+        }
+    }
+
+    /// Return remainder of truncated division x - y * trunc(x / y).
+    fn remainder(&self, other: CalculatorFloat) -> CalculatorFloat {
+        match (self, &other) {
+            (Self::Float(x), Self::Float(y)) => {
+                if *y == 0.0 {
+                    panic!("Division by zero")
+                }
+                Self::Float(x % y)
+            }
+            _ => {
+                let quotient = self.clone() / &other;
+                let truncated = quotient.signum() * quotient.abs().floor();
+                self.clone() - other * truncated
+            }
+        }
+    }
+
+    /// Return tangent function tan(x) for CalculatorFloat.
+    pub fn tan(&self) -> CalculatorFloat {
+        match self {
+            Self::Float(x) => CalculatorFloat::Float(x.tan()),
+            Self::Str(y) => Self::Str(y.function_1_argument("tan")),
+        }
+    }
+
+    /// Return arcsine function asin(x) for CalculatorFloat.
+    pub fn asin(&self) -> CalculatorFloat {
+        match self {
+            Self::Float(x) => CalculatorFloat::Float(x.asin()),
+            Self::Str(y) => Self::Str(y.function_1_argument("asin")),
+        }
+    }
+
+    /// Return arctangent function atan(x) for CalculatorFloat.
+    pub fn atan(&self) -> CalculatorFloat {
+        match self {
+            Self::Float(x) => CalculatorFloat::Float(x.atan()),
+            Self::Str(y) => Self::Str(y.function_1_argument("atan")),
+        }
+    }
+
+    /// Return hyperbolic sine function sinh(x) for CalculatorFloat.
+    pub fn sinh(&self) -> CalculatorFloat {
+        match self {
+            Self::Float(x) => CalculatorFloat::Float(x.sinh()),
+            Self::Str(y) => Self::Str(y.function_1_argument("sinh")),
+        }
+    }
+
+    /// Return hyperbolic cosine function cosh(x) for CalculatorFloat.
+    pub fn cosh(&self) -> CalculatorFloat {
+        match self {
+            Self::Float(x) => CalculatorFloat::Float(x.cosh()),
+            Self::Str(y) => Self::Str(y.function_1_argument("cosh")),
+        }
+    }
+
+    /// Return hyperbolic tangent function tanh(x) for CalculatorFloat.
+    pub fn tanh(&self) -> CalculatorFloat {
+        match self {
+            Self::Float(x) => CalculatorFloat::Float(x.tanh()),
+            Self::Str(y) => Self::Str(y.function_1_argument("tanh")),
+        }
+    }
+
+    /// Return inverse hyperbolic sine function asinh(x) for CalculatorFloat.
+    pub fn asinh(&self) -> CalculatorFloat {
+        match self {
+            Self::Float(x) => CalculatorFloat::Float(x.asinh()),
+            Self::Str(y) => Self::Str(y.function_1_argument("asinh")),
+        }
+    }
+
+    /// Return inverse hyperbolic cosine function acosh(x) for CalculatorFloat.
+    pub fn acosh(&self) -> CalculatorFloat {
+        match self {
+            Self::Float(x) => CalculatorFloat::Float(x.acosh()),
+            Self::Str(y) => Self::Str(y.function_1_argument("acosh")),
+        }
+    }
+
+    /// Return inverse hyperbolic tangent function atanh(x) for CalculatorFloat.
+    pub fn atanh(&self) -> CalculatorFloat {
+        match self {
+            Self::Float(x) => CalculatorFloat::Float(x.atanh()),
+            Self::Str(y) => Self::Str(y.function_1_argument("atanh")),
+        }
+    }
+
+    /// Return natural logarithm log(x) for CalculatorFloat.
+    pub fn ln(&self) -> CalculatorFloat {
+        match self {
+            Self::Float(x) => CalculatorFloat::Float(x.ln()),
+            Self::Str(y) => Self::Str(y.function_1_argument("log")),
+        }
+    }
+
+    /// Return decimal logarithm log10(x) for CalculatorFloat.
+    pub fn log10(&self) -> CalculatorFloat {
+        match self {
+            Self::Float(x) => CalculatorFloat::Float(x.log10()),
+            Self::Str(y) => Self::Str(y.function_1_argument("log10")),
+        }
+    }
+
+    /// Return base 2 exponential function exp2(x) for CalculatorFloat.
+    pub fn exp2(&self) -> CalculatorFloat {
+        match self {
+            Self::Float(x) => CalculatorFloat::Float(x.exp2()),
+            Self::Str(y) => Self::Str(y.function_1_argument("exp2")),
+        }
+    }
+
+    /// Return exponential function minus one expm1(x) for CalculatorFloat.
+    pub fn exp_m1(&self) -> CalculatorFloat {
+        match self {
+            Self::Float(x) => CalculatorFloat::Float(x.exp_m1()),
+            Self::Str(y) => Self::Str(y.function_1_argument("expm1")),
+        }
+    }
+
+    /// Return cubic root cbrt(x) for CalculatorFloat.
+    pub fn cbrt(&self) -> CalculatorFloat {
+        match self {
+            Self::Float(x) => CalculatorFloat::Float(x.cbrt()),
+            Self::Str(y) => Self::Str(y.function_1_argument("cbrt")),
+        }
+    }
+
+    /// Return smallest integer greater or equal ceil(x) for CalculatorFloat.
+    pub fn ceil(&self) -> CalculatorFloat {
+        match self {
+            Self::Float(x) => CalculatorFloat::Float(x.ceil()),
+            Self::Str(y) => Self::Str(y.function_1_argument("ceil")),
+        }
+    }
+
+    /// Return largest integer less or equal floor(x) for CalculatorFloat.
+    pub fn floor(&self) -> CalculatorFloat {
+        match self {
+            Self::Float(x) => CalculatorFloat::Float(x.floor()),
+            Self::Str(y) => Self::Str(y.function_1_argument("floor")),
+        }
+    }
+
+    /// Return fractional part fract(x) for CalculatorFloat.
+    pub fn fract(&self) -> CalculatorFloat {
+        match self {
+            Self::Float(x) => CalculatorFloat::Float(x.fract()),
+            Self::Str(y) => Self::Str(y.function_1_argument("fract")),
+        }
+    }
+
+    /// Return nearest integer round(x) for CalculatorFloat.
+    pub fn round(&self) -> CalculatorFloat {
+        match self {
+            Self::Float(x) => CalculatorFloat::Float(x.round()),
+            Self::Str(y) => Self::Str(y.function_1_argument("round")),
+        }
+    }
+
+    /// Return Kronecker delta function delta(x) for CalculatorFloat.
+    ///
+    /// Evaluates to 1 when x is zero and to 0 otherwise.
+    pub fn delta(&self) -> CalculatorFloat {
+        match self {
+            Self::Float(x) => {
+                if (x - 0.0).abs() < ATOL {
+                    CalculatorFloat::Float(1.0)
+                } else {
+                    CalculatorFloat::Float(0.0)
+                }
+            }
+            Self::Str(y) => Self::Str(y.function_1_argument("delta")),
+        }
+    }
+    /// Return Heaviside step function theta(x) for CalculatorFloat.
+    ///
+    /// Evaluates to 0 for negative x, to 0.5 when x is zero and to 1 otherwise.
+    pub fn theta(&self) -> CalculatorFloat {
+        match self {
+            Self::Float(x) => {
+                if (x - 0.0).abs() < ATOL {
+                    CalculatorFloat::Float(0.5)
+                } else if *x < 0.0 {
+                    CalculatorFloat::Float(0.0)
+                } else {
+                    CalculatorFloat::Float(1.0)
+                }
+            }
+            Self::Str(y) => Self::Str(y.function_1_argument("theta")),
+        }
+    }
+
+    /// Return euclidean norm hypot(x, y) for CalculatorFloat and generic type `T`.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - Any type T for which CalculatorFloat::From<T> trait is implemented
+    ///
+    pub fn hypot<T>(&self, other: T) -> CalculatorFloat
+    where
+        CalculatorFloat: From<T>,
+    {
+        match (self, Self::from(other)) {
+            (Self::Float(x), Self::Float(y)) => CalculatorFloat::Float(x.hypot(y)),
+            (x, y) => <Self as From<CalculatorExpr>>::from(
+                CalculatorExpr::from(x).function_2_arguments("hypot", CalculatorExpr::from(y)),
+            ),
+        }
+    }
+
+    /// Return maximum max(x, y) for CalculatorFloat and generic type `T`.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - Any type T for which CalculatorFloat::From<T> trait is implemented
+    ///
+    pub fn max<T>(&self, other: T) -> CalculatorFloat
+    where
+        CalculatorFloat: From<T>,
+    {
+        match (self, Self::from(other)) {
+            (Self::Float(x), Self::Float(y)) => CalculatorFloat::Float(x.max(y)),
+            (x, y) => <Self as From<CalculatorExpr>>::from(
+                CalculatorExpr::from(x).function_2_arguments("max", CalculatorExpr::from(y)),
+            ),
+        }
+    }
+
+    /// Return minimum min(x, y) for CalculatorFloat and generic type `T`.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - Any type T for which CalculatorFloat::From<T> trait is implemented
+    ///
+    pub fn min<T>(&self, other: T) -> CalculatorFloat
+    where
+        CalculatorFloat: From<T>,
+    {
+        match (self, Self::from(other)) {
+            (Self::Float(x), Self::Float(y)) => CalculatorFloat::Float(x.min(y)),
+            (x, y) => <Self as From<CalculatorExpr>>::from(
+                CalculatorExpr::from(x).function_2_arguments("min", CalculatorExpr::from(y)),
+            ),
+        }
+    }
+
+    /// Return sum of CalculatorFloat and CalculatorFloat reference.
+    ///
+    /// Works on borrowed values, symbolic operands are shared with the result.
+    pub(crate) fn add_ref(&self, other: &CalculatorFloat) -> CalculatorFloat {
+        match (self, other) {
+            (Self::Float(x), Self::Float(y)) => Self::Float(x + y),
+            _ => Self::from(CalculatorExpr::binary(
+                BinaryOperator::Add,
+                CalculatorExpr::from(self),
+                CalculatorExpr::from(other),
+            )),
+        }
+    }
+
+    /// Return difference of CalculatorFloat and CalculatorFloat reference.
+    ///
+    /// Works on borrowed values, symbolic operands are shared with the result.
+    pub(crate) fn sub_ref(&self, other: &CalculatorFloat) -> CalculatorFloat {
+        match (self, other) {
+            (Self::Float(x), Self::Float(y)) => Self::Float(x - y),
+            _ => Self::from(CalculatorExpr::binary(
+                BinaryOperator::Sub,
+                CalculatorExpr::from(self),
+                CalculatorExpr::from(other),
+            )),
+        }
+    }
+
+    /// Return product of CalculatorFloat and CalculatorFloat reference.
+    ///
+    /// Works on borrowed values, symbolic operands are shared with the result.
+    pub(crate) fn mul_ref(&self, other: &CalculatorFloat) -> CalculatorFloat {
+        match (self, other) {
+            (Self::Float(x), Self::Float(y)) => Self::Float(x * y),
+            _ => Self::from(CalculatorExpr::binary(
+                BinaryOperator::Mul,
+                CalculatorExpr::from(self),
+                CalculatorExpr::from(other),
+            )),
+        }
+    }
+
+    /// Return quotient of CalculatorFloat and CalculatorFloat reference.
+    ///
+    /// Works on borrowed values, symbolic operands are shared with the result.
+    ///
+    /// # Panics
+    ///
+    /// Panics on division by zero.
+    /// Division by zero is only detected when other is CalculatorFloat::Float
+    ///
+    pub(crate) fn div_ref(&self, other: &CalculatorFloat) -> CalculatorFloat {
+        match (self, other) {
+            (_, Self::Float(y)) if *y == 0.0 => panic!("Division by zero"),
+            (Self::Float(x), Self::Float(y)) => Self::Float(x / y),
+            _ => Self::from(CalculatorExpr::binary(
+                BinaryOperator::Div,
+                CalculatorExpr::from(self),
+                CalculatorExpr::from(other),
+            )),
+        }
+    }
+
+    /// Return negative of CalculatorFloat reference.
+    pub(crate) fn neg_ref(&self) -> CalculatorFloat {
+        match self {
+            Self::Float(x) => Self::Float(-x),
+            Self::Str(y) => Self::Str(-y.clone()),
         }
     }
 }
@@ -646,29 +1023,7 @@ where
 {
     type Output = Self;
     fn add(self, other: T) -> Self {
-        let other_from = Self::from(other);
-        match self {
-            Self::Float(x) => match other_from {
-                Self::Float(y) => CalculatorFloat::Float(x + y),
-                Self::Str(y) => {
-                    if x != 0.0 {
-                        Self::Str(format!("({:e} + {})", x, &y))
-                    } else {
-                        Self::Str(y)
-                    }
-                }
-            },
-            Self::Str(x) => match other_from {
-                Self::Float(y) => {
-                    if y != 0.0 {
-                        Self::Str(format!("({} + {:e})", &x, y))
-                    } else {
-                        Self::Str(x)
-                    }
-                }
-                Self::Str(y) => Self::Str(format!("({} + {})", &x, &y)),
-            },
-        }
+        self.add_ref(&Self::from(other))
     }
 }
 
@@ -699,36 +1054,7 @@ where
     CalculatorFloat: From<T>,
 {
     fn add_assign(&mut self, other: T) {
-        let other_from = CalculatorFloat::from(other);
-
-        match self {
-            Self::Float(x) => match other_from {
-                Self::Float(y) => {
-                    *self = Self::Float(*x + y);
-                }
-                Self::Str(y) => {
-                    *self = {
-                        if (*x - 0.0).abs() > ATOL {
-                            Self::Str(format!("({:e} + {})", x, &y))
-                        } else {
-                            Self::Str(y)
-                        }
-                    }
-                }
-            },
-            Self::Str(x) => match other_from {
-                Self::Float(y) => {
-                    *self = {
-                        if y != 0.0 {
-                            Self::Str(format!("({} + {:e})", x, y))
-                        } else {
-                            Self::Str(x.to_owned())
-                        }
-                    }
-                }
-                Self::Str(y) => *self = Self::Str(format!("({} + {})", x, &y)),
-            },
-        }
+        *self = self.add_ref(&Self::from(other));
     }
 }
 
@@ -738,35 +1064,66 @@ where
 ///
 /// * `other` - Any type T for which CalculatorFloat::From<T> trait is implemented
 ///
-impl<'a, T> ops::Add<T> for &'a CalculatorFloat
+impl<T> ops::Add<T> for &CalculatorFloat
 where
     CalculatorFloat: From<T>,
 {
     type Output = CalculatorFloat;
     fn add(self, other: T) -> CalculatorFloat {
-        let other_from = CalculatorFloat::from(other);
-        match self {
-            CalculatorFloat::Float(x) => match other_from {
-                CalculatorFloat::Float(y) => CalculatorFloat::Float(x + y),
-                CalculatorFloat::Str(y) => {
-                    if (x - 0.0).abs() > ATOL {
-                        CalculatorFloat::Str(format!("({:e} + {})", x, &y))
-                    } else {
-                        CalculatorFloat::Str(y)
-                    }
-                }
-            },
-            CalculatorFloat::Str(x) => match other_from {
-                CalculatorFloat::Float(y) => {
-                    if y != 0.0 {
-                        CalculatorFloat::Str(format!("({} + {:e})", x, y))
-                    } else {
-                        CalculatorFloat::Str(x.to_owned())
-                    }
-                }
-                CalculatorFloat::Str(y) => CalculatorFloat::Str(format!("({} + {})", x, &y)),
-            },
-        }
+        self.add_ref(&CalculatorFloat::from(other))
+    }
+}
+
+/// Implement `-` (subtract) for &CalculatorFloat and generic type `T`.
+///
+/// # Arguments
+///
+/// * `other` - Any type T for which CalculatorFloat::From<T> trait is implemented
+///
+impl<T> ops::Sub<T> for &CalculatorFloat
+where
+    CalculatorFloat: From<T>,
+{
+    type Output = CalculatorFloat;
+    fn sub(self, other: T) -> CalculatorFloat {
+        self.sub_ref(&CalculatorFloat::from(other))
+    }
+}
+
+/// Implement `*` (multiply) for &CalculatorFloat and generic type `T`.
+///
+/// # Arguments
+///
+/// * `other` - Any type T for which CalculatorFloat::From<T> trait is implemented
+///
+impl<T> ops::Mul<T> for &CalculatorFloat
+where
+    CalculatorFloat: From<T>,
+{
+    type Output = CalculatorFloat;
+    fn mul(self, other: T) -> CalculatorFloat {
+        self.mul_ref(&CalculatorFloat::from(other))
+    }
+}
+
+/// Implement `/` (divide) for &CalculatorFloat and generic type `T`.
+///
+/// # Arguments
+///
+/// * `other` - Any type T for which CalculatorFloat::From<T> trait is implemented
+///
+/// # Panics
+///
+/// Panics on division by zero.
+/// Division by zero is only detected when other is converted to CalculatorFloat::Float
+///
+impl<T> ops::Div<T> for &CalculatorFloat
+where
+    CalculatorFloat: From<T>,
+{
+    type Output = CalculatorFloat;
+    fn div(self, other: T) -> CalculatorFloat {
+        self.div_ref(&CalculatorFloat::from(other))
     }
 }
 
@@ -787,37 +1144,7 @@ where
 {
     type Output = Self;
     fn div(self, other: T) -> Self {
-        let other_from = Self::from(other);
-        match self {
-            Self::Float(x) => match other_from {
-                Self::Float(y) => {
-                    if y == 0.0 {
-                        panic!("Division by zero")
-                    } else {
-                        Self::Float(x / y)
-                    }
-                }
-                Self::Str(y) => {
-                    if x == 0.0 {
-                        Self::Float(0.0)
-                    } else {
-                        Self::Str(format!("({:e} / {})", x, &y))
-                    }
-                }
-            },
-            Self::Str(x) => match other_from {
-                Self::Float(y) => {
-                    if y == 0.0 {
-                        panic!("Division by zero")
-                    } else if (y - 1.0).abs() < ATOL {
-                        Self::Str(x)
-                    } else {
-                        Self::Str(format!("({} / {:e})", &x, y))
-                    }
-                }
-                Self::Str(y) => Self::Str(format!("({} / {})", &x, &y)),
-            },
-        }
+        self.div_ref(&Self::from(other))
     }
 }
 
@@ -837,43 +1164,7 @@ where
     CalculatorFloat: From<T>,
 {
     fn div_assign(&mut self, other: T) {
-        let other_from = Self::from(other);
-        match self {
-            Self::Float(x) => match other_from {
-                Self::Float(y) => {
-                    *self = {
-                        if y == 0.0 {
-                            panic!("Division by zero")
-                        } else {
-                            Self::Float(*x / y)
-                        }
-                    }
-                }
-                Self::Str(y) => {
-                    *self = {
-                        if (*x - 0.0).abs() < ATOL {
-                            Self::Float(0.0)
-                        } else {
-                            Self::Str(format!("({:e} / {})", x, &y))
-                        }
-                    }
-                }
-            },
-            Self::Str(x) => match other_from {
-                Self::Float(y) => {
-                    *self = {
-                        if y == 0.0 {
-                            panic!("Division by zero")
-                        } else if (y - 1.0).abs() < ATOL {
-                            Self::Str(x.to_owned())
-                        } else {
-                            Self::Str(format!("({} / {:e})", x, y))
-                        }
-                    }
-                }
-                Self::Str(y) => *self = Self::Str(format!("({} / {})", x, &y)),
-            },
-        }
+        *self = self.div_ref(&Self::from(other));
     }
 }
 
@@ -889,33 +1180,7 @@ where
 {
     type Output = Self;
     fn mul(self, other: T) -> Self {
-        let other_from = Self::from(other);
-        match self {
-            Self::Float(x) => match other_from {
-                Self::Float(y) => Self::Float(x * y),
-                Self::Str(y) => {
-                    if x == 0.0 {
-                        Self::Float(0.0)
-                    } else if (x - 1.0).abs() < ATOL {
-                        Self::Str(y)
-                    } else {
-                        Self::Str(format!("({:e} * {})", x, &y))
-                    }
-                }
-            },
-            Self::Str(x) => match other_from {
-                Self::Float(y) => {
-                    if y == 0.0 {
-                        Self::Float(0.0)
-                    } else if (y - 1.0).abs() < ATOL {
-                        Self::Str(x)
-                    } else {
-                        Self::Str(format!("({} * {:e})", &x, y))
-                    }
-                }
-                Self::Str(y) => Self::Str(format!("({} * {})", x, y)),
-            },
-        }
+        self.mul_ref(&Self::from(other))
     }
 }
 
@@ -930,39 +1195,7 @@ where
     CalculatorFloat: From<T>,
 {
     fn mul_assign(&mut self, other: T) {
-        let other_from = Self::from(other);
-        match self {
-            Self::Float(x) => match other_from {
-                Self::Float(y) => {
-                    *self = Self::Float(*x * y);
-                }
-                Self::Str(y) => {
-                    *self = {
-                        if (*x - 0.0).abs() < ATOL {
-                            Self::Float(0.0)
-                        } else if (*x - 1.0).abs() < ATOL {
-                            Self::Str(y)
-                        } else {
-                            Self::Str(format!("({:e} * {})", x, y))
-                        }
-                    }
-                }
-            },
-            Self::Str(x) => match other_from {
-                Self::Float(y) => {
-                    *self = {
-                        if y == 0.0 {
-                            Self::Float(0.0)
-                        } else if (y - 1.0).abs() < ATOL {
-                            Self::Str(x.to_string())
-                        } else {
-                            Self::Str(format!("({} * {:e})", x, y))
-                        }
-                    }
-                }
-                Self::Str(y) => *self = Self::Str(format!("({} * {})", x, y)),
-            },
-        }
+        *self = self.mul_ref(&Self::from(other));
     }
 }
 
@@ -978,29 +1211,7 @@ where
 {
     type Output = Self;
     fn sub(self, other: T) -> Self {
-        let other_from = Self::from(other);
-        match self {
-            CalculatorFloat::Float(x) => match other_from {
-                CalculatorFloat::Float(y) => CalculatorFloat::Float(x - y),
-                CalculatorFloat::Str(y) => {
-                    if x != 0.0 {
-                        CalculatorFloat::Str(format!("({:e} - {})", x, y))
-                    } else {
-                        CalculatorFloat::Str(format!("(-{})", &y))
-                    }
-                }
-            },
-            CalculatorFloat::Str(x) => match other_from {
-                CalculatorFloat::Float(y) => {
-                    if y != 0.0 {
-                        CalculatorFloat::Str(format!("({} - {:e})", x, y))
-                    } else {
-                        CalculatorFloat::Str(x)
-                    }
-                }
-                CalculatorFloat::Str(y) => CalculatorFloat::Str(format!("({} - {})", x, y)),
-            },
-        }
+        self.sub_ref(&Self::from(other))
     }
 }
 
@@ -1015,35 +1226,105 @@ where
     CalculatorFloat: From<T>,
 {
     fn sub_assign(&mut self, other: T) {
-        let other_from = Self::from(other);
-        match self {
-            Self::Float(x) => match other_from {
-                Self::Float(y) => {
-                    *self = Self::Float(*x - y);
+        *self = self.sub_ref(&Self::from(other));
+    }
+}
+
+/// Implement `%` (remainder) for CalculatorFloat and generic type `T`.
+///
+/// The remainder has the sign of the dividend like the f64 remainder,
+/// symbolic values are expressed through the Calculator functions sign, abs and floor.
+///
+/// # Arguments
+///
+/// * `other` - Any type T for which CalculatorFloat::From<T> trait is implemented
+///
+/// # Panics
+///
+/// Panics on division by zero.
+///
+impl<T> ops::Rem<T> for CalculatorFloat
+where
+    CalculatorFloat: From<T>,
+{
+    type Output = Self;
+    fn rem(self, other: T) -> Self {
+        self.remainder(Self::from(other))
+    }
+}
+
+/// Implement `%` (remainder) for &CalculatorFloat and generic type `T`.
+///
+/// # Arguments
+///
+/// * `other` - Any type T for which CalculatorFloat::From<T> trait is implemented
+///
+/// # Panics
+///
+/// Panics on division by zero.
+///
+impl<T> ops::Rem<T> for &CalculatorFloat
+where
+    CalculatorFloat: From<T>,
+{
+    type Output = CalculatorFloat;
+    fn rem(self, other: T) -> CalculatorFloat {
+        self.remainder(CalculatorFloat::from(other))
+    }
+}
+
+/// Implement arithmetic operators with a primitive left hand side and CalculatorFloat.
+///
+/// Allows writing `2.0 * x` instead of `x * 2.0` for CalculatorFloat `x`.
+macro_rules! impl_primitive_lhs_ops {
+    ($($primitive:ty),*) => {
+        $(
+            impl ops::Add<CalculatorFloat> for $primitive {
+                type Output = CalculatorFloat;
+                fn add(self, other: CalculatorFloat) -> CalculatorFloat {
+                    CalculatorFloat::from(self) + other
                 }
-                Self::Str(y) => {
-                    *self = {
-                        if (*x - 0.0).abs() > ATOL {
-                            Self::Str(format!("({:e} - {})", x, y))
-                        } else {
-                            Self::Str(format!("(-{})", y))
-                        }
-                    }
+            }
+
+            impl ops::Sub<CalculatorFloat> for $primitive {
+                type Output = CalculatorFloat;
+                fn sub(self, other: CalculatorFloat) -> CalculatorFloat {
+                    CalculatorFloat::from(self) - other
                 }
-            },
-            Self::Str(x) => match other_from {
-                Self::Float(y) => {
-                    *self = {
-                        if y != 0.0 {
-                            Self::Str(format!("({} - {:e})", x, y))
-                        } else {
-                            Self::Str(x.to_owned())
-                        }
-                    }
+            }
+
+            impl ops::Mul<CalculatorFloat> for $primitive {
+                type Output = CalculatorFloat;
+                fn mul(self, other: CalculatorFloat) -> CalculatorFloat {
+                    CalculatorFloat::from(self) * other
                 }
-                Self::Str(y) => *self = Self::Str(format!("({} - {})", x, y)),
-            },
+            }
+
+            impl ops::Div<CalculatorFloat> for $primitive {
+                type Output = CalculatorFloat;
+                fn div(self, other: CalculatorFloat) -> CalculatorFloat {
+                    CalculatorFloat::from(self) / other
+                }
+            }
+        )*
+    };
+}
+
+impl_primitive_lhs_ops!(f64, i32, u32);
+
+/// Implements multiplying over an iterator of CalculatorFloat
+///
+/// # Arguments
+///
+/// * `iter` - Any iterator over CalculatorFloat items
+///
+impl std::iter::Product for CalculatorFloat {
+    fn product<I: Iterator<Item = CalculatorFloat>>(iter: I) -> Self {
+        let mut product = CalculatorFloat::from(1);
+        for i in iter {
+            product *= i;
         }
+        product
     }
 }
 
@@ -1052,19 +1333,85 @@ impl ops::Neg for CalculatorFloat {
     type Output = CalculatorFloat;
 
     fn neg(self) -> Self {
-        match self {
-            Self::Float(x) => Self::Float(-x),
-            Self::Str(y) => Self::Str(format!("(-{})", y)),
-        }
+        self.neg_ref()
+    }
+}
+
+/// Implement minus sign for &CalculatorFloat.
+impl ops::Neg for &CalculatorFloat {
+    type Output = CalculatorFloat;
+
+    fn neg(self) -> CalculatorFloat {
+        self.neg_ref()
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::CalculatorFloat;
+    use crate::{Calculator, CalculatorError};
     use serde_test::{assert_tokens, Configure, Token};
     use std::convert::TryFrom;
 
+    // Test that summing agrees with adding the terms one by one, also for 10^5 terms
+    #[test]
+    fn sum() {
+        let terms = vec![
+            CalculatorFloat::from(0),
+            CalculatorFloat::from("x"),
+            CalculatorFloat::from(1.5),
+            CalculatorFloat::from(0),
+            CalculatorFloat::from("y"),
+        ];
+        let mut expected = CalculatorFloat::from(0);
+        for term in terms.iter() {
+            expected += term;
+        }
+        assert_eq!(terms.into_iter().sum::<CalculatorFloat>(), expected);
+        let floats: CalculatorFloat = (0..4).map(CalculatorFloat::from).sum();
+        assert_eq!(floats, CalculatorFloat::from(6.0));
+        let long: CalculatorFloat = (0..100_000).map(|_| CalculatorFloat::from("x")).sum();
+        assert_eq!(long.to_string().len(), 100_000 * 6 - 5);
+        assert!(long.to_string().ends_with(" + x) + x) + x)"));
+        let mut calculator = Calculator::new();
+        calculator.set_variable("x", 0.5);
+        assert_eq!(calculator.evaluate_float(&long), Ok(50_000.0));
+        assert_eq!(calculator.parse_get(long), Ok(50_000.0));
+    }
+
+    // Test that symbolic values share their expression tree and evaluate without the string form
+    #[test]
+    fn symbolic_tree() {
+        let x = CalculatorFloat::from("x");
+        let mut expression = CalculatorFloat::from(0);
+        for i in 1..=1000 {
+            expression = expression * &x + i;
+        }
+        let shared = expression.clone();
+        match (&expression, &shared) {
+            (CalculatorFloat::Str(a), CalculatorFloat::Str(b)) => assert_eq!(a, b),
+            _ => panic!("Expression is symbolic"),
+        }
+        let mut calculator = Calculator::new();
+        calculator.set_variable("x", 0.5);
+        let expected = (1..=1000).fold(0.0, |sum, i| sum * 0.5 + i as f64);
+        assert_eq!(calculator.evaluate_float(&shared), Ok(expected));
+        // Folded numeric values and the string form are unchanged
+        assert_eq!(x.clone() * 0.0, CalculatorFloat::Float(0.0));
+        assert_eq!(
+            (x.clone() + 1) * 2,
+            CalculatorFloat::from("((x + 1e0) * 2e0)")
+        );
+        assert_eq!(x.recip().to_string(), "(1 / x)");
+        // Symbolic leaves with assignments are rejected by the read-only evaluation
+        assert_eq!(
+            calculator.evaluate_float(&(CalculatorFloat::from("a = 2") + 1)),
+            Err(CalculatorError::AssignmentNotAllowed {
+                name: "a".to_string()
+            })
+        );
+    }
+
     // Test the serialization/deserialization of CalculatorFloat from string
     #[test]
     fn ser_de_string() {
@@ -1131,7 +1478,7 @@ mod tests {
         let inp: &str = "3t";
         let x = CalculatorFloat::from(inp);
         if let CalculatorFloat::Str(y) = x.clone() {
-            assert_eq!(y, "3t")
+            assert_eq!(y.to_string(), "3t")
         }
         assert!(!x.is_float());
 
@@ -1144,14 +1491,14 @@ mod tests {
         let x = CalculatorFloat::from(&test_string);
         test_string.push_str(&String::from("2t"));
         if let CalculatorFloat::Str(y) = x.clone() {
-            assert_eq!(y, "3t")
+            assert_eq!(y.to_string(), "3t")
         }
         assert!(!x.is_float());
 
         let test_string = String::from("3t");
         let x = CalculatorFloat::from(test_string);
         if let CalculatorFloat::Str(y) = x.clone() {
-            assert_eq!(y, "3t")
+            assert_eq!(y.to_string(), "3t")
         }
         assert!(!x.is_float());
 
@@ -1159,7 +1506,7 @@ mod tests {
         test_string.push_str("3t");
         let x = CalculatorFloat::from(test_string);
         if let CalculatorFloat::Str(y) = x.clone() {
-            assert_eq!(y, "3t")
+            assert_eq!(y.to_string(), "3t")
         }
         assert!(!x.is_float());
 
@@ -1200,6 +1547,28 @@ mod tests {
         assert!((x - f64::try_from(x3).unwrap()).abs() < f64::EPSILON);
     }
 
+    // Test strict parsing of CalculatorFloat from strings
+    #[test]
+    fn from_str() {
+        assert_eq!("2.5".parse(), Ok(CalculatorFloat::Float(2.5)));
+        assert_eq!(
+            "2*theta + sin(phi)".parse(),
+            Ok(CalculatorFloat::from("2*theta + sin(phi)"))
+        );
+        for expression in ["sin(x", "2 ++* y", "", "foo(1)"].iter() {
+            match expression.parse::<CalculatorFloat>() {
+                Err(CalculatorError::InvalidExpression {
+                    expression: invalid,
+                    problems,
+                }) => {
+                    assert_eq!(&invalid, expression);
+                    assert!(!problems.is_empty());
+                }
+                other => panic!("{:?} accepted as {:?}", expression, other),
+            }
+        }
+    }
+
     // Test the add functionality of CalculatorFloat with all possible input types
     #[test]
     fn add() {
@@ -1211,12 +1580,12 @@ mod tests {
         assert_eq!(x3 + 2.0, CalculatorFloat::Float(5.0));
 
         let x2 = &CalculatorFloat::from(0.0);
-        assert_eq!(x2 + "3t", CalculatorFloat::Str(String::from("3t")));
+        assert_eq!(x2 + "3t", CalculatorFloat::from("3t"));
 
         let x2s = &CalculatorFloat::from("3t");
-        assert_eq!(x2s + 0.0, CalculatorFloat::Str(String::from("3t")));
-        assert_eq!(x2s + 1.0, CalculatorFloat::Str(String::from("(3t + 1e0)")));
-        assert_eq!(x2s + "2x", CalculatorFloat::Str(String::from("(3t + 2x)")));
+        assert_eq!(x2s + 0.0, CalculatorFloat::from("3t"));
+        assert_eq!(x2s + 1.0, CalculatorFloat::from("(3t + 1e0)"));
+        assert_eq!(x2s + "2x", CalculatorFloat::from("(3t + 2x)"));
 
         // Test simple add function: x + y
         let mut x3 = CalculatorFloat::from(3);
@@ -1233,15 +1602,15 @@ mod tests {
 
         let x2 = CalculatorFloat::from(0.0);
         if let CalculatorFloat::Str(y) = x2.clone() + "3t" {
-            assert_eq!(y, "3t")
+            assert_eq!(y.to_string(), "3t")
         }
 
         let x2 = CalculatorFloat::from("3t");
         if let CalculatorFloat::Str(y) = x2.clone() + 0.0 {
-            assert_eq!(y, "3t")
+            assert_eq!(y.to_string(), "3t")
         }
         if let CalculatorFloat::Str(y) = x2.clone() + "2x" {
-            assert_eq!(y, "(3t + 2x)")
+            assert_eq!(y.to_string(), "(3t + 2x)")
         }
 
         // Test add_assign function: x += y
@@ -1252,35 +1621,35 @@ mod tests {
         }
         x3 += "x";
         if let CalculatorFloat::Str(y) = x3.clone() {
-            assert_eq!(y, "(5e0 + x)")
+            assert_eq!(y.to_string(), "(5e0 + x)")
         }
         let mut x3 = CalculatorFloat::from(0.0);
         x3 += "x";
         if let CalculatorFloat::Str(y) = x3.clone() {
-            assert_eq!(y, "x")
+            assert_eq!(y.to_string(), "x")
         }
         let mut x3s = CalculatorFloat::from("3t");
         if let CalculatorFloat::Str(y) = x3s.clone() + x2.clone() {
-            assert_eq!(y, "(3t + 2e0)")
+            assert_eq!(y.to_string(), "(3t + 2e0)")
         }
         if let CalculatorFloat::Str(y) = x3s.clone() + "2e0" {
-            assert_eq!(y, "(3t + 2e0)")
+            assert_eq!(y.to_string(), "(3t + 2e0)")
         }
         if let CalculatorFloat::Str(y) = x3s.clone() + x2.clone() {
-            assert_eq!(y, "(3t + 2e0)")
+            assert_eq!(y.to_string(), "(3t + 2e0)")
         }
 
         x3s += x2;
         if let CalculatorFloat::Str(y) = x3s.clone() {
-            assert_eq!(y, "(3t + 2e0)")
+            assert_eq!(y.to_string(), "(3t + 2e0)")
         }
         x3s += 0.0;
         if let CalculatorFloat::Str(y) = x3s.clone() {
-            assert_eq!(y, "(3t + 2e0)")
+            assert_eq!(y.to_string(), "(3t + 2e0)")
         }
         x3s += "x";
         if let CalculatorFloat::Str(y) = x3s.clone() {
-            assert_eq!(y, "((3t + 2e0) + x)")
+            assert_eq!(y.to_string(), "((3t + 2e0) + x)")
         }
     }
 
@@ -1293,10 +1662,7 @@ mod tests {
         assert_eq!(x3.clone() / x2.clone(), CalculatorFloat::Float(1.0));
         assert_eq!(x3.clone() / 3, CalculatorFloat::Float(1.0));
         assert_eq!(x3.clone() / 3.0, CalculatorFloat::Float(1.0));
-        assert_eq!(
-            x3.clone() / "x",
-            CalculatorFloat::Str(String::from("(3e0 / x)"))
-        );
+        assert_eq!(x3.clone() / "x", CalculatorFloat::from("(3e0 / x)"));
 
         let mut x0 = CalculatorFloat::from(0.0);
         assert_eq!(x0.clone() / "3t", x0);
@@ -1304,26 +1670,17 @@ mod tests {
         let mut x3s = CalculatorFloat::from("3t");
         assert_eq!(
             x3s.clone() / x2.clone(),
-            CalculatorFloat::Str(String::from("(3t / 3e0)"))
-        );
-        assert_eq!(
-            x3s.clone() / 2.0,
-            CalculatorFloat::Str(String::from("(3t / 2e0)"))
-        );
-        assert_eq!(
-            x3s.clone() / 2.0,
-            CalculatorFloat::Str(String::from("(3t / 2e0)"))
-        );
-        assert_eq!(
-            x3s.clone() / "2.0",
-            CalculatorFloat::Str(String::from("(3t / 2e0)"))
+            CalculatorFloat::from("(3t / 3e0)")
         );
+        assert_eq!(x3s.clone() / 2.0, CalculatorFloat::from("(3t / 2e0)"));
+        assert_eq!(x3s.clone() / 2.0, CalculatorFloat::from("(3t / 2e0)"));
+        assert_eq!(x3s.clone() / "2.0", CalculatorFloat::from("(3t / 2e0)"));
         assert_eq!(x3s.clone() / 1.0, x3s);
 
         let x2s = CalculatorFloat::from("2x");
         assert_eq!(
             x3s.clone() / x2s.clone(),
-            CalculatorFloat::Str(String::from("(3t / 2x)"))
+            CalculatorFloat::from("(3t / 2x)")
         );
 
         // Test div_assign function: x /= y
@@ -1334,13 +1691,13 @@ mod tests {
         assert_eq!(x0, CalculatorFloat::Float(0.0));
 
         x3 /= x2s.clone();
-        assert_eq!(x3, CalculatorFloat::Str(String::from("(1e0 / 2x)")));
+        assert_eq!(x3, CalculatorFloat::from("(1e0 / 2x)"));
         x3s /= 1.0;
-        assert_eq!(x3s, CalculatorFloat::Str(String::from("3t")));
+        assert_eq!(x3s, CalculatorFloat::from("3t"));
         x3s /= x2;
-        assert_eq!(x3s, CalculatorFloat::Str(String::from("(3t / 3e0)")));
+        assert_eq!(x3s, CalculatorFloat::from("(3t / 3e0)"));
         x3s /= x2s;
-        assert_eq!(x3s, CalculatorFloat::Str(String::from("((3t / 3e0) / 2x)")));
+        assert_eq!(x3s, CalculatorFloat::from("((3t / 3e0) / 2x)"));
     }
 
     // Test the division of CalculatorFloat from float by zero (should panic)
@@ -1384,33 +1741,24 @@ mod tests {
         assert_eq!(x3.clone() * x2.clone(), CalculatorFloat::Float(9.0));
         assert_eq!(x3.clone() * 3, CalculatorFloat::Float(9.0));
         assert_eq!(x3.clone() * 3.0, CalculatorFloat::Float(9.0));
-        assert_eq!(
-            x3.clone() * "x",
-            CalculatorFloat::Str(String::from("(3e0 * x)"))
-        );
+        assert_eq!(x3.clone() * "x", CalculatorFloat::from("(3e0 * x)"));
 
         let x2 = CalculatorFloat::from(0.0);
         assert_eq!(x2 * "x", CalculatorFloat::Float(0.0));
 
         let x2 = CalculatorFloat::from(1.0);
-        assert_eq!(x2 * "x", CalculatorFloat::Str(String::from("x")));
+        assert_eq!(x2 * "x", CalculatorFloat::from("x"));
 
         let mut x3s = CalculatorFloat::from("3t");
         let x2 = CalculatorFloat::from(3.0);
         assert_eq!(
             x3s.clone() * x2.clone(),
-            CalculatorFloat::Str(String::from("(3t * 3e0)"))
-        );
-        assert_eq!(
-            x3s.clone() * 2.0,
-            CalculatorFloat::Str(String::from("(3t * 2e0)"))
+            CalculatorFloat::from("(3t * 3e0)")
         );
+        assert_eq!(x3s.clone() * 2.0, CalculatorFloat::from("(3t * 2e0)"));
         assert_eq!(x3s.clone() * 0.0, CalculatorFloat::Float(0.0));
-        assert_eq!(x3s.clone() * 1.0, CalculatorFloat::Str(String::from("3t")));
-        assert_eq!(
-            x3s.clone() * "2x",
-            CalculatorFloat::Str(String::from("(3t * 2x)"))
-        );
+        assert_eq!(x3s.clone() * 1.0, CalculatorFloat::from("3t"));
+        assert_eq!(x3s.clone() * "2x", CalculatorFloat::from("(3t * 2x)"));
 
         // Test mul_assign function: x *= y
         x3 *= x2.clone();
@@ -1422,20 +1770,20 @@ mod tests {
 
         let mut x3 = CalculatorFloat::from(1.0);
         x3 *= "x";
-        assert_eq!(x3, CalculatorFloat::Str(String::from("x")));
+        assert_eq!(x3, CalculatorFloat::from("x"));
 
         let mut x3 = CalculatorFloat::from(3.0);
         x3 *= "x";
-        assert_eq!(x3, CalculatorFloat::Str(String::from("(3e0 * x)")));
+        assert_eq!(x3, CalculatorFloat::from("(3e0 * x)"));
 
         x3s *= 1.0;
-        assert_eq!(x3s, CalculatorFloat::Str(String::from("3t")));
+        assert_eq!(x3s, CalculatorFloat::from("3t"));
 
         x3s *= x2;
-        assert_eq!(x3s, CalculatorFloat::Str(String::from("(3t * 3e0)")));
+        assert_eq!(x3s, CalculatorFloat::from("(3t * 3e0)"));
 
         x3s *= "2x";
-        assert_eq!(x3s, CalculatorFloat::Str(String::from("((3t * 3e0) * 2x)")));
+        assert_eq!(x3s, CalculatorFloat::from("((3t * 3e0) * 2x)"));
 
         x3s *= 0.0;
         assert_eq!(x3s, CalculatorFloat::Float(0.0));
@@ -1450,50 +1798,38 @@ mod tests {
         assert_eq!(x3.clone() - x2.clone(), CalculatorFloat::Float(0.0));
         assert_eq!(x3.clone() - 3, CalculatorFloat::Float(0.0));
         assert_eq!(x3.clone() - 3.0, CalculatorFloat::Float(0.0));
-        assert_eq!(
-            x3.clone() - "x",
-            CalculatorFloat::Str(String::from("(3e0 - x)"))
-        );
+        assert_eq!(x3.clone() - "x", CalculatorFloat::from("(3e0 - x)"));
 
         let x3 = CalculatorFloat::from(0.0);
-        assert_eq!(x3.clone() - "x", CalculatorFloat::Str(String::from("(-x)")));
+        assert_eq!(x3.clone() - "x", CalculatorFloat::from("(-x)"));
 
         let mut x3s = CalculatorFloat::from("3t");
         assert_eq!(
             x3s.clone() - x2.clone(),
-            CalculatorFloat::Str(String::from("(3t - 3e0)"))
-        );
-        assert_eq!(
-            x3s.clone() - 2.0,
-            CalculatorFloat::Str(String::from("(3t - 2e0)"))
-        );
-        assert_eq!(x3s.clone() - 0.0, CalculatorFloat::Str(String::from("3t")));
-        assert_eq!(
-            x3s.clone() - "2.0",
-            CalculatorFloat::Str(String::from("(3t - 2e0)"))
-        );
-        assert_eq!(
-            x3s.clone() - "2x",
-            CalculatorFloat::Str(String::from("(3t - 2x)"))
+            CalculatorFloat::from("(3t - 3e0)")
         );
+        assert_eq!(x3s.clone() - 2.0, CalculatorFloat::from("(3t - 2e0)"));
+        assert_eq!(x3s.clone() - 0.0, CalculatorFloat::from("3t"));
+        assert_eq!(x3s.clone() - "2.0", CalculatorFloat::from("(3t - 2e0)"));
+        assert_eq!(x3s.clone() - "2x", CalculatorFloat::from("(3t - 2x)"));
 
         // Test sub_assign function: x -= y
         let mut x3 = CalculatorFloat::from(3);
         x3 -= x2.clone();
         assert_eq!(x3, CalculatorFloat::Float(0.0));
         x3 -= "x";
-        assert_eq!(x3, CalculatorFloat::Str(String::from("(-x)")));
+        assert_eq!(x3, CalculatorFloat::from("(-x)"));
 
         let mut x3 = CalculatorFloat::from(3);
         x3 -= "x";
-        assert_eq!(x3, CalculatorFloat::Str(String::from("(3e0 - x)")));
+        assert_eq!(x3, CalculatorFloat::from("(3e0 - x)"));
 
         x3s -= x2;
-        assert_eq!(x3s, CalculatorFloat::Str(String::from("(3t - 3e0)")));
+        assert_eq!(x3s, CalculatorFloat::from("(3t - 3e0)"));
         x3s -= 0.0;
-        assert_eq!(x3s, CalculatorFloat::Str(String::from("(3t - 3e0)")));
+        assert_eq!(x3s, CalculatorFloat::from("(3t - 3e0)"));
         x3s -= "x";
-        assert_eq!(x3s, CalculatorFloat::Str(String::from("((3t - 3e0) - x)")));
+        assert_eq!(x3s, CalculatorFloat::from("((3t - 3e0) - x)"));
     }
 
     // Test the negative (*-1) functionality of CalculatorFloat with all possible input types
@@ -1504,7 +1840,7 @@ mod tests {
         assert_eq!(x2, CalculatorFloat::Float(-3.0));
         let x3s = CalculatorFloat::from("3t");
         let x2 = -x3s.clone();
-        assert_eq!(x2, CalculatorFloat::Str(String::from("(-3t)")));
+        assert_eq!(x2, CalculatorFloat::from("(-3t)"));
     }
 
     // Test the square root functionality of CalculatorFloat with all possible input types
@@ -1514,7 +1850,7 @@ mod tests {
         let x2: f64 = 3.0;
         assert_eq!(CalculatorFloat::Float(x2.sqrt()), x3.sqrt());
         let x3s = CalculatorFloat::from("3t");
-        assert_eq!(x3s.sqrt(), CalculatorFloat::Str(String::from("sqrt(3t)")));
+        assert_eq!(x3s.sqrt(), CalculatorFloat::from("sqrt(3t)"));
     }
 
     // Test the arccosine functionality of CalculatorFloat with all possible input types
@@ -1524,7 +1860,7 @@ mod tests {
         let x2: f64 = 1.0;
         assert_eq!(CalculatorFloat::Float(x2.acos()), x3.acos());
         let x3s = CalculatorFloat::from("1t");
-        assert_eq!(x3s.acos(), CalculatorFloat::Str(String::from("acos(1t)")));
+        assert_eq!(x3s.acos(), CalculatorFloat::from("acos(1t)"));
     }
 
     // Test the exponential functionality of CalculatorFloat with all possible input types
@@ -1534,7 +1870,7 @@ mod tests {
         let x2: f64 = 3.0;
         assert_eq!(CalculatorFloat::Float(x2.exp()), x3.exp());
         let x3s = CalculatorFloat::from("3t");
-        assert_eq!(x3s.exp(), CalculatorFloat::Str(String::from("exp(3t)")));
+        assert_eq!(x3s.exp(), CalculatorFloat::from("exp(3t)"));
     }
 
     // Test the absolute value functionality of CalculatorFloat with all possible input types
@@ -1544,7 +1880,7 @@ mod tests {
         let x2: f64 = -3.0;
         assert_eq!(CalculatorFloat::Float(x2.abs()), x3.abs());
         let x3s = CalculatorFloat::from("-3t");
-        assert_eq!(x3s.abs(), CalculatorFloat::Str(String::from("abs(-3t)")));
+        assert_eq!(x3s.abs(), CalculatorFloat::from("abs(-3t)"));
     }
 
     // Test the cosine functionality of CalculatorFloat with all possible input types
@@ -1554,7 +1890,7 @@ mod tests {
         let x2: f64 = -3.0;
         assert_eq!(CalculatorFloat::Float(x2.cos()), x3.cos());
         let x3s = CalculatorFloat::from("-3t");
-        assert_eq!(x3s.cos(), CalculatorFloat::Str(String::from("cos(-3t)")));
+        assert_eq!(x3s.cos(), CalculatorFloat::from("cos(-3t)"));
     }
 
     // Test the sine functionality of CalculatorFloat with all possible input types
@@ -1564,7 +1900,7 @@ mod tests {
         let x2: f64 = -3.0;
         assert_eq!(CalculatorFloat::Float(x2.sin()), x3.sin());
         let x3s = CalculatorFloat::from("-3t");
-        assert_eq!(x3s.sin(), CalculatorFloat::Str(String::from("sin(-3t)")));
+        assert_eq!(x3s.sin(), CalculatorFloat::from("sin(-3t)"));
     }
 
     // Test the arctangent functionality of CalculatorFloat with all possible input types
@@ -1575,18 +1911,9 @@ mod tests {
         let x2: f64 = -3.0;
         assert_eq!(CalculatorFloat::Float(x2.atan2(2.0)), x3.atan2(2.0));
         let x3s = CalculatorFloat::from("-3t");
-        assert_eq!(
-            x3s.atan2("test"),
-            CalculatorFloat::Str(String::from("atan2(-3t, test)"))
-        );
-        assert_eq!(
-            x3s.atan2(1.0),
-            CalculatorFloat::Str(String::from("atan2(-3t, 1e0)"))
-        );
-        assert_eq!(
-            x3.atan2("test"),
-            CalculatorFloat::Str(String::from("atan2(-3e0, test)"))
-        );
+        assert_eq!(x3s.atan2("test"), CalculatorFloat::from("atan2(-3t, test)"));
+        assert_eq!(x3s.atan2(1.0), CalculatorFloat::from("atan2(-3t, 1e0)"));
+        assert_eq!(x3.atan2("test"), CalculatorFloat::from("atan2(-3e0, test)"));
     }
 
     // Test the sign functionality of CalculatorFloat with all possible input types
@@ -1595,7 +1922,7 @@ mod tests {
         let x2 = CalculatorFloat::from(-3);
         let x3 = CalculatorFloat::from("-3t");
         assert_eq!(x2.signum(), CalculatorFloat::Float(-1.0));
-        assert_eq!(x3.signum(), CalculatorFloat::Str(String::from("sign(-3t)")));
+        assert_eq!(x3.signum(), CalculatorFloat::from("sign(-3t)"));
     }
 
     // Test the power functionality of CalculatorFloat with all possible input types
@@ -1604,18 +1931,9 @@ mod tests {
         let x1 = CalculatorFloat::from(2.0);
         let x1s = CalculatorFloat::from("2x");
         assert_eq!(x1.powf(2.0), CalculatorFloat::from(4.0));
-        assert_eq!(
-            x1.powf("t"),
-            CalculatorFloat::Str(String::from("(2e0 ^ t)"))
-        );
-        assert_eq!(
-            x1s.powf(2.0),
-            CalculatorFloat::Str(String::from("(2x ^ 2e0)"))
-        );
-        assert_eq!(
-            x1s.powf("t"),
-            CalculatorFloat::Str(String::from("(2x ^ t)"))
-        );
+        assert_eq!(x1.powf("t"), CalculatorFloat::from("(2e0 ^ t)"));
+        assert_eq!(x1s.powf(2.0), CalculatorFloat::from("(2x ^ 2e0)"));
+        assert_eq!(x1s.powf("t"), CalculatorFloat::from("(2x ^ t)"));
     }
 
     // Test the inverse/reciprocal functionality of CalculatorFloat with all possible input types
@@ -1626,7 +1944,7 @@ mod tests {
         let x1_recip = x1.recip();
         let x1s_recip = x1s.recip();
         assert_eq!(x1_recip, CalculatorFloat::from(0.5));
-        assert_eq!(x1s_recip, CalculatorFloat::Str(String::from("(1 / 2x)")));
+        assert_eq!(x1s_recip, CalculatorFloat::from("(1 / 2x)"));
     }
 
     // Test the Display functionality of CalculatorFloat with all possible input types
@@ -1664,22 +1982,37 @@ mod tests {
         let mut x3s = CalculatorFloat::from("3t");
         assert_eq!(
             x3s.clone() + x2.clone(),
-            CalculatorFloat::Str(String::from("(3t + 2e0)"))
-        );
-        assert_eq!(
-            x3s.clone() + 2.0,
-            CalculatorFloat::Str(String::from("(3t + 2e0)"))
-        );
-        assert_eq!(
-            x3s.clone() + 2.0,
-            CalculatorFloat::Str(String::from("(3t + 2e0)"))
-        );
-        assert_eq!(
-            x3s.clone() + "2.0",
-            CalculatorFloat::Str(String::from("(3t + 2e0)"))
+            CalculatorFloat::from("(3t + 2e0)")
         );
+        assert_eq!(x3s.clone() + 2.0, CalculatorFloat::from("(3t + 2e0)"));
+        assert_eq!(x3s.clone() + 2.0, CalculatorFloat::from("(3t + 2e0)"));
+        assert_eq!(x3s.clone() + "2.0", CalculatorFloat::from("(3t + 2e0)"));
         x3s += x2;
-        assert_eq!(x3s, CalculatorFloat::Str(String::from("(3t + 2e0)")));
+        assert_eq!(x3s, CalculatorFloat::from("(3t + 2e0)"));
+    }
+
+    // Test that operators on references work on borrowed operands
+    #[test]
+    fn ref_ops_borrowed() {
+        let a = CalculatorFloat::from(6.0);
+        let b = CalculatorFloat::from(3.0);
+        assert_eq!(&a + &b, CalculatorFloat::Float(9.0));
+        assert_eq!(&a - &b, CalculatorFloat::Float(3.0));
+        assert_eq!(&a * &b, CalculatorFloat::Float(18.0));
+        assert_eq!(&a / &b, CalculatorFloat::Float(2.0));
+        assert_eq!(-&a, CalculatorFloat::Float(-6.0));
+        assert_eq!(a.add_ref(&b), CalculatorFloat::Float(9.0));
+        assert_eq!(a.mul_ref(&b), CalculatorFloat::Float(18.0));
+        // Operands are untouched and still usable
+        assert_eq!(a, CalculatorFloat::Float(6.0));
+        assert_eq!(b, CalculatorFloat::Float(3.0));
+
+        let x = CalculatorFloat::from("x");
+        assert_eq!(&x * 0.0, CalculatorFloat::Float(0.0));
+        assert_eq!(0.0 / x.clone(), CalculatorFloat::Float(0.0));
+        assert_eq!(&x + 0.0, x);
+        assert_eq!(&x * &b, CalculatorFloat::from("(x * 3e0)"));
+        assert_eq!(x, CalculatorFloat::from("x"));
     }
 
     // Test the Debug trait for CalculatorFloat
@@ -1715,5 +2048,188 @@ mod tests {
         assert!(x1s == x2s);
         assert!(x2s == x1s);
     }
+
+    // Test the additional functions of the Calculator function table for CalculatorFloat
+    #[test]
+    fn function_table_parity() {
+        let x = CalculatorFloat::from(0.3);
+        let y: f64 = 0.3;
+        assert_eq!(x.tan(), CalculatorFloat::Float(y.tan()));
+        assert_eq!(x.asin(), CalculatorFloat::Float(y.asin()));
+        assert_eq!(x.atan(), CalculatorFloat::Float(y.atan()));
+        assert_eq!(x.sinh(), CalculatorFloat::Float(y.sinh()));
+        assert_eq!(x.cosh(), CalculatorFloat::Float(y.cosh()));
+        assert_eq!(x.tanh(), CalculatorFloat::Float(y.tanh()));
+        assert_eq!(x.asinh(), CalculatorFloat::Float(y.asinh()));
+        assert_eq!(
+            (x.clone() + 1).acosh(),
+            CalculatorFloat::Float(1.3_f64.acosh())
+        );
+        assert_eq!(x.atanh(), CalculatorFloat::Float(y.atanh()));
+        assert_eq!(x.ln(), CalculatorFloat::Float(y.ln()));
+        assert_eq!(x.log10(), CalculatorFloat::Float(y.log10()));
+        assert_eq!(x.exp2(), CalculatorFloat::Float(y.exp2()));
+        assert_eq!(x.exp_m1(), CalculatorFloat::Float(y.exp_m1()));
+        assert_eq!(x.cbrt(), CalculatorFloat::Float(y.cbrt()));
+        assert_eq!(x.ceil(), CalculatorFloat::Float(1.0));
+        assert_eq!(x.floor(), CalculatorFloat::Float(0.0));
+        assert_eq!(x.fract(), CalculatorFloat::Float(y.fract()));
+        assert_eq!(x.round(), CalculatorFloat::Float(0.0));
+        assert_eq!(x.delta(), CalculatorFloat::Float(0.0));
+        assert_eq!(CalculatorFloat::ZERO.delta(), CalculatorFloat::Float(1.0));
+        assert_eq!(x.theta(), CalculatorFloat::Float(1.0));
+        assert_eq!((-x.clone()).theta(), CalculatorFloat::Float(0.0));
+        assert_eq!(CalculatorFloat::ZERO.theta(), CalculatorFloat::Float(0.5));
+        assert_eq!(x.hypot(0.4), CalculatorFloat::Float(y.hypot(0.4)));
+        assert_eq!(x.max(0.4), CalculatorFloat::Float(0.4));
+        assert_eq!(x.min(0.4), CalculatorFloat::Float(0.3));
+    }
+
+    // Test that symbolic results of the function table methods evaluate like the Calculator
+    #[test]
+    fn function_table_parity_symbolic() {
+        let x = CalculatorFloat::from("x");
+        assert_eq!(x.ln(), CalculatorFloat::from("log(x)"));
+        assert_eq!(x.exp_m1(), CalculatorFloat::from("expm1(x)"));
+        assert_eq!(x.hypot(2.0), CalculatorFloat::from("hypot(x, 2e0)"));
+        assert_eq!(
+            CalculatorFloat::from(2.0).max("x"),
+            CalculatorFloat::from("max(2e0, x)")
+        );
+        assert_eq!(x.min("y"), CalculatorFloat::from("min(x, y)"));
+        let mut calculator = Calculator::new();
+        calculator.set_variable("x", 0.3);
+        let symbolic = vec![
+            x.tan(),
+            x.asin(),
+            x.atan(),
+            x.sinh(),
+            x.cosh(),
+            x.tanh(),
+            x.asinh(),
+            (x.clone() + 1).acosh(),
+            x.atanh(),
+            x.ln(),
+            x.log10(),
+            x.exp2(),
+            x.exp_m1(),
+            x.cbrt(),
+            x.ceil(),
+            x.floor(),
+            x.fract(),
+            x.round(),
+            x.delta(),
+            x.theta(),
+            x.hypot(0.4),
+            x.max(0.4),
+            x.min(0.4),
+        ];
+        let numeric = CalculatorFloat::from(0.3);
+        let expected = vec![
+            numeric.tan(),
+            numeric.asin(),
+            numeric.atan(),
+            numeric.sinh(),
+            numeric.cosh(),
+            numeric.tanh(),
+            numeric.asinh(),
+            (numeric.clone() + 1).acosh(),
+            numeric.atanh(),
+            numeric.ln(),
+            numeric.log10(),
+            numeric.exp2(),
+            numeric.exp_m1(),
+            numeric.cbrt(),
+            numeric.ceil(),
+            numeric.floor(),
+            numeric.fract(),
+            numeric.round(),
+            numeric.delta(),
+            numeric.theta(),
+            numeric.hypot(0.4),
+            numeric.max(0.4),
+            numeric.min(0.4),
+        ];
+        for (s, e) in symbolic.iter().zip(expected.iter()) {
+            let value = calculator.parse_get(s.clone()).unwrap();
+            assert!(e.isclose(value));
+        }
+    }
+
+    // Test the remainder of CalculatorFloat for numeric and symbolic values
+    #[test]
+    fn rem() {
+        assert_eq!(CalculatorFloat::from(7.5) % 2, CalculatorFloat::from(1.5));
+        assert_eq!(CalculatorFloat::from(-7.5) % 2, CalculatorFloat::from(-1.5));
+        let mut calculator = Calculator::new();
+        calculator.set_variable("x", -7.5);
+        calculator.set_variable("y", 2.0);
+        let symbolic = CalculatorFloat::from("x") % "y";
+        assert_eq!(calculator.parse_get(symbolic).unwrap(), -1.5);
+        let symbolic = CalculatorFloat::from(7.5) % "y";
+        assert_eq!(calculator.parse_get(symbolic).unwrap(), 1.5);
+    }
+
+    // Test panic of remainder by zero
+    #[test]
+    #[should_panic]
+    fn rem_zero() {
+        let _ = CalculatorFloat::from(1) % 0;
+    }
+
+    // Test the product over an iterator of CalculatorFloat
+    #[test]
+    fn product() {
+        let values = vec![CalculatorFloat::from(2), CalculatorFloat::from(3.5)];
+        let product: CalculatorFloat = values.into_iter().product();
+        assert_eq!(product, CalculatorFloat::from(7));
+        let values = vec![CalculatorFloat::from(2), CalculatorFloat::from("x")];
+        let product: CalculatorFloat = values.into_iter().product();
+        assert_eq!(product, CalculatorFloat::from("(2e0 * x)"));
+    }
+
+    // Test arithmetic with primitive left hand side
+    #[test]
+    fn primitive_lhs() {
+        let x = CalculatorFloat::from("x");
+        assert_eq!(2.0 * x.clone(), CalculatorFloat::from("(2e0 * x)"));
+        assert_eq!(2 + x.clone(), CalculatorFloat::from("(2e0 + x)"));
+        assert_eq!(2u32 - x.clone(), CalculatorFloat::from("(2e0 - x)"));
+        assert_eq!(1.0 / x, CalculatorFloat::from("(1e0 / x)"));
+        let y = CalculatorFloat::from(4);
+        assert_eq!(2.0 * y.clone(), CalculatorFloat::from(8));
+        assert_eq!(2 - y.clone(), CalculatorFloat::from(-2));
+        assert_eq!(2u32 / y.clone(), CalculatorFloat::from(0.5));
+        assert_eq!(1.5 + y, CalculatorFloat::from(5.5));
+    }
+
+    // Test that operators on references agree with operators on owned values
+    #[test]
+    fn reference_ops() {
+        let values = [
+            CalculatorFloat::from(0),
+            CalculatorFloat::from(1),
+            CalculatorFloat::from(2.5),
+            CalculatorFloat::from("x"),
+        ];
+        let divisors = [
+            CalculatorFloat::from(1),
+            CalculatorFloat::from(2.5),
+            CalculatorFloat::from("y"),
+        ];
+        for a in values.iter() {
+            for b in values.iter() {
+                assert_eq!(a + b, a.clone() + b.clone());
+                assert_eq!(a - b, a.clone() - b.clone());
+                assert_eq!(a * b, a.clone() * b.clone());
+                assert_eq!(a - b.clone(), a.clone() - b);
+            }
+            for b in divisors.iter() {
+                assert_eq!(a / b, a.clone() / b.clone());
+                assert_eq!(a / b.clone(), a.clone() / b);
+                assert_eq!(a % b, a.clone() % b.clone());
+            }
+            assert_eq!(-a, -a.clone());
+        }
+    }
 }
-// End of tests
@@ -0,0 +1,868 @@
+// Copyright © 2020-2021 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! calculator_interval module
+//!
+//! Provides interval arithmetic evaluation of string expressions for Calculator.
+
+use crate::calculator::{
+    function_1_argument, function_2_arguments, Evaluator, Parser, ReadOnlyEvaluator,
+};
+use crate::calculator_cache::execute;
+use crate::{Calculator, CalculatorError};
+use std::collections::HashMap;
+use std::f64::consts::{FRAC_PI_2, PI};
+
+static ATOL: f64 = f64::EPSILON;
+static TWO_PI: f64 = 2.0 * PI;
+// Below this magnitude products and quotients may lose precision to underflow
+static TINY: f64 = 1e-290;
+
+/// Return smallest float larger than x.
+fn next_up(x: f64) -> f64 {
+    if x.is_nan() || x == f64::INFINITY {
+        x
+    } else if x == 0.0 {
+        f64::from_bits(1)
+    } else if x > 0.0 {
+        f64::from_bits(x.to_bits() + 1)
+    } else {
+        f64::from_bits(x.to_bits() - 1)
+    }
+}
+
+/// Return largest float smaller than x.
+fn next_down(x: f64) -> f64 {
+    -next_up(-x)
+}
+
+/// Return lower and upper bound of a rounded value given its rounding error.
+///
+/// A positive error means the exact value lies above the rounded value.
+fn error_bounds(value: f64, error: f64) -> (f64, f64) {
+    if error > 0.0 {
+        (value, next_up(value))
+    } else if error < 0.0 {
+        (next_down(value), value)
+    } else if error == 0.0 {
+        (value, value)
+    } else {
+        (next_down(value), next_up(value))
+    }
+}
+
+/// Return bounds of a result that overflowed or involves infinite operands.
+fn infinite_bounds(value: f64, finite_operands: bool) -> (f64, f64) {
+    if finite_operands {
+        (next_down(value), next_up(value))
+    } else {
+        (value, value)
+    }
+}
+
+/// Return lower and upper bound of x + y.
+fn add_bounds(x: f64, y: f64) -> (f64, f64) {
+    let sum = x + y;
+    if !sum.is_finite() {
+        return infinite_bounds(sum, x.is_finite() && y.is_finite());
+    }
+    let y_virtual = sum - x;
+    error_bounds(sum, (x - (sum - y_virtual)) + (y - y_virtual))
+}
+
+/// Return lower and upper bound of x * y treating 0 * inf as 0.
+fn mul_bounds(x: f64, y: f64) -> (f64, f64) {
+    if x == 0.0 || y == 0.0 {
+        return (0.0, 0.0);
+    }
+    let product = x * y;
+    if !product.is_finite() {
+        infinite_bounds(product, x.is_finite() && y.is_finite())
+    } else if product.abs() < TINY {
+        (next_down(product), next_up(product))
+    } else {
+        error_bounds(product, x.mul_add(y, -product))
+    }
+}
+
+/// Return lower and upper bound of x / y for y not zero.
+fn div_bounds(x: f64, y: f64) -> (f64, f64) {
+    let quotient = x / y;
+    if x == 0.0 || y.is_infinite() && x.is_finite() {
+        (quotient, quotient)
+    } else if !quotient.is_finite() {
+        infinite_bounds(quotient, x.is_finite())
+    } else if quotient.abs() < TINY {
+        (next_down(quotient), next_up(quotient))
+    } else {
+        let residual = quotient.mul_add(y, -x);
+        error_bounds(quotient, if y > 0.0 { -residual } else { residual })
+    }
+}
+
+/// Return lower and upper bound of x^y.
+///
+/// The power is exact for bases 0 and ±1, infinite bases and exponent 0.
+fn pow_bounds(x: f64, y: f64) -> (f64, f64) {
+    let power = x.powf(y);
+    if x == 0.0 || x.abs() == 1.0 || x.is_infinite() || y == 0.0 {
+        (power, power)
+    } else {
+        (next_down(power), next_up(power))
+    }
+}
+
+/// Return smallest interval containing all bounds.
+fn hull(bounds: &[(f64, f64)]) -> Interval {
+    Interval {
+        lower: bounds.iter().map(|x| x.0).fold(f64::INFINITY, f64::min),
+        upper: bounds.iter().map(|x| x.1).fold(f64::NEG_INFINITY, f64::max),
+    }
+}
+
+/// Return true when [lower, upper] contains offset + k * period for an integer k.
+///
+/// The check is conservative: points within rounding distance of the interval
+/// are reported as contained.
+fn contains_periodic_point(lower: f64, upper: f64, offset: f64, period: f64) -> bool {
+    let k_lower = (lower - offset) / period;
+    let k_upper = (upper - offset) / period;
+    let tolerance = 1e-12 * (1.0 + k_lower.abs().max(k_upper.abs()));
+    (k_lower - tolerance).ceil() <= (k_upper + tolerance).floor()
+}
+
+/// Closed interval [lower, upper] of real numbers.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Interval {
+    /// Lower bound of the interval
+    pub(crate) lower: f64,
+    /// Upper bound of the interval
+    pub(crate) upper: f64,
+}
+
+impl Interval {
+    /// Return interval containing a single point.
+    fn point(x: f64) -> Self {
+        Interval { lower: x, upper: x }
+    }
+
+    /// Return interval with bounds rounded outward by one unit in the last place.
+    fn rounded(lower: f64, upper: f64) -> Self {
+        Interval {
+            lower: next_down(lower),
+            upper: next_up(upper),
+        }
+    }
+
+    /// Return the interval bounds as function arguments for error reporting.
+    fn arguments(&self) -> Vec<f64> {
+        vec![self.lower, self.upper]
+    }
+
+    /// Return interval clamped to [lower, upper].
+    fn clamp(self, lower: f64, upper: f64) -> Self {
+        Interval {
+            lower: self.lower.max(lower),
+            upper: self.upper.min(upper),
+        }
+    }
+
+    /// Return DomainError when the interval is not contained in [lower, upper].
+    fn check_domain(&self, fct: &str, lower: f64, upper: f64) -> Result<(), CalculatorError> {
+        if self.lower < lower || self.upper > upper {
+            Err(CalculatorError::DomainError {
+                fct: fct.to_string(),
+                arguments: self.arguments(),
+            })
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Return image of the interval under a non-decreasing function of the function table.
+    fn increasing(&self, fct: &str, exact: bool) -> Result<Self, CalculatorError> {
+        let lower = function_1_argument(fct, self.lower)?;
+        let upper = function_1_argument(fct, self.upper)?;
+        if exact {
+            Ok(Interval { lower, upper })
+        } else {
+            Ok(Interval::rounded(lower, upper))
+        }
+    }
+
+    /// Return image of the interval under a non-increasing function of the function table.
+    fn decreasing(&self, fct: &str) -> Result<Self, CalculatorError> {
+        let lower = function_1_argument(fct, self.upper)?;
+        let upper = function_1_argument(fct, self.lower)?;
+        Ok(Interval::rounded(lower, upper))
+    }
+
+    /// Return reciprocal 1/x of the interval.
+    ///
+    /// Intervals with zero as a bound are mapped to half-infinite intervals,
+    /// intervals containing zero in their interior to the whole real line.
+    fn recip(&self) -> Self {
+        if self.lower > 0.0 || self.upper < 0.0 {
+            hull(&[div_bounds(1.0, self.upper), div_bounds(1.0, self.lower)])
+        } else if self.lower == 0.0 && self.upper > 0.0 {
+            Interval {
+                lower: div_bounds(1.0, self.upper).0,
+                upper: f64::INFINITY,
+            }
+        } else if self.upper == 0.0 && self.lower < 0.0 {
+            Interval {
+                lower: f64::NEG_INFINITY,
+                upper: div_bounds(1.0, self.lower).1,
+            }
+        } else {
+            Interval {
+                lower: f64::NEG_INFINITY,
+                upper: f64::INFINITY,
+            }
+        }
+    }
+
+    /// Return sum of two intervals.
+    fn add(&self, other: &Self) -> Self {
+        Interval {
+            lower: add_bounds(self.lower, other.lower).0,
+            upper: add_bounds(self.upper, other.upper).1,
+        }
+    }
+
+    /// Return product of two intervals.
+    fn mul(&self, other: &Self) -> Self {
+        hull(&[
+            mul_bounds(self.lower, other.lower),
+            mul_bounds(self.lower, other.upper),
+            mul_bounds(self.upper, other.lower),
+            mul_bounds(self.upper, other.upper),
+        ])
+    }
+
+    /// Return quotient of two intervals for a divisor that is not [0, 0].
+    fn div(&self, other: &Self) -> Self {
+        if other.lower > 0.0 || other.upper < 0.0 {
+            hull(&[
+                div_bounds(self.lower, other.lower),
+                div_bounds(self.lower, other.upper),
+                div_bounds(self.upper, other.lower),
+                div_bounds(self.upper, other.upper),
+            ])
+        } else {
+            self.mul(&other.recip())
+        }
+    }
+
+    /// Return interval raised to a point integer power n.
+    fn powi(&self, n: f64) -> Self {
+        if n == 0.0 {
+            return Interval::point(1.0);
+        }
+        let magnitude = n.abs();
+        let lower_power = pow_bounds(self.lower, magnitude);
+        let upper_power = pow_bounds(self.upper, magnitude);
+        let power = if magnitude % 2.0 == 1.0 || self.lower >= 0.0 {
+            Interval {
+                lower: lower_power.0,
+                upper: upper_power.1,
+            }
+        } else if self.upper <= 0.0 {
+            Interval {
+                lower: upper_power.0,
+                upper: lower_power.1,
+            }
+        } else {
+            Interval {
+                lower: 0.0,
+                upper: lower_power.1.max(upper_power.1),
+            }
+        };
+        if n > 0.0 {
+            power
+        } else {
+            power.recip()
+        }
+    }
+
+    /// Return interval raised to the power of another interval.
+    fn pow(&self, other: &Self) -> Result<Self, CalculatorError> {
+        if other.lower == other.upper && other.lower.fract() == 0.0 {
+            return Ok(self.powi(other.lower));
+        }
+        if self.lower < 0.0 {
+            return Err(CalculatorError::DomainError {
+                fct: "pow".to_string(),
+                arguments: vec![self.lower, self.upper, other.lower, other.upper],
+            });
+        }
+        Ok(hull(&[
+            pow_bounds(self.lower, other.lower),
+            pow_bounds(self.lower, other.upper),
+            pow_bounds(self.upper, other.lower),
+            pow_bounds(self.upper, other.upper),
+        ]))
+    }
+
+    /// Return range of the cosine over the interval.
+    ///
+    /// Maxima of the cosine lie at 2kπ and minima at (2k+1)π.
+    fn cos(&self) -> Self {
+        self.periodic_extrema(f64::cos, 0.0, PI)
+    }
+
+    /// Return range of the sine over the interval.
+    ///
+    /// Maxima of the sine lie at π/2 + 2kπ and minima at -π/2 + 2kπ.
+    fn sin(&self) -> Self {
+        self.periodic_extrema(f64::sin, FRAC_PI_2, -FRAC_PI_2)
+    }
+
+    /// Return range of a 2π-periodic function with one maximum and one minimum per period.
+    fn periodic_extrema(&self, function: fn(f64) -> f64, maximum: f64, minimum: f64) -> Self {
+        if !self.lower.is_finite() || !self.upper.is_finite() || self.upper - self.lower >= TWO_PI {
+            return Interval {
+                lower: -1.0,
+                upper: 1.0,
+            };
+        }
+        let lower_value = function(self.lower);
+        let upper_value = function(self.upper);
+        let mut range =
+            Interval::rounded(lower_value.min(upper_value), lower_value.max(upper_value));
+        if contains_periodic_point(self.lower, self.upper, maximum, TWO_PI) {
+            range.upper = 1.0;
+        }
+        if contains_periodic_point(self.lower, self.upper, minimum, TWO_PI) {
+            range.lower = -1.0;
+        }
+        range.clamp(-1.0, 1.0)
+    }
+
+    /// Return range of the tangent over the interval.
+    fn tan(&self) -> Self {
+        if !self.lower.is_finite()
+            || !self.upper.is_finite()
+            || self.upper - self.lower >= PI
+            || contains_periodic_point(self.lower, self.upper, FRAC_PI_2, PI)
+        {
+            Interval {
+                lower: f64::NEG_INFINITY,
+                upper: f64::INFINITY,
+            }
+        } else {
+            Interval::rounded(self.lower.tan(), self.upper.tan())
+        }
+    }
+
+    /// Return range of an even function with its minimum at zero.
+    fn even(&self, fct: &str, exact: bool) -> Result<Self, CalculatorError> {
+        if self.lower >= 0.0 {
+            self.increasing(fct, exact)
+        } else if self.upper <= 0.0 {
+            let mirrored = Interval {
+                lower: -self.upper,
+                upper: -self.lower,
+            };
+            mirrored.increasing(fct, exact)
+        } else {
+            let maximum = function_1_argument(fct, self.lower.abs().max(self.upper))?;
+            let minimum = function_1_argument(fct, 0.0)?;
+            if exact {
+                Ok(Interval {
+                    lower: minimum,
+                    upper: maximum,
+                })
+            } else {
+                Ok(Interval::rounded(minimum, maximum))
+            }
+        }
+    }
+
+    /// Return range of fract(x) = x - trunc(x) over the interval.
+    fn fract(&self) -> Self {
+        if self.lower.is_finite()
+            && self.upper.is_finite()
+            && self.lower.trunc() == self.upper.trunc()
+            && (self.lower >= 0.0 || self.upper <= 0.0)
+        {
+            Interval::rounded(self.lower.fract(), self.upper.fract()).clamp(-1.0, 1.0)
+        } else if self.lower >= 0.0 {
+            Interval {
+                lower: 0.0,
+                upper: 1.0,
+            }
+        } else if self.upper <= 0.0 {
+            Interval {
+                lower: -1.0,
+                upper: 0.0,
+            }
+        } else {
+            Interval {
+                lower: -1.0,
+                upper: 1.0,
+            }
+        }
+    }
+
+    /// Return range of delta(x) over the interval.
+    fn delta(&self) -> Self {
+        if self.upper <= -ATOL || self.lower >= ATOL {
+            Interval::point(0.0)
+        } else if self.lower > -ATOL && self.upper < ATOL {
+            Interval::point(1.0)
+        } else {
+            Interval {
+                lower: 0.0,
+                upper: 1.0,
+            }
+        }
+    }
+
+    /// Return range of atan2(self, other) over the intervals.
+    ///
+    /// The result is only narrower than [-π, π] when the intervals
+    /// do not touch the branch cut of atan2 along the negative real axis.
+    fn atan2(&self, other: &Self) -> Self {
+        if other.lower > 0.0 || self.lower > 0.0 || self.upper < 0.0 {
+            let angles = [
+                self.lower.atan2(other.lower),
+                self.lower.atan2(other.upper),
+                self.upper.atan2(other.lower),
+                self.upper.atan2(other.upper),
+            ];
+            Interval::rounded(
+                angles.iter().cloned().fold(f64::INFINITY, f64::min),
+                angles.iter().cloned().fold(f64::NEG_INFINITY, f64::max),
+            )
+        } else {
+            Interval::rounded(-PI, PI)
+        }
+    }
+
+    /// Return range of absolute value over the interval.
+    fn abs(&self) -> Self {
+        if self.lower >= 0.0 {
+            *self
+        } else if self.upper <= 0.0 {
+            Interval {
+                lower: -self.upper,
+                upper: -self.lower,
+            }
+        } else {
+            Interval {
+                lower: 0.0,
+                upper: self.upper.max(-self.lower),
+            }
+        }
+    }
+}
+
+/// Evaluator for interval arithmetic evaluation.
+///
+/// # Fields
+///
+/// * `calculator` - Calculator providing point values for variables without range
+/// * `ranges` - Ranges of variables
+/// * `assigned` - Variables assigned during the evaluation
+///
+struct IntervalEvaluator<'a> {
+    calculator: &'a Calculator,
+    ranges: &'a HashMap<String, (f64, f64)>,
+    assigned: HashMap<String, Interval>,
+}
+
+/// Return DomainError when an interval bound is NaN.
+fn check_nan(fct: &str, value: Interval) -> Result<Interval, CalculatorError> {
+    if value.lower.is_nan() || value.upper.is_nan() {
+        Err(CalculatorError::DomainError {
+            fct: fct.to_string(),
+            arguments: value.arguments(),
+        })
+    } else {
+        Ok(value)
+    }
+}
+
+impl<'a> Evaluator for IntervalEvaluator<'a> {
+    type Value = Interval;
+
+    fn number(&self, value: f64) -> Result<Interval, CalculatorError> {
+        Ok(Interval::point(value))
+    }
+
+    fn variable(&self, name: &str) -> Result<Interval, CalculatorError> {
+        if let Some(value) = self.assigned.get(name) {
+            return Ok(*value);
+        }
+        match self.ranges.get(name) {
+            Some((lower, upper)) => {
+                if lower <= upper {
+                    Ok(Interval {
+                        lower: *lower,
+                        upper: *upper,
+                    })
+                } else {
+                    Err(CalculatorError::InvalidInterval {
+                        name: name.to_string(),
+                        lower: *lower,
+                        upper: *upper,
+                    })
+                }
+            }
+            None => match self.calculator.stored_variable(name) {
+                Some(value) => Ok(Interval::point(value)),
+                // Variables defined by expressions are bounded through their definition
+                None => match self.calculator.definitions.get(name) {
+                    Some(definition) => execute(
+                        &mut ReadOnlyEvaluator { evaluator: self },
+                        &definition.program,
+                    ),
+                    None => Ok(Interval::point(self.calculator.get_variable(name)?)),
+                },
+            },
+        }
+    }
+
+    fn assign(&mut self, name: &str, value: Interval) -> Result<Interval, CalculatorError> {
+        self.assigned.insert(name.to_string(), value);
+        Ok(value)
+    }
+
+    fn add(&self, lhs: Interval, rhs: Interval) -> Result<Interval, CalculatorError> {
+        check_nan("add", lhs.add(&rhs))
+    }
+
+    fn sub(&self, lhs: Interval, rhs: Interval) -> Result<Interval, CalculatorError> {
+        check_nan("sub", lhs.add(&self.neg(rhs)?))
+    }
+
+    fn mul(&self, lhs: Interval, rhs: Interval) -> Result<Interval, CalculatorError> {
+        check_nan("mul", lhs.mul(&rhs))
+    }
+
+    fn div(&self, lhs: Interval, rhs: Interval) -> Result<Interval, CalculatorError> {
+        if rhs.lower == 0.0 && rhs.upper == 0.0 {
+            return Err(CalculatorError::DivisionByZero);
+        }
+        check_nan("div", lhs.div(&rhs))
+    }
+
+    fn pow(&self, lhs: Interval, rhs: Interval) -> Result<Interval, CalculatorError> {
+        check_nan("pow", lhs.pow(&rhs)?)
+    }
+
+    fn neg(&self, value: Interval) -> Result<Interval, CalculatorError> {
+        Ok(Interval {
+            lower: -value.upper,
+            upper: -value.lower,
+        })
+    }
+
+    fn function_1_argument(&self, fct: &str, arg0: Interval) -> Result<Interval, CalculatorError> {
+        let value = match fct {
+            "sin" => arg0.sin(),
+            "cos" => arg0.cos(),
+            "tan" => arg0.tan(),
+            "abs" => arg0.abs(),
+            "acos" => {
+                arg0.check_domain(fct, -1.0, 1.0)?;
+                arg0.decreasing(fct)?.clamp(0.0, next_up(PI))
+            }
+            "asin" => {
+                arg0.check_domain(fct, -1.0, 1.0)?;
+                arg0.increasing(fct, false)?
+            }
+            "atanh" | "artanh" => {
+                arg0.check_domain(fct, -1.0, 1.0)?;
+                arg0.increasing(fct, false)?
+            }
+            "acosh" | "arcosh" => {
+                arg0.check_domain(fct, 1.0, f64::INFINITY)?;
+                arg0.increasing(fct, false)?.clamp(0.0, f64::INFINITY)
+            }
+            "log" | "log10" | "sqrt" => {
+                arg0.check_domain(fct, 0.0, f64::INFINITY)?;
+                let value = arg0.increasing(fct, false)?;
+                if fct == "sqrt" {
+                    value.clamp(0.0, f64::INFINITY)
+                } else {
+                    value
+                }
+            }
+            "tanh" => arg0.increasing(fct, false)?.clamp(-1.0, 1.0),
+            "exp" | "exp2" => arg0.increasing(fct, false)?.clamp(0.0, f64::INFINITY),
+            "atan" | "sinh" | "asinh" | "arsinh" | "expm1" | "cbrt" => {
+                arg0.increasing(fct, false)?
+            }
+            "floor" | "ceil" | "round" | "sign" | "theta" => arg0.increasing(fct, true)?,
+            "cosh" => arg0.even(fct, false)?,
+            "fract" => arg0.fract(),
+            "delta" => arg0.delta(),
+            _ => {
+                return Err(CalculatorError::FunctionNotFound {
+                    fct: fct.to_string(),
+                })
+            }
+        };
+        check_nan(fct, value)
+    }
+
+    fn function_2_arguments(
+        &self,
+        fct: &str,
+        arg0: Interval,
+        arg1: Interval,
+    ) -> Result<Interval, CalculatorError> {
+        let value = match fct {
+            "atan2" => arg0.atan2(&arg1),
+            "hypot" => {
+                let (x, y) = (arg0.abs(), arg1.abs());
+                Interval::rounded(
+                    function_2_arguments(fct, x.lower, y.lower)?,
+                    function_2_arguments(fct, x.upper, y.upper)?,
+                )
+            }
+            "pow" => arg0.pow(&arg1)?,
+            "max" | "min" => Interval {
+                lower: function_2_arguments(fct, arg0.lower, arg1.lower)?,
+                upper: function_2_arguments(fct, arg0.upper, arg1.upper)?,
+            },
+            _ => {
+                return Err(CalculatorError::FunctionNotFound {
+                    fct: fct.to_string(),
+                })
+            }
+        };
+        check_nan(fct, value)
+    }
+}
+
+impl Calculator {
+    /// Compute guaranteed bounds of a string expression using interval arithmetic.
+    ///
+    /// Variables with a range are evaluated over the whole range, all other variables
+    /// are taken from the Calculator. All operations round outward, so the returned
+    /// bounds contain every value the expression takes for variables in their ranges.
+    /// Assignments in the expression are local to the evaluation.
+    ///
+    /// # Arguments
+    ///
+    /// * `expression` - Expression that is evaluated
+    /// * `ranges` - Lower and upper bound of variables
+    ///
+    /// # Returns
+    ///
+    /// `(f64, f64)` - Lower and upper bound of the expression
+    ///
+    pub fn evaluate_interval(
+        &self,
+        expression: &str,
+        ranges: &HashMap<String, (f64, f64)>,
+    ) -> Result<(f64, f64), CalculatorError> {
+        let mut evaluator = IntervalEvaluator {
+            calculator: self,
+            ranges,
+            assigned: HashMap::new(),
+        };
+        let mut parser = Parser::new(expression, &mut evaluator);
+        match parser.evaluate_all_tokens()? {
+            None => Err(CalculatorError::NoValueReturnedParsing),
+            Some(x) => Ok((x.lower, x.upper)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{next_down, next_up, Interval};
+    use crate::{Calculator, CalculatorError};
+    use std::collections::HashMap;
+    use std::f64::consts::PI;
+
+    // Sample expression over the ranges and check that all values lie within the bounds
+    fn assert_bounds_contain(expression: &str, name: &str, lower: f64, upper: f64) {
+        let calculator = Calculator::new();
+        let mut ranges = HashMap::new();
+        ranges.insert(name.to_string(), (lower, upper));
+        let (bound_lower, bound_upper) = calculator.evaluate_interval(expression, &ranges).unwrap();
+        for step in 0..=1000 {
+            let mut point_calculator = Calculator::new();
+            let x = (lower + (upper - lower) * step as f64 / 1000.0).min(upper);
+            point_calculator.set_variable(name, x);
+            let value = point_calculator.parse_str(expression).unwrap();
+            assert!(
+                bound_lower <= value && value <= bound_upper,
+                "{} = {} not in [{}, {}]",
+                expression,
+                value,
+                bound_lower,
+                bound_upper
+            );
+        }
+    }
+
+    // Test outward rounding helpers
+    #[test]
+    fn test_next_up_down() {
+        assert!(next_up(1.0) > 1.0);
+        assert!(next_down(1.0) < 1.0);
+        assert!(next_up(-1.0) > -1.0);
+        assert!(next_down(0.0) < 0.0);
+        assert_eq!(next_up(f64::INFINITY), f64::INFINITY);
+        assert_eq!(next_down(f64::NEG_INFINITY), f64::NEG_INFINITY);
+        let x = Interval::rounded(0.1, 0.1);
+        assert!(x.lower < 0.1 && x.upper > 0.1);
+    }
+
+    // Test bounds of arithmetic expressions
+    #[test]
+    fn test_arithmetic() {
+        let mut calculator = Calculator::new();
+        calculator.set_variable("b", 2.0);
+        let mut ranges = HashMap::new();
+        ranges.insert("a".to_string(), (-1.0, 3.0));
+        let (lower, upper) = calculator.evaluate_interval("a * b - 1", &ranges).unwrap();
+        assert!((lower + 3.0).abs() < 1e-12 && lower <= -3.0);
+        assert!((upper - 5.0).abs() < 1e-12 && upper >= 5.0);
+        let (lower, upper) = calculator.evaluate_interval("a^2", &ranges).unwrap();
+        assert_eq!(lower, 0.0);
+        assert!(upper >= 9.0);
+        let (lower, upper) = calculator.evaluate_interval("1 / a", &ranges).unwrap();
+        assert_eq!((lower, upper), (f64::NEG_INFINITY, f64::INFINITY));
+        let (lower, upper) = calculator
+            .evaluate_interval("c=a+1; c / b", &ranges)
+            .unwrap();
+        assert!(lower <= 0.0 && upper >= 2.0);
+        assert!(calculator.get_variable("c").is_err());
+        assert_eq!(
+            calculator.evaluate_interval("(6 - b) / 4", &ranges),
+            Ok((1.0, 1.0))
+        );
+        assert!(calculator.evaluate_interval("sqrt(b - 2)", &ranges).is_ok());
+
+        assert_bounds_contain("x^3 - 2 * x", "x", -2.0, 1.5);
+        assert_bounds_contain("x^(-2)", "x", 0.5, 1.5);
+        assert_bounds_contain("x^1.5 + 2^x", "x", 0.0, 4.0);
+        assert_bounds_contain("(x + 1) / (x - 3)", "x", -1.0, 2.0);
+    }
+
+    // Test bounds of the built-in functions
+    #[test]
+    fn test_functions() {
+        for function in &[
+            "sin", "cos", "tan", "abs", "atan", "cosh", "sinh", "tanh", "asinh", "arsinh", "exp",
+            "exp2", "expm1", "cbrt", "ceil", "floor", "fract", "round", "sign", "delta", "theta",
+        ] {
+            assert_bounds_contain(&format!("{}(x)", function), "x", -1.3, 1.1);
+            assert_bounds_contain(&format!("{}(x)", function), "x", 0.2, 0.7);
+        }
+        for function in &["acos", "asin", "atanh", "artanh"] {
+            assert_bounds_contain(&format!("{}(x)", function), "x", -0.9, 0.4);
+        }
+        for function in &["acosh", "arcosh", "log", "log10", "sqrt"] {
+            assert_bounds_contain(&format!("{}(x)", function), "x", 1.0, 7.5);
+        }
+        for function in &["atan2", "hypot", "pow", "max", "min"] {
+            assert_bounds_contain(&format!("{}(x, 0.5 - x)", function), "x", 0.1, 0.4);
+            assert_bounds_contain(&format!("{}(2 - x, x)", function), "x", 0.1, 2.0);
+        }
+        assert_bounds_contain("atan2(x, -1)", "x", -1.0, 1.0);
+    }
+
+    // Test that sine and cosine bounds include the extrema inside the interval
+    #[test]
+    fn test_periodic_extrema() {
+        let calculator = Calculator::new();
+        let mut ranges = HashMap::new();
+        ranges.insert("theta".to_string(), (0.0, 2.0 * PI));
+        let (lower, upper) = calculator
+            .evaluate_interval("2 * sin(theta)", &ranges)
+            .unwrap();
+        assert!((-2.0 - 1e-12..=-2.0).contains(&lower));
+        assert!((2.0..2.0 + 1e-12).contains(&upper));
+        ranges.insert("theta".to_string(), (0.1, 3.0));
+        let (lower, upper) = calculator.evaluate_interval("sin(theta)", &ranges).unwrap();
+        assert_eq!(upper, 1.0);
+        assert!(lower <= 3.0_f64.sin() && lower > 0.0);
+        let (lower, upper) = calculator.evaluate_interval("cos(theta)", &ranges).unwrap();
+        assert!(lower <= 3.0_f64.cos() && lower > -1.0);
+        assert!(upper >= 0.1_f64.cos() && upper < 1.0);
+        ranges.insert("theta".to_string(), (3.0, 3.5));
+        let (lower, _) = calculator.evaluate_interval("cos(theta)", &ranges).unwrap();
+        assert_eq!(lower, -1.0);
+        assert_bounds_contain("sin(x) * cos(x)", "x", 1.0, 8.0);
+        assert_bounds_contain("tan(x)", "x", 1.0, 2.0);
+    }
+
+    // Test errors of interval evaluation
+    #[test]
+    fn test_errors() {
+        let calculator = Calculator::new();
+        let mut ranges = HashMap::new();
+        ranges.insert("x".to_string(), (-1.0, 1.0));
+        assert_eq!(
+            calculator.evaluate_interval("sqrt(x)", &ranges),
+            Err(CalculatorError::DomainError {
+                fct: "sqrt".to_string(),
+                arguments: vec![-1.0, 1.0]
+            })
+        );
+        assert_eq!(
+            calculator.evaluate_interval("x / 0", &ranges),
+            Err(CalculatorError::DivisionByZero)
+        );
+        assert_eq!(
+            calculator.evaluate_interval("x + y", &ranges),
+            Err(CalculatorError::VariableNotSet {
+                name: "y".to_string()
+            })
+        );
+        assert_eq!(
+            calculator.evaluate_interval("erf(x)", &ranges),
+            Err(CalculatorError::FunctionNotFound {
+                fct: "erf".to_string()
+            })
+        );
+        ranges.insert("y".to_string(), (1.0, -1.0));
+        assert_eq!(
+            calculator.evaluate_interval("x + y", &ranges),
+            Err(CalculatorError::InvalidInterval {
+                name: "y".to_string(),
+                lower: 1.0,
+                upper: -1.0
+            })
+        );
+    }
+
+    // Test bounds of variables defined by expressions
+    #[test]
+    fn test_defined_variables() {
+        let mut calculator = Calculator::new();
+        calculator.set_variable("x", 0.5);
+        calculator.set_expression("y", "2 * x").unwrap();
+        calculator.set_expression("w", "y^2 - x").unwrap();
+        let mut ranges = HashMap::new();
+        ranges.insert("x".to_string(), (0.0, 1.0));
+        let (lower, upper) = calculator.evaluate_interval("y", &ranges).unwrap();
+        assert!((-1e-12..=0.0).contains(&lower));
+        assert!((2.0..2.0 + 1e-12).contains(&upper));
+        let (lower, upper) = calculator.evaluate_interval("w", &ranges).unwrap();
+        assert!((-1.0 - 1e-12..=-1.0).contains(&lower));
+        assert!((4.0..4.0 + 1e-12).contains(&upper));
+        ranges.insert("y".to_string(), (-1.0, 1.0));
+        assert_eq!(calculator.evaluate_interval("y", &ranges), Ok((-1.0, 1.0)));
+        ranges.remove("x");
+        let (lower, upper) = calculator.evaluate_interval("w", &ranges).unwrap();
+        assert!(lower <= -0.5 && upper >= 0.5);
+    }
+}
@@ -0,0 +1,266 @@
+// Copyright © 2020-2021 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations underthe License.
+
+//! calculator_ndarray module
+//!
+//! Provides linear algebra helpers for two-dimensional arrays of CalculatorComplex
+//! and their evaluation to numeric arrays with a Calculator.
+
+use crate::{Calculator, CalculatorComplex, CalculatorError, CalculatorFloat};
+use ndarray::{Array2, ArrayBase, Data, Ix2};
+use num_complex::Complex64;
+use std::collections::HashMap;
+
+/// Linear algebra operations on two-dimensional arrays of CalculatorComplex.
+pub trait CalculatorArray {
+    /// Return matrix product self * other.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - Right hand side of the product
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Array2<CalculatorComplex>)` - Matrix product
+    /// * `Err(CalculatorError::ShapeMismatch)` - Number of columns of self and rows of other differ
+    fn matmul<S>(
+        &self,
+        other: &ArrayBase<S, Ix2>,
+    ) -> Result<Array2<CalculatorComplex>, CalculatorError>
+    where
+        S: Data<Elem = CalculatorComplex>;
+
+    /// Return Kronecker product of self and other.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - Right hand side of the Kronecker product
+    fn kron<S>(&self, other: &ArrayBase<S, Ix2>) -> Array2<CalculatorComplex>
+    where
+        S: Data<Elem = CalculatorComplex>;
+
+    /// Return trace, the sum of the diagonal elements.
+    fn trace(&self) -> CalculatorComplex;
+
+    /// Return conjugate transpose (hermitian conjugate).
+    fn conj_transpose(&self) -> Array2<CalculatorComplex>;
+}
+
+impl<S> CalculatorArray for ArrayBase<S, Ix2>
+where
+    S: Data<Elem = CalculatorComplex>,
+{
+    fn matmul<S2>(
+        &self,
+        other: &ArrayBase<S2, Ix2>,
+    ) -> Result<Array2<CalculatorComplex>, CalculatorError>
+    where
+        S2: Data<Elem = CalculatorComplex>,
+    {
+        if self.ncols() != other.nrows() {
+            return Err(CalculatorError::ShapeMismatch {
+                lhs: self.dim(),
+                rhs: other.dim(),
+            });
+        }
+        Ok(Array2::from_shape_fn(
+            (self.nrows(), other.ncols()),
+            |(i, j)| {
+                self.row(i)
+                    .iter()
+                    .zip(other.column(j).iter())
+                    .map(|(a, b)| a.clone() * b)
+                    .sum()
+            },
+        ))
+    }
+
+    fn kron<S2>(&self, other: &ArrayBase<S2, Ix2>) -> Array2<CalculatorComplex>
+    where
+        S2: Data<Elem = CalculatorComplex>,
+    {
+        let (rows, cols) = other.dim();
+        Array2::from_shape_fn((self.nrows() * rows, self.ncols() * cols), |(i, j)| {
+            self[[i / rows, j / cols]].clone() * &other[[i % rows, j % cols]]
+        })
+    }
+
+    fn trace(&self) -> CalculatorComplex {
+        self.diag().iter().cloned().sum()
+    }
+
+    fn conj_transpose(&self) -> Array2<CalculatorComplex> {
+        self.t().map(|x| x.conj())
+    }
+}
+
+impl Calculator {
+    /// Evaluate two-dimensional array of CalculatorComplex to numeric array.
+    ///
+    /// Every distinct symbolic expression in the array is evaluated only once.
+    /// The Calculator is not modified, expressions containing assignments are rejected.
+    ///
+    /// # Arguments
+    ///
+    /// * `array` - Array of CalculatorComplex that is evaluated
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Array2<Complex64>)` - Evaluated array
+    /// * `Err(CalculatorError)` - Evaluating one of the entries failed
+    pub fn evaluate_array<S>(
+        &self,
+        array: &ArrayBase<S, Ix2>,
+    ) -> Result<Array2<Complex64>, CalculatorError>
+    where
+        S: Data<Elem = CalculatorComplex>,
+    {
+        let mut memo: HashMap<String, f64> = HashMap::new();
+        let mut values = Vec::with_capacity(array.len());
+        for element in array.iter() {
+            let re = self.evaluate_memoized(&element.re, &mut memo)?;
+            let im = self.evaluate_memoized(&element.im, &mut memo)?;
+            values.push(Complex64::new(re, im));
+        }
+        Ok(Array2::from_shape_vec(array.dim(), values)
+            .expect("Number of values matches shape of input array"))
+    }
+
+    /// Return value of CalculatorFloat, evaluating each symbolic expression only once.
+    fn evaluate_memoized(
+        &self,
+        value: &CalculatorFloat,
+        memo: &mut HashMap<String, f64>,
+    ) -> Result<f64, CalculatorError> {
+        match value {
+            CalculatorFloat::Float(x) => Ok(*x),
+            CalculatorFloat::Str(expression) => {
+                let key = expression.to_string();
+                match memo.get(&key) {
+                    Some(x) => Ok(*x),
+                    None => {
+                        let x = self.evaluate_float(value)?;
+                        memo.insert(key, x);
+                        Ok(x)
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CalculatorArray;
+    use crate::{Calculator, CalculatorComplex, CalculatorError};
+    use ndarray::{array, Array2};
+    use num_complex::Complex64;
+
+    /// Return symbolic rotation exp(-i theta/2 X).
+    fn rotation_x(theta: &str) -> Array2<CalculatorComplex> {
+        let c = CalculatorComplex::new(format!("cos({} / 2)", theta), 0);
+        let s = CalculatorComplex::new(0, format!("(-sin({} / 2))", theta));
+        array![[c.clone(), s.clone()], [s, c]]
+    }
+
+    // Test matrix product and evaluation of symbolic arrays
+    #[test]
+    fn matmul_evaluate() {
+        let a = rotation_x("a");
+        let b = rotation_x("b");
+        let product = a.matmul(&b).unwrap();
+        let mut calculator = Calculator::new();
+        calculator.set_variable("a", 0.3);
+        calculator.set_variable("b", 0.5);
+        calculator.set_variable("c", 0.8);
+        let evaluated = calculator.evaluate_array(&product).unwrap();
+        let expected = calculator.evaluate_array(&rotation_x("c")).unwrap();
+        for (x, y) in evaluated.iter().zip(expected.iter()) {
+            assert!((x - y).norm() < 1e-12);
+        }
+        assert_eq!(
+            a.matmul(&Array2::from_elem((3, 1), CalculatorComplex::ONE)),
+            Err(CalculatorError::ShapeMismatch {
+                lhs: (2, 2),
+                rhs: (3, 1)
+            })
+        );
+    }
+
+    // Test Kronecker product, trace and conjugate transpose
+    #[test]
+    fn kron_trace_conj_transpose() {
+        let x = array![
+            [CalculatorComplex::ZERO, CalculatorComplex::ONE],
+            [CalculatorComplex::ONE, CalculatorComplex::ZERO]
+        ];
+        let y = array![
+            [CalculatorComplex::ZERO, -CalculatorComplex::I],
+            [CalculatorComplex::I, CalculatorComplex::ZERO]
+        ];
+        let xy = x.kron(&y);
+        assert_eq!(xy.dim(), (4, 4));
+        assert_eq!(xy[[0, 3]], -CalculatorComplex::I);
+        assert_eq!(xy[[3, 0]], CalculatorComplex::I);
+        assert_eq!(xy[[0, 0]], CalculatorComplex::ZERO);
+        assert_eq!(xy.trace(), CalculatorComplex::ZERO);
+        assert_eq!(xy.conj_transpose(), xy);
+        let r = rotation_x("a");
+        assert_eq!(
+            r.trace(),
+            CalculatorComplex::new("(cos(a / 2) + cos(a / 2))", 0)
+        );
+        assert_eq!(
+            r.conj_transpose()[[0, 1]],
+            CalculatorComplex::new(0, "(-(-sin(a / 2)))")
+        );
+        let mut calculator = Calculator::new();
+        calculator.set_variable("a", 0.7);
+        let identity = r.conj_transpose().matmul(&r).unwrap();
+        let evaluated = calculator.evaluate_array(&identity).unwrap();
+        assert!((evaluated[[0, 0]] - Complex64::new(1.0, 0.0)).norm() < 1e-12);
+        assert!(evaluated[[0, 1]].norm() < 1e-12);
+    }
+
+    // Test error of evaluating array with unknown variables
+    #[test]
+    fn evaluate_error() {
+        let calculator = Calculator::new();
+        assert_eq!(
+            calculator.evaluate_array(&rotation_x("a")),
+            Err(CalculatorError::VariableNotSet {
+                name: "a".to_string()
+            })
+        );
+    }
+
+    // Test that evaluating an array does not modify the Calculator
+    #[test]
+    fn evaluate_read_only() {
+        let mut calculator = Calculator::new();
+        calculator.set_variable("a", 1.0);
+        let assignment = array![[
+            CalculatorComplex::new("a = 2", 0),
+            CalculatorComplex::new("a", 0)
+        ]];
+        assert_eq!(
+            calculator.evaluate_array(&assignment),
+            Err(CalculatorError::AssignmentNotAllowed {
+                name: "a".to_string()
+            })
+        );
+        assert_eq!(calculator.get_variable("a"), Ok(1.0));
+        let shared = std::sync::Arc::new(calculator);
+        let evaluated = shared.evaluate_array(&rotation_x("a")).unwrap();
+        assert!((evaluated[[0, 0]].re - 0.5_f64.cos()).abs() < 1e-15);
+    }
+}
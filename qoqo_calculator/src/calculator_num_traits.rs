@@ -0,0 +1,257 @@
+// Copyright © 2020-2021 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations underthe License.
+
+//! calculator_num_traits module
+//!
+//! Provides implementations of the num-traits numeric traits for CalculatorFloat
+//! and CalculatorComplex so they can be used in generic numeric code.
+
+use crate::{CalculatorComplex, CalculatorError, CalculatorFloat};
+use num_traits::{Inv, Num, One, Pow, Signed, Zero};
+
+/// Implement additive identity for CalculatorFloat.
+impl Zero for CalculatorFloat {
+    fn zero() -> Self {
+        CalculatorFloat::ZERO
+    }
+
+    fn is_zero(&self) -> bool {
+        matches!(self, CalculatorFloat::Float(x) if *x == 0.0)
+    }
+}
+
+/// Implement multiplicative identity for CalculatorFloat.
+impl One for CalculatorFloat {
+    fn one() -> Self {
+        CalculatorFloat::ONE
+    }
+}
+
+/// Implement Num for CalculatorFloat.
+impl Num for CalculatorFloat {
+    type FromStrRadixErr = CalculatorError;
+
+    /// Return CalculatorFloat from string in given radix.
+    ///
+    /// Strings in radix 10 that are not numbers are kept as symbolic expressions,
+    /// other radices only accept integers.
+    fn from_str_radix(str: &str, radix: u32) -> Result<Self, Self::FromStrRadixErr> {
+        if radix == 10 {
+            return Ok(match str.parse::<f64>() {
+                Ok(x) => CalculatorFloat::Float(x),
+                Err(_) => CalculatorFloat::from(str),
+            });
+        }
+        i64::from_str_radix(str, radix)
+            .map(|x| CalculatorFloat::Float(x as f64))
+            .map_err(|_| CalculatorError::NotConvertable)
+    }
+}
+
+/// Implement Signed for CalculatorFloat.
+///
+/// Symbolic values are neither positive nor negative.
+impl Signed for CalculatorFloat {
+    fn abs(&self) -> Self {
+        CalculatorFloat::abs(self)
+    }
+
+    fn abs_sub(&self, other: &Self) -> Self {
+        (self.clone() - other).max(0.0)
+    }
+
+    fn signum(&self) -> Self {
+        CalculatorFloat::signum(self)
+    }
+
+    fn is_positive(&self) -> bool {
+        matches!(self, CalculatorFloat::Float(x) if *x > 0.0)
+    }
+
+    fn is_negative(&self) -> bool {
+        matches!(self, CalculatorFloat::Float(x) if *x < 0.0)
+    }
+}
+
+/// Implement multiplicative inverse for CalculatorFloat.
+impl Inv for CalculatorFloat {
+    type Output = CalculatorFloat;
+
+    fn inv(self) -> Self::Output {
+        self.recip()
+    }
+}
+
+/// Implement power x^y for CalculatorFloat and generic type `T`.
+///
+/// # Arguments
+///
+/// * `other` - Any type T for which CalculatorFloat::From<T> trait is implemented
+///
+impl<T> Pow<T> for CalculatorFloat
+where
+    CalculatorFloat: From<T>,
+{
+    type Output = CalculatorFloat;
+
+    fn pow(self, other: T) -> Self::Output {
+        self.powf(other)
+    }
+}
+
+/// Implement additive identity for CalculatorComplex.
+impl Zero for CalculatorComplex {
+    fn zero() -> Self {
+        CalculatorComplex::ZERO
+    }
+
+    fn is_zero(&self) -> bool {
+        self.re.is_zero() && self.im.is_zero()
+    }
+}
+
+/// Implement multiplicative identity for CalculatorComplex.
+impl One for CalculatorComplex {
+    fn one() -> Self {
+        CalculatorComplex::ONE
+    }
+}
+
+/// Implement multiplicative inverse for CalculatorComplex.
+impl Inv for CalculatorComplex {
+    type Output = CalculatorComplex;
+
+    fn inv(self) -> Self::Output {
+        self.recip()
+    }
+}
+
+/// Implement power x^y for CalculatorComplex and generic type `T`.
+///
+/// # Arguments
+///
+/// * `other` - Any type T for which CalculatorComplex::From<T> trait is implemented
+///
+impl<T> Pow<T> for CalculatorComplex
+where
+    CalculatorComplex: From<T>,
+{
+    type Output = CalculatorComplex;
+
+    fn pow(self, other: T) -> Self::Output {
+        self.powc(other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{CalculatorComplex, CalculatorError, CalculatorFloat};
+    use num_traits::{Inv, Num, One, Pow, Signed, Zero};
+
+    /// Return polynomial sum_k coefficients[k] * x^k evaluated with Horner's scheme.
+    fn horner<T: Num + Clone>(coefficients: &[T], x: T) -> T {
+        coefficients
+            .iter()
+            .rev()
+            .fold(T::zero(), |acc, c| acc * x.clone() + c.clone())
+    }
+
+    // Test generic numeric code with CalculatorFloat
+    #[test]
+    fn generic_float() {
+        let coefficients = vec![
+            CalculatorFloat::from(1),
+            CalculatorFloat::from(2),
+            CalculatorFloat::from(3),
+        ];
+        assert_eq!(
+            horner(&coefficients, CalculatorFloat::from(2)),
+            CalculatorFloat::from(17)
+        );
+        let symbolic = horner(&coefficients, CalculatorFloat::from("x"));
+        let mut calculator = crate::Calculator::new();
+        calculator.set_variable("x", 2.0);
+        assert_eq!(calculator.parse_get(symbolic).unwrap(), 17.0);
+    }
+
+    // Test the identities of CalculatorFloat and CalculatorComplex
+    #[test]
+    fn identities() {
+        assert!(CalculatorFloat::zero().is_zero());
+        assert!(!CalculatorFloat::from("x").is_zero());
+        assert!(CalculatorFloat::one().is_one());
+        assert!(CalculatorComplex::zero().is_zero());
+        assert!(!CalculatorComplex::new(0, "x").is_zero());
+        assert!(CalculatorComplex::one().is_one());
+    }
+
+    // Test from_str_radix for CalculatorFloat
+    #[test]
+    fn from_str_radix() {
+        assert_eq!(
+            CalculatorFloat::from_str_radix("1.5", 10),
+            Ok(CalculatorFloat::from(1.5))
+        );
+        assert_eq!(
+            CalculatorFloat::from_str_radix("2*x", 10),
+            Ok(CalculatorFloat::from("2*x"))
+        );
+        assert_eq!(
+            CalculatorFloat::from_str_radix("ff", 16),
+            Ok(CalculatorFloat::from(255))
+        );
+        assert_eq!(
+            CalculatorFloat::from_str_radix("x", 16),
+            Err(CalculatorError::NotConvertable)
+        );
+    }
+
+    // Test Signed for CalculatorFloat
+    #[test]
+    fn signed() {
+        let x = CalculatorFloat::from(-2.5);
+        assert_eq!(Signed::abs(&x), CalculatorFloat::from(2.5));
+        assert_eq!(Signed::signum(&x), CalculatorFloat::from(-1));
+        assert!(x.is_negative());
+        assert!(!x.is_positive());
+        assert_eq!(x.abs_sub(&CalculatorFloat::from(1)), CalculatorFloat::ZERO);
+        assert_eq!(
+            CalculatorFloat::from(3).abs_sub(&CalculatorFloat::from(1)),
+            CalculatorFloat::from(2)
+        );
+        let s = CalculatorFloat::from("x");
+        assert!(!s.is_negative());
+        assert!(!s.is_positive());
+        assert_eq!(
+            s.abs_sub(&CalculatorFloat::from("y")),
+            CalculatorFloat::from("max((x - y), 0e0)")
+        );
+    }
+
+    // Test Inv and Pow for CalculatorFloat and CalculatorComplex
+    #[test]
+    fn inv_pow() {
+        assert_eq!(CalculatorFloat::from(4).inv(), CalculatorFloat::from(0.25));
+        assert_eq!(CalculatorFloat::from(2).pow(3), CalculatorFloat::from(8));
+        assert_eq!(
+            CalculatorFloat::from("x").pow(2),
+            CalculatorFloat::from("(x ^ 2e0)")
+        );
+        assert_eq!(
+            CalculatorComplex::new(3, 4).inv(),
+            CalculatorComplex::new(0.12, -0.16)
+        );
+        assert!(CalculatorComplex::new(0, 1)
+            .pow(2)
+            .isclose(CalculatorComplex::new(-1, 0)));
+    }
+}
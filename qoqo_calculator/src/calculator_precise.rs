@@ -0,0 +1,715 @@
+// Copyright © 2020-2021 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! calculator_precise module
+//!
+//! Provides evaluation of string expressions with arbitrary-precision floats.
+//! Only available with the `arbitrary-precision` feature.
+
+use crate::calculator::{Evaluator, Parser, ReadOnlyEvaluator};
+use crate::{Calculator, CalculatorError};
+use dashu_float::ops::SquareRoot;
+use dashu_float::round::mode::HalfEven;
+use dashu_float::{DBig, FBig};
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::str::FromStr;
+
+/// Arbitrary-precision binary float returned by [Calculator::parse_str_precise].
+///
+/// Use `to_f64().value()` for a lossy conversion to f64.
+pub type PreciseFloat = FBig<HalfEven, 2>;
+
+/// Additional bits used for intermediate results of functions.
+static GUARD_BITS: usize = 32;
+/// Maximum number of terms of power series.
+static MAX_TERMS: usize = 100_000;
+
+/// Return integer as PreciseFloat with given precision.
+fn int(value: i64, precision: usize) -> PreciseFloat {
+    PreciseFloat::from(value).with_precision(precision).value()
+}
+
+/// Return true when x is zero.
+fn is_zero(x: &PreciseFloat) -> bool {
+    x.repr().is_zero()
+}
+
+/// Return true when x is negative.
+fn is_negative(x: &PreciseFloat) -> bool {
+    x < &int(0, x.precision())
+}
+
+/// Return -x.
+fn neg(x: &PreciseFloat) -> PreciseFloat {
+    -x.clone()
+}
+
+/// Return DomainError for function evaluated at arguments.
+fn domain_error(fct: &str, arguments: &[&PreciseFloat]) -> CalculatorError {
+    CalculatorError::DomainError {
+        fct: fct.to_string(),
+        arguments: arguments.iter().map(|x| x.to_f64().value()).collect(),
+    }
+}
+
+/// Return sum of a power series with first term `term` and term ratio given by `next`.
+///
+/// The summation stops when adding a term no longer changes the sum.
+fn sum_series<F>(term: PreciseFloat, mut next: F) -> PreciseFloat
+where
+    F: FnMut(&PreciseFloat, i64) -> PreciseFloat,
+{
+    let mut sum = term.clone();
+    let mut term = term;
+    for n in 1..MAX_TERMS as i64 {
+        term = next(&term, n);
+        let new_sum = &sum + &term;
+        if new_sum == sum {
+            break;
+        }
+        sum = new_sum;
+    }
+    sum
+}
+
+/// Return atan(x) from the Taylor series, converging quickly for |x| well below 1.
+fn atan_series(x: &PreciseFloat) -> PreciseFloat {
+    let square = x * x;
+    let precision = x.precision();
+    let mut power = x.clone();
+    sum_series(x.clone(), |_, n| {
+        power = neg(&(&power * &square));
+        &power / &int(2 * n + 1, precision)
+    })
+}
+
+/// Return pi with the given precision using Machin's formula.
+fn pi(precision: usize) -> PreciseFloat {
+    let one = int(1, precision);
+    let fifth = &one / &int(5, precision);
+    let part = &one / &int(239, precision);
+    &(&int(16, precision) * &atan_series(&fifth)) - &(&int(4, precision) * &atan_series(&part))
+}
+
+/// Evaluator for arbitrary-precision evaluation.
+///
+/// Functions are evaluated with additional guard bits and
+/// rounded to the requested precision afterwards.
+///
+/// # Fields
+///
+/// * `calculator` - Calculator providing variables
+/// * `precision` - Number of significant bits of the results
+/// * `working` - Number of significant bits of intermediate results
+/// * `pi` - Pi with working precision
+/// * `assigned` - Precise values of variables assigned during the evaluation
+///
+struct PreciseEvaluator<'a> {
+    calculator: &'a mut Calculator,
+    precision: usize,
+    working: usize,
+    pi: PreciseFloat,
+    assigned: HashMap<String, PreciseFloat>,
+}
+
+impl<'a> PreciseEvaluator<'a> {
+    /// Return f64 as PreciseFloat, NaN and infinite values are not representable.
+    fn precise_f64(&self, fct: &str, value: f64) -> Result<PreciseFloat, CalculatorError> {
+        match PreciseFloat::try_from(value) {
+            Ok(x) => Ok(x.with_precision(self.precision).value()),
+            Err(_) if value.is_nan() => Err(CalculatorError::DomainError {
+                fct: fct.to_string(),
+                arguments: vec![value],
+            }),
+            Err(_) => Err(CalculatorError::Overflow {
+                fct: fct.to_string(),
+                arguments: vec![value],
+            }),
+        }
+    }
+
+    /// Return x with working precision.
+    fn widen(&self, x: &PreciseFloat) -> PreciseFloat {
+        x.clone().with_precision(self.working).value()
+    }
+
+    /// Return x rounded to the requested precision.
+    fn round(&self, x: PreciseFloat) -> PreciseFloat {
+        x.with_precision(self.precision).value()
+    }
+
+    /// Return integer with working precision.
+    fn int(&self, value: i64) -> PreciseFloat {
+        int(value, self.working)
+    }
+
+    /// Return (sin(x), cos(x)) for x with working precision.
+    fn sin_cos(&self, x: &PreciseFloat) -> (PreciseFloat, PreciseFloat) {
+        // Reduce argument with pi accurate enough for the magnitude of x
+        let magnitude = x.to_f64().value().abs();
+        let (x, pi) = if magnitude > 1.0 {
+            let extra = magnitude.log2().ceil().min(1e6) as usize;
+            let precision = self.working + extra;
+            (x.clone().with_precision(precision).value(), pi(precision))
+        } else {
+            (x.clone(), self.pi.clone())
+        };
+        let precision = x.precision();
+        let half_pi = &pi / &int(2, precision);
+        let quadrants = (&x / &half_pi).round();
+        let reduced = self.widen(&(&x - &(&quadrants * &half_pi)));
+        let four = int(4, precision);
+        let quadrant = (&quadrants - &(&four * &(&quadrants / &four).floor()))
+            .to_f64()
+            .value() as i64;
+        let square = &reduced * &reduced;
+        let sin = sum_series(reduced.clone(), |term, n| {
+            neg(&(&(term * &square) / &self.int((2 * n) * (2 * n + 1))))
+        });
+        let cos = sum_series(self.int(1), |term, n| {
+            neg(&(&(term * &square) / &self.int((2 * n - 1) * (2 * n))))
+        });
+        match quadrant {
+            1 => (cos, neg(&sin)),
+            2 => (neg(&sin), neg(&cos)),
+            3 => (neg(&cos), sin),
+            _ => (sin, cos),
+        }
+    }
+
+    /// Return atan(x) for x with working precision.
+    fn atan(&self, x: &PreciseFloat) -> PreciseFloat {
+        let one = self.int(1);
+        if is_negative(x) {
+            return neg(&self.atan(&neg(x)));
+        }
+        if x > &one {
+            let half_pi = &self.pi / &self.int(2);
+            return &half_pi - &self.atan(&(&one / x));
+        }
+        // Halve the argument with atan(x) = 2 atan(x / (1 + sqrt(1 + x^2)))
+        let mut reduced = x.clone();
+        for _ in 0..4 {
+            let root = (&one + &(&reduced * &reduced)).sqrt();
+            reduced = &reduced / &(&one + &root);
+        }
+        &self.int(16) * &atan_series(&reduced)
+    }
+
+    /// Return exp(x) - 1 for x with working precision.
+    fn exp_m1(&self, x: &PreciseFloat) -> PreciseFloat {
+        x.exp_m1()
+    }
+
+    /// Return x^n for integer n by repeated squaring.
+    fn powi(&self, x: &PreciseFloat, n: i64) -> PreciseFloat {
+        let mut result = self.int(1);
+        let mut base = x.clone();
+        let mut exponent = n.unsigned_abs();
+        while exponent > 0 {
+            if exponent % 2 == 1 {
+                result = &result * &base;
+            }
+            base = &base * &base;
+            exponent /= 2;
+        }
+        if n < 0 {
+            &self.int(1) / &result
+        } else {
+            result
+        }
+    }
+
+    /// Return x^y for arguments with working precision.
+    fn powf(&self, x: &PreciseFloat, y: &PreciseFloat) -> Result<PreciseFloat, CalculatorError> {
+        let is_integer = is_zero(&y.fract());
+        let integer = y.to_f64().value();
+        if is_zero(y) {
+            return Ok(self.int(1));
+        }
+        if is_zero(x) {
+            return if is_negative(y) {
+                Err(domain_error("pow", &[x, y]))
+            } else {
+                Ok(self.int(0))
+            };
+        }
+        if is_integer && integer.abs() <= 2_f64.powi(31) {
+            return Ok(self.powi(x, integer as i64));
+        }
+        if is_negative(x) {
+            return Err(domain_error("pow", &[x, y]));
+        }
+        Ok((y * &x.ln()).exp())
+    }
+
+    /// Return function with one argument for x with working precision.
+    fn function_1_argument_working(
+        &self,
+        fct: &str,
+        x: &PreciseFloat,
+    ) -> Result<PreciseFloat, CalculatorError> {
+        let one = self.int(1);
+        let half = &one / &self.int(2);
+        let tolerance = self.precise_f64(fct, f64::EPSILON)?;
+        match fct {
+            "sin" => Ok(self.sin_cos(x).0),
+            "cos" => Ok(self.sin_cos(x).1),
+            "tan" => {
+                let (sin, cos) = self.sin_cos(x);
+                if is_zero(&cos) {
+                    return Err(domain_error(fct, &[x]));
+                }
+                Ok(&sin / &cos)
+            }
+            "abs" => Ok(if is_negative(x) { neg(x) } else { x.clone() }),
+            "asin" | "acos" => {
+                let magnitude = if is_negative(x) { neg(x) } else { x.clone() };
+                if magnitude > one {
+                    return Err(domain_error(fct, &[x]));
+                }
+                let half_pi = &self.pi / &self.int(2);
+                let asin = if magnitude == one {
+                    if is_negative(x) {
+                        neg(&half_pi)
+                    } else {
+                        half_pi.clone()
+                    }
+                } else {
+                    self.atan(&(x / &(&one - &(x * x)).sqrt()))
+                };
+                if fct == "asin" {
+                    Ok(asin)
+                } else {
+                    Ok(&half_pi - &asin)
+                }
+            }
+            "atan" => Ok(self.atan(x)),
+            "sinh" => Ok(&(&self.exp_m1(x) - &self.exp_m1(&neg(x))) * &half),
+            "cosh" => Ok(&(&x.exp() + &neg(x).exp()) * &half),
+            "tanh" => {
+                let exp_m1 = self.exp_m1(&(x * &self.int(2)));
+                Ok(&exp_m1 / &(&exp_m1 + &self.int(2)))
+            }
+            "asinh" | "arsinh" => {
+                let magnitude = if is_negative(x) { neg(x) } else { x.clone() };
+                let square = x * x;
+                let root = (&one + &square).sqrt();
+                let asinh = (&magnitude + &(&square / &(&one + &root))).ln_1p();
+                Ok(if is_negative(x) { neg(&asinh) } else { asinh })
+            }
+            "acosh" | "arcosh" => {
+                if x < &one {
+                    return Err(domain_error(fct, &[x]));
+                }
+                Ok((x + &(&(x * x) - &one).sqrt()).ln())
+            }
+            "atanh" | "artanh" => {
+                if &one <= x || x <= &neg(&one) {
+                    return Err(domain_error(fct, &[x]));
+                }
+                Ok(&(&x.ln_1p() - &neg(x).ln_1p()) * &half)
+            }
+            "exp" => Ok(x.exp()),
+            "exp2" => Ok((x * &self.int(2).ln()).exp()),
+            "expm1" => Ok(self.exp_m1(x)),
+            "log" | "log10" => {
+                if is_zero(x) || is_negative(x) {
+                    return Err(domain_error(fct, &[x]));
+                }
+                if fct == "log" {
+                    Ok(x.ln())
+                } else {
+                    Ok(&x.ln() / &self.int(10).ln())
+                }
+            }
+            "sqrt" => {
+                if is_negative(x) {
+                    return Err(domain_error(fct, &[x]));
+                }
+                Ok(if is_zero(x) { x.clone() } else { x.sqrt() })
+            }
+            "cbrt" => {
+                if is_zero(x) {
+                    return Ok(x.clone());
+                }
+                let magnitude = if is_negative(x) { neg(x) } else { x.clone() };
+                let cbrt = (&magnitude.ln() / &self.int(3)).exp();
+                Ok(if is_negative(x) { neg(&cbrt) } else { cbrt })
+            }
+            "ceil" => Ok(x.ceil()),
+            "floor" => Ok(x.floor()),
+            "fract" => Ok(x.fract()),
+            "round" => Ok(x.round()),
+            "sign" => Ok(if is_negative(x) { neg(&one) } else { one }),
+            "delta" => {
+                let magnitude = if is_negative(x) { neg(x) } else { x.clone() };
+                Ok(if magnitude < tolerance {
+                    one
+                } else {
+                    self.int(0)
+                })
+            }
+            "theta" => {
+                let magnitude = if is_negative(x) { neg(x) } else { x.clone() };
+                Ok(if magnitude < tolerance {
+                    half
+                } else if is_negative(x) {
+                    self.int(0)
+                } else {
+                    one
+                })
+            }
+            _ => Err(CalculatorError::FunctionNotFound {
+                fct: fct.to_string(),
+            }),
+        }
+    }
+
+    /// Return function with two arguments for x and y with working precision.
+    fn function_2_arguments_working(
+        &self,
+        fct: &str,
+        x: &PreciseFloat,
+        y: &PreciseFloat,
+    ) -> Result<PreciseFloat, CalculatorError> {
+        match fct {
+            "atan2" => {
+                if is_zero(y) {
+                    let half_pi = &self.pi / &self.int(2);
+                    return Ok(if is_zero(x) {
+                        self.int(0)
+                    } else if is_negative(x) {
+                        neg(&half_pi)
+                    } else {
+                        half_pi
+                    });
+                }
+                let angle = self.atan(&(x / y));
+                Ok(if !is_negative(y) {
+                    angle
+                } else if is_negative(x) {
+                    &angle - &self.pi
+                } else {
+                    &angle + &self.pi
+                })
+            }
+            "hypot" => Ok((&(x * x) + &(y * y)).sqrt()),
+            "pow" => self.powf(x, y),
+            "max" => Ok(if x >= y { x.clone() } else { y.clone() }),
+            "min" => Ok(if x <= y { x.clone() } else { y.clone() }),
+            _ => Err(CalculatorError::FunctionNotFound {
+                fct: fct.to_string(),
+            }),
+        }
+    }
+}
+
+impl<'a> Evaluator for PreciseEvaluator<'a> {
+    type Value = PreciseFloat;
+
+    fn number(&self, value: f64) -> Result<PreciseFloat, CalculatorError> {
+        self.precise_f64("number", value)
+    }
+
+    fn number_literal(&self, literal: &str, value: f64) -> Result<PreciseFloat, CalculatorError> {
+        match DBig::from_str(literal) {
+            Ok(x) => Ok(x
+                .with_base_and_precision::<2>(self.precision)
+                .value()
+                .with_rounding::<HalfEven>()),
+            Err(_) => self.number(value),
+        }
+    }
+
+    fn variable(&self, name: &str) -> Result<PreciseFloat, CalculatorError> {
+        if let Some(x) = self.assigned.get(name) {
+            return Ok(x.clone());
+        }
+        match self.calculator.stored_variable(name) {
+            Some(value) => self.precise_f64(name, value),
+            // Variables defined by expressions are evaluated with full precision.
+            // The definition is parsed again because its compiled form only keeps f64 literals.
+            None => match self.calculator.definitions.get(name) {
+                Some(definition) => {
+                    let mut evaluator = ReadOnlyEvaluator { evaluator: self };
+                    let mut parser = Parser::new(&definition.expression, &mut evaluator);
+                    parser
+                        .evaluate_all_tokens()?
+                        .ok_or(CalculatorError::NoValueReturnedParsing)
+                }
+                None => self.precise_f64(name, self.calculator.get_variable(name)?),
+            },
+        }
+    }
+
+    fn assign(&mut self, name: &str, value: PreciseFloat) -> Result<PreciseFloat, CalculatorError> {
+        self.calculator.set_variable(name, value.to_f64().value());
+        self.assigned.insert(name.to_string(), value.clone());
+        Ok(value)
+    }
+
+    fn add(&self, lhs: PreciseFloat, rhs: PreciseFloat) -> Result<PreciseFloat, CalculatorError> {
+        Ok(&lhs + &rhs)
+    }
+
+    fn sub(&self, lhs: PreciseFloat, rhs: PreciseFloat) -> Result<PreciseFloat, CalculatorError> {
+        Ok(&lhs - &rhs)
+    }
+
+    fn mul(&self, lhs: PreciseFloat, rhs: PreciseFloat) -> Result<PreciseFloat, CalculatorError> {
+        Ok(&lhs * &rhs)
+    }
+
+    fn div(&self, lhs: PreciseFloat, rhs: PreciseFloat) -> Result<PreciseFloat, CalculatorError> {
+        if is_zero(&rhs) {
+            return Err(CalculatorError::DivisionByZero);
+        }
+        Ok(&lhs / &rhs)
+    }
+
+    fn pow(&self, lhs: PreciseFloat, rhs: PreciseFloat) -> Result<PreciseFloat, CalculatorError> {
+        Ok(self.round(self.powf(&self.widen(&lhs), &self.widen(&rhs))?))
+    }
+
+    fn neg(&self, value: PreciseFloat) -> Result<PreciseFloat, CalculatorError> {
+        Ok(-value)
+    }
+
+    fn function_1_argument(
+        &self,
+        fct: &str,
+        arg0: PreciseFloat,
+    ) -> Result<PreciseFloat, CalculatorError> {
+        Ok(self.round(self.function_1_argument_working(fct, &self.widen(&arg0))?))
+    }
+
+    fn function_2_arguments(
+        &self,
+        fct: &str,
+        arg0: PreciseFloat,
+        arg1: PreciseFloat,
+    ) -> Result<PreciseFloat, CalculatorError> {
+        Ok(self.round(self.function_2_arguments_working(
+            fct,
+            &self.widen(&arg0),
+            &self.widen(&arg1),
+        )?))
+    }
+}
+
+impl Calculator {
+    ///  Parse a string expression with arbitrary precision.
+    ///
+    /// Number literals are parsed exactly and all operations and functions are
+    /// evaluated with `precision` significant bits. Variables of the Calculator are
+    /// taken as exact f64 values, variables defined by expressions are evaluated from their
+    /// definition with the same precision. Assignments store the lossy f64 value in the Calculator.
+    /// Operations without a finite real result return a DomainError.
+    ///
+    /// # Arguments
+    ///
+    /// * `expression` - Expression that is parsed
+    /// * `precision` - Number of significant bits of the evaluation, at least 1
+    ///
+    /// # Returns
+    ///
+    /// `PreciseFloat` - High-precision value, `to_f64().value()` converts to f64
+    ///
+    pub fn parse_str_precise(
+        &mut self,
+        expression: &str,
+        precision: usize,
+    ) -> Result<PreciseFloat, CalculatorError> {
+        let precision = precision.max(1);
+        let working = precision + GUARD_BITS;
+        let mut evaluator = PreciseEvaluator {
+            calculator: self,
+            precision,
+            working,
+            pi: pi(working),
+            assigned: HashMap::new(),
+        };
+        let mut parser = Parser::new(expression, &mut evaluator);
+        match parser.evaluate_all_tokens()? {
+            None => Err(CalculatorError::NoValueReturnedParsing),
+            Some(x) => Ok(x),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PreciseFloat;
+    use crate::{Calculator, CalculatorError};
+    use dashu_float::DBig;
+    use std::str::FromStr;
+
+    // Return decimal value with given binary precision
+    fn decimal(value: &str, precision: usize) -> PreciseFloat {
+        DBig::from_str(value)
+            .unwrap()
+            .with_base_and_precision::<2>(precision)
+            .value()
+            .with_rounding()
+    }
+
+    // Test that precise evaluation agrees with the f64 evaluation
+    #[test]
+    fn test_f64_agreement() {
+        let mut calculator = Calculator::new();
+        calculator.set_variable("x", 0.3);
+        calculator.set_variable("y", -0.7);
+        let mut expressions: Vec<String> = vec![
+            "x + y * 3 - 2 / x".to_string(),
+            "x^y + 2^(-3) + y^3 + 0.5e-1".to_string(),
+        ];
+        for function in &[
+            "sin", "cos", "tan", "abs", "atan", "cosh", "sinh", "tanh", "asinh", "arsinh", "exp",
+            "exp2", "expm1", "cbrt", "ceil", "floor", "fract", "round", "sign", "delta", "theta",
+            "acos", "asin", "atanh", "artanh",
+        ] {
+            expressions.push(format!("{}(x)", function));
+            expressions.push(format!("{}(y)", function));
+            expressions.push(format!("{}(9 * y)", function));
+        }
+        for function in &["acosh", "arcosh", "log", "log10", "sqrt"] {
+            expressions.push(format!("{}(1 - 5 * y)", function));
+        }
+        for function in &["atan2", "hypot", "pow", "max", "min"] {
+            expressions.push(format!("{}(x, 2 - x)", function));
+            expressions.push(format!("{}(y, x)", function));
+            expressions.push(format!("{}(2 * y, y)", function));
+        }
+        for expression in expressions
+            .iter()
+            // Skip arguments outside of the real domain of the function
+            .filter(|e| {
+                ![
+                    "acos(9",
+                    "asin(9",
+                    "atanh(9",
+                    "artanh(9",
+                    "pow(y",
+                    "pow(2 * y",
+                ]
+                .iter()
+                .any(|start| e.starts_with(start))
+            })
+        {
+            let value = calculator.parse_str(expression).unwrap();
+            let precise = calculator
+                .parse_str_precise(expression, 128)
+                .unwrap()
+                .to_f64()
+                .value();
+            assert!(
+                (value - precise).abs() <= 4.0 * f64::EPSILON * value.abs().max(1.0),
+                "{}: {} != {}",
+                expression,
+                value,
+                precise
+            );
+        }
+    }
+
+    // Test precision beyond f64
+    #[test]
+    fn test_precision() {
+        let mut calculator = Calculator::new();
+        let value = calculator
+            .parse_str_precise("0.1 + 0.2 - 0.3", 200)
+            .unwrap();
+        assert!(value < decimal("1e-58", 200) && value > decimal("-1e-58", 200));
+        let pi = calculator.parse_str_precise("4 * atan(1)", 256).unwrap();
+        let reference = decimal(
+            "3.14159265358979323846264338327950288419716939937510582097494459230781640628620899",
+            256,
+        );
+        assert!((&pi - &reference) < decimal("1e-75", 256));
+        assert!((&reference - &pi) < decimal("1e-75", 256));
+        let sin = calculator.parse_str_precise("sin(1e6)", 128).unwrap();
+        assert!((sin.to_f64().value() - 1e6_f64.sin()).abs() < 1e-15);
+        // Angle of a QFT rotation is exactly representable
+        let angle = calculator
+            .parse_str_precise("1 + 2^(-80) - 1", 128)
+            .unwrap();
+        assert_eq!(
+            angle,
+            decimal("1", 128) / decimal("1208925819614629174706176", 128)
+        );
+        assert_eq!(calculator.parse_str("1 + 2^(-80) - 1").unwrap(), 0.0);
+    }
+
+    // Test that variables defined by expressions are evaluated with full precision
+    #[test]
+    fn test_defined_variables() {
+        let mut calculator = Calculator::new();
+        calculator.set_expression("pi", "4*atan(1)").unwrap();
+        calculator.set_expression("phi", "pi/3").unwrap();
+        calculator.set_expression("tenth", "0.1").unwrap();
+        let phi = calculator.parse_str_precise("phi", 200).unwrap();
+        let reference = calculator.parse_str_precise("pi/3", 200).unwrap();
+        assert_eq!(phi, reference);
+        // The value is not limited to f64 precision
+        let rounded = decimal(&format!("{:e}", phi.to_f64().value()), 200);
+        assert!(&phi - &rounded > decimal("1e-30", 200) || &rounded - &phi > decimal("1e-30", 200));
+        assert_eq!(
+            calculator.parse_str_precise("tenth", 200).unwrap(),
+            decimal("0.1", 200)
+        );
+        assert_eq!(
+            calculator.parse_str_precise("2*phi + tenth", 200).unwrap(),
+            calculator.parse_str_precise("2*pi/3 + 0.1", 200).unwrap()
+        );
+        // A stored value still takes precedence over the definition
+        calculator.set_variable("phi", 1.0);
+        assert_eq!(
+            calculator.parse_str_precise("phi", 200).unwrap(),
+            decimal("1", 200)
+        );
+    }
+
+    // Test assignments and errors of precise evaluation
+    #[test]
+    fn test_assign_errors() {
+        let mut calculator = Calculator::new();
+        let value = calculator.parse_str_precise("a=1/3; 3*a", 100).unwrap();
+        assert!((value.to_f64().value() - 1.0).abs() < 1e-15);
+        assert!((calculator.get_variable("a").unwrap() - 1.0 / 3.0).abs() < 1e-16);
+        assert_eq!(
+            calculator.parse_str_precise("1 / 0", 100),
+            Err(CalculatorError::DivisionByZero)
+        );
+        assert_eq!(
+            calculator.parse_str_precise("log(0)", 100),
+            Err(CalculatorError::DomainError {
+                fct: "log".to_string(),
+                arguments: vec![0.0]
+            })
+        );
+        assert_eq!(
+            calculator.parse_str_precise("sqrt(-4)", 100),
+            Err(CalculatorError::DomainError {
+                fct: "sqrt".to_string(),
+                arguments: vec![-4.0]
+            })
+        );
+        assert_eq!(
+            calculator.parse_str_precise("b + 1", 100),
+            Err(CalculatorError::VariableNotSet {
+                name: "b".to_string()
+            })
+        );
+    }
+}
@@ -0,0 +1,797 @@
+// Copyright © 2020-2021 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations underthe License.
+
+//! calculator_rational module
+//!
+//! Provides CalculatorRational struct for exact arithmetic with rational numbers
+//! and rational multiples of (powers of) pi.
+
+use crate::CalculatorError;
+use crate::CalculatorFloat;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::f64::consts::PI;
+use std::fmt;
+use std::ops;
+
+/// Reduced fraction numerator/denominator with positive denominator.
+///
+/// Numerator and denominator are stored as i128 so that sums and products of
+/// fractions with i64 numerators and denominators are always representable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+struct Rational {
+    numerator: i128,
+    denominator: i128,
+}
+
+/// Return greatest common divisor of two integers.
+fn gcd(mut a: i128, mut b: i128) -> i128 {
+    while b != 0 {
+        let r = a % b;
+        a = b;
+        b = r;
+    }
+    a.abs()
+}
+
+/// Rational arithmetic, returning None when a result does not fit into i128.
+///
+/// Common factors are cancelled before multiplying, so intermediate values
+/// never grow larger than the reduced result.
+impl Rational {
+    /// Return reduced Rational from numerator and nonzero denominator.
+    fn reduced(numerator: i128, denominator: i128) -> Option<Self> {
+        let divisor = gcd(numerator, denominator).checked_mul(denominator.signum())?;
+        Some(Rational {
+            numerator: numerator.checked_div(divisor)?,
+            denominator: denominator.checked_div(divisor)?,
+        })
+    }
+
+    fn add(self, other: Self) -> Option<Self> {
+        let divisor = gcd(self.denominator, other.denominator);
+        let numerator = self
+            .numerator
+            .checked_mul(other.denominator / divisor)?
+            .checked_add(other.numerator.checked_mul(self.denominator / divisor)?)?;
+        let denominator = self.denominator.checked_mul(other.denominator / divisor)?;
+        Self::reduced(numerator, denominator)
+    }
+
+    fn mul(self, other: Self) -> Option<Self> {
+        let divisor_1 = gcd(self.numerator, other.denominator).max(1);
+        let divisor_2 = gcd(other.numerator, self.denominator).max(1);
+        Some(Rational {
+            numerator: (self.numerator / divisor_1).checked_mul(other.numerator / divisor_2)?,
+            denominator: (self.denominator / divisor_2)
+                .checked_mul(other.denominator / divisor_1)?,
+        })
+    }
+
+    fn neg(self) -> Option<Self> {
+        Some(Rational {
+            numerator: self.numerator.checked_neg()?,
+            denominator: self.denominator,
+        })
+    }
+
+    fn recip(self) -> Option<Self> {
+        Self::reduced(self.denominator, self.numerator)
+    }
+
+    fn float(self) -> f64 {
+        self.numerator as f64 / self.denominator as f64
+    }
+}
+
+impl fmt::Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.denominator == 1 {
+            write!(f, "{}", self.numerator)
+        } else {
+            write!(f, "({}/{})", self.numerator, self.denominator)
+        }
+    }
+}
+
+/// Struct CalculatorRational.
+///
+/// Exact value of the form sum_k c_k * pi^k with rational coefficients c_k
+/// and integer (possibly negative) powers k. Sums, differences and products
+/// are computed exactly; rounding only happens when converting with `float()`.
+///
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct CalculatorRational {
+    /// Nonzero rational coefficients indexed by the power of pi
+    terms: BTreeMap<i32, Rational>,
+}
+
+/// Initialize CalculatorRational from i32 value.
+///
+/// # Returns
+///
+/// * `CalculatorRational`
+///
+impl From<i32> for CalculatorRational {
+    fn from(item: i32) -> Self {
+        Self::new(item as i64, 1)
+    }
+}
+
+/// Initialize CalculatorRational from u32 value.
+///
+/// # Returns
+///
+/// * `CalculatorRational`
+///
+impl From<u32> for CalculatorRational {
+    fn from(item: u32) -> Self {
+        Self::new(item as i64, 1)
+    }
+}
+
+/// Initialize CalculatorRational from i64 value.
+///
+/// # Returns
+///
+/// * `CalculatorRational`
+///
+impl From<i64> for CalculatorRational {
+    fn from(item: i64) -> Self {
+        Self::new(item, 1)
+    }
+}
+
+/// Initialize CalculatorRational from CalculatorRational reference &CalculatorRational.
+///
+/// # Returns
+///
+/// * `CalculatorRational`
+///
+impl<'a> From<&'a CalculatorRational> for CalculatorRational {
+    fn from(item: &'a CalculatorRational) -> Self {
+        item.clone()
+    }
+}
+
+/// Turn CalculatorRational into CalculatorFloat, rounding to the nearest f64.
+///
+/// # Returns
+///
+/// * `CalculatorFloat`
+///
+impl From<CalculatorRational> for CalculatorFloat {
+    fn from(item: CalculatorRational) -> Self {
+        CalculatorFloat::Float(item.float())
+    }
+}
+
+/// Turn CalculatorRational reference into CalculatorFloat, rounding to the nearest f64.
+///
+/// # Returns
+///
+/// * `CalculatorFloat`
+///
+impl<'a> From<&'a CalculatorRational> for CalculatorFloat {
+    fn from(item: &'a CalculatorRational) -> Self {
+        CalculatorFloat::Float(item.float())
+    }
+}
+
+/// Implement Display trait for CalculatorRational.
+///
+/// Terms are written as `c*pi^k` in ascending powers of pi, e.g. `1 + (3/8)*pi`.
+///
+impl fmt::Display for CalculatorRational {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.terms.is_empty() {
+            return write!(f, "0");
+        }
+        let terms: Vec<String> = self
+            .terms
+            .iter()
+            .map(|(power, coefficient)| match power {
+                0 => format!("{}", coefficient),
+                1 => format!("{}*pi", coefficient),
+                _ => format!("{}*pi^({})", coefficient, power),
+            })
+            .collect();
+        write!(f, "{}", terms.join(" + "))
+    }
+}
+
+impl CalculatorRational {
+    /// Return CalculatorRational for the fraction numerator/denominator.
+    ///
+    /// # Arguments
+    ///
+    /// * `numerator` - Numerator of the fraction
+    /// * `denominator` - Denominator of the fraction
+    ///
+    /// # Panics
+    ///
+    /// Panics when denominator is zero
+    pub fn new(numerator: i64, denominator: i64) -> Self {
+        Self::pi_power(numerator, denominator, 0)
+    }
+
+    /// Return CalculatorRational for the rational multiple (numerator/denominator)*pi.
+    ///
+    /// # Arguments
+    ///
+    /// * `numerator` - Numerator of the prefactor of pi
+    /// * `denominator` - Denominator of the prefactor of pi
+    ///
+    /// # Panics
+    ///
+    /// Panics when denominator is zero
+    pub fn pi_fraction(numerator: i64, denominator: i64) -> Self {
+        Self::pi_power(numerator, denominator, 1)
+    }
+
+    /// Return CalculatorRational representing pi.
+    pub fn pi() -> Self {
+        Self::pi_fraction(1, 1)
+    }
+
+    /// Return CalculatorRational for the single term (numerator/denominator)*pi^power.
+    fn pi_power(numerator: i64, denominator: i64, power: i32) -> Self {
+        if denominator == 0 {
+            panic!("Division by zero")
+        }
+        let mut terms = BTreeMap::new();
+        if numerator != 0 {
+            // Fractions of i64 values always fit into the i128 coefficients
+            if let Some(coefficient) = Rational::reduced(numerator as i128, denominator as i128) {
+                terms.insert(power, coefficient);
+            }
+        }
+        CalculatorRational { terms }
+    }
+
+    /// Return true when the value is exactly zero.
+    pub fn is_zero(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// Return the value approximated as f64.
+    ///
+    /// Numerators, denominators and powers of pi are rounded to f64 separately,
+    /// so the result can differ from the nearest f64 in the last few bits.
+    pub fn float(&self) -> f64 {
+        self.terms
+            .iter()
+            .map(|(power, coefficient)| coefficient.float() * PI.powi(*power))
+            .sum()
+    }
+
+    /// Return the rational prefactor (numerator, denominator) of pi^power.
+    ///
+    /// # Arguments
+    ///
+    /// * `power` - Power of pi whose prefactor is returned
+    ///
+    /// # Returns
+    ///
+    /// * `(i128, i128)` - Reduced fraction, (0, 1) when the term is absent
+    pub fn coefficient(&self, power: i32) -> (i128, i128) {
+        self.terms
+            .get(&power)
+            .map(|c| (c.numerator, c.denominator))
+            .unwrap_or((0, 1))
+    }
+
+    /// Return the exact sum self + other.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - Summand
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(CalculatorRational)` - Exact sum
+    /// * `Err(CalculatorError::NotExactlyRepresentable)` - A coefficient of the sum overflows i128
+    pub fn checked_add<T>(&self, other: T) -> Result<CalculatorRational, CalculatorError>
+    where
+        CalculatorRational: From<T>,
+    {
+        let other_from = Self::from(other);
+        let mut sum = self.clone();
+        sum.add_terms(&other_from).map(|_| sum).ok_or_else(|| {
+            CalculatorError::NotExactlyRepresentable {
+                val: format!("({}) + ({})", self, other_from),
+            }
+        })
+    }
+
+    /// Return the exact difference self - other.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - Subtrahend
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(CalculatorRational)` - Exact difference
+    /// * `Err(CalculatorError::NotExactlyRepresentable)` - A coefficient of the difference overflows i128
+    pub fn checked_sub<T>(&self, other: T) -> Result<CalculatorRational, CalculatorError>
+    where
+        CalculatorRational: From<T>,
+    {
+        let other_from = Self::from(other);
+        let mut difference = self.clone();
+        difference
+            .sub_terms(&other_from)
+            .map(|_| difference)
+            .ok_or_else(|| CalculatorError::NotExactlyRepresentable {
+                val: format!("({}) - ({})", self, other_from),
+            })
+    }
+
+    /// Return the exact product self * other.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - Factor
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(CalculatorRational)` - Exact product
+    /// * `Err(CalculatorError::NotExactlyRepresentable)` - A coefficient or power of pi of the product overflows
+    pub fn checked_mul<T>(&self, other: T) -> Result<CalculatorRational, CalculatorError>
+    where
+        CalculatorRational: From<T>,
+    {
+        let other_from = Self::from(other);
+        self.mul_terms(&other_from)
+            .ok_or_else(|| CalculatorError::NotExactlyRepresentable {
+                val: format!("({}) * ({})", self, other_from),
+            })
+    }
+
+    /// Return the exact quotient self/other.
+    ///
+    /// Division is exact when other consists of a single term c*pi^k.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - Divisor
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(CalculatorRational)` - Exact quotient
+    /// * `Err(CalculatorError::DivisionByZero)` - other is zero
+    /// * `Err(CalculatorError::NotExactlyRepresentable)` - other has more than one term or the quotient overflows
+    pub fn checked_div<T>(&self, other: T) -> Result<CalculatorRational, CalculatorError>
+    where
+        CalculatorRational: From<T>,
+    {
+        let other_from = Self::from(other);
+        let mut other_terms = other_from.terms.iter();
+        let quotient = match (other_terms.next(), other_terms.next()) {
+            (None, _) => return Err(CalculatorError::DivisionByZero),
+            (Some((power, coefficient)), None) => coefficient.recip().and_then(|recip| {
+                self.terms
+                    .iter()
+                    .map(|(p, c)| Some((p.checked_sub(*power)?, c.mul(recip)?)))
+                    .collect::<Option<BTreeMap<i32, Rational>>>()
+            }),
+            _ => None,
+        };
+        quotient
+            .map(|terms| CalculatorRational { terms })
+            .ok_or_else(|| CalculatorError::NotExactlyRepresentable {
+                val: format!("({}) / ({})", self, other_from),
+            })
+    }
+
+    /// Add all terms of other to self, returning None on overflow.
+    fn add_terms(&mut self, other: &CalculatorRational) -> Option<()> {
+        for (power, coefficient) in other.terms.iter() {
+            self.add_term(*power, *coefficient)?;
+        }
+        Some(())
+    }
+
+    /// Subtract all terms of other from self, returning None on overflow.
+    fn sub_terms(&mut self, other: &CalculatorRational) -> Option<()> {
+        for (power, coefficient) in other.terms.iter() {
+            self.add_term(*power, coefficient.neg()?)?;
+        }
+        Some(())
+    }
+
+    /// Return product of self and other, or None on overflow.
+    fn mul_terms(&self, other: &CalculatorRational) -> Option<CalculatorRational> {
+        let mut product = CalculatorRational::default();
+        for (p1, c1) in self.terms.iter() {
+            for (p2, c2) in other.terms.iter() {
+                product.add_term(p1.checked_add(*p2)?, c1.mul(*c2)?)?;
+            }
+        }
+        Some(product)
+    }
+
+    /// Add coefficient*pi^power to self, dropping the term if it cancels.
+    fn add_term(&mut self, power: i32, coefficient: Rational) -> Option<()> {
+        let sum = match self.terms.get(&power) {
+            Some(c) => c.add(coefficient)?,
+            None => coefficient,
+        };
+        if sum.numerator == 0 {
+            self.terms.remove(&power);
+        } else {
+            self.terms.insert(power, sum);
+        }
+        Some(())
+    }
+}
+
+/// Message of the panic when an operator overflows.
+const OVERFLOW: &str = "Overflow in CalculatorRational arithmetic";
+
+/// Implement `+` for CalculatorRational and generic type `T`.
+///
+/// # Arguments
+///
+/// * `other` - Any type T for which CalculatorRational::From<T> trait is implemented
+///
+/// # Panics
+///
+/// Panics when a coefficient overflows i128, use `checked_add` to handle this case
+///
+impl<T> ops::Add<T> for CalculatorRational
+where
+    CalculatorRational: From<T>,
+{
+    type Output = Self;
+    fn add(mut self, other: T) -> Self {
+        self.add_terms(&Self::from(other)).expect(OVERFLOW);
+        self
+    }
+}
+
+/// Implement `+=` for CalculatorRational and generic type `T`.
+///
+/// # Arguments
+///
+/// * `other` - Any type T for which CalculatorRational::From<T> trait is implemented
+///
+/// # Panics
+///
+/// Panics when a coefficient overflows i128, use `checked_add` to handle this case
+///
+impl<T> ops::AddAssign<T> for CalculatorRational
+where
+    CalculatorRational: From<T>,
+{
+    fn add_assign(&mut self, other: T) {
+        self.add_terms(&Self::from(other)).expect(OVERFLOW);
+    }
+}
+
+/// Implement `-` for CalculatorRational and generic type `T`.
+///
+/// # Arguments
+///
+/// * `other` - Any type T for which CalculatorRational::From<T> trait is implemented
+///
+/// # Panics
+///
+/// Panics when a coefficient overflows i128, use `checked_sub` to handle this case
+///
+impl<T> ops::Sub<T> for CalculatorRational
+where
+    CalculatorRational: From<T>,
+{
+    type Output = Self;
+    fn sub(mut self, other: T) -> Self {
+        self.sub_terms(&Self::from(other)).expect(OVERFLOW);
+        self
+    }
+}
+
+/// Implement `-=` for CalculatorRational and generic type `T`.
+///
+/// # Arguments
+///
+/// * `other` - Any type T for which CalculatorRational::From<T> trait is implemented
+///
+/// # Panics
+///
+/// Panics when a coefficient overflows i128, use `checked_sub` to handle this case
+///
+impl<T> ops::SubAssign<T> for CalculatorRational
+where
+    CalculatorRational: From<T>,
+{
+    fn sub_assign(&mut self, other: T) {
+        self.sub_terms(&Self::from(other)).expect(OVERFLOW);
+    }
+}
+
+/// Implement minus sign for CalculatorRational.
+///
+/// # Panics
+///
+/// Panics when a numerator is i128::MIN
+///
+impl ops::Neg for CalculatorRational {
+    type Output = CalculatorRational;
+    fn neg(self) -> Self {
+        CalculatorRational {
+            terms: self
+                .terms
+                .into_iter()
+                .map(|(p, c)| (p, c.neg().expect(OVERFLOW)))
+                .collect(),
+        }
+    }
+}
+
+/// Implement `*` for CalculatorRational and generic type `T`.
+///
+/// # Arguments
+///
+/// * `other` - Any type T for which CalculatorRational::From<T> trait is implemented
+///
+/// # Panics
+///
+/// Panics when a coefficient or power of pi overflows, use `checked_mul` to handle this case
+///
+impl<T> ops::Mul<T> for CalculatorRational
+where
+    CalculatorRational: From<T>,
+{
+    type Output = Self;
+    fn mul(self, other: T) -> Self {
+        self.mul_terms(&Self::from(other)).expect(OVERFLOW)
+    }
+}
+
+/// Implement `*=` for CalculatorRational and generic type `T`.
+///
+/// # Arguments
+///
+/// * `other` - Any type T for which CalculatorRational::From<T> trait is implemented
+///
+/// # Panics
+///
+/// Panics when a coefficient or power of pi overflows, use `checked_mul` to handle this case
+///
+impl<T> ops::MulAssign<T> for CalculatorRational
+where
+    CalculatorRational: From<T>,
+{
+    fn mul_assign(&mut self, other: T) {
+        *self = self.mul_terms(&Self::from(other)).expect(OVERFLOW);
+    }
+}
+
+/// Implement `/` for CalculatorRational and generic type `T`.
+///
+/// # Arguments
+///
+/// * `other` - Any type T for which CalculatorRational::From<T> trait is implemented
+///
+/// # Panics
+///
+/// Panics on division by zero, when the quotient is not exactly representable
+/// or when it overflows, use `checked_div` to handle these cases
+///
+impl<T> ops::Div<T> for CalculatorRational
+where
+    CalculatorRational: From<T>,
+{
+    type Output = Self;
+    fn div(self, other: T) -> Self {
+        match self.checked_div(other) {
+            Ok(x) => x,
+            Err(CalculatorError::DivisionByZero) => panic!("Division by zero"),
+            Err(err) => panic!("{}", err),
+        }
+    }
+}
+
+/// Implement `/=` for CalculatorRational and generic type `T`.
+///
+/// # Arguments
+///
+/// * `other` - Any type T for which CalculatorRational::From<T> trait is implemented
+///
+/// # Panics
+///
+/// Panics on division by zero, when the quotient is not exactly representable
+/// or when it overflows
+///
+impl<T> ops::DivAssign<T> for CalculatorRational
+where
+    CalculatorRational: From<T>,
+{
+    fn div_assign(&mut self, other: T) {
+        *self = self.clone() / other;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CalculatorRational;
+    use crate::CalculatorError;
+    use crate::CalculatorFloat;
+    use std::f64::consts::PI;
+
+    // Test that angle multiples of pi add up exactly
+    #[test]
+    fn exact_angles() {
+        let t = CalculatorRational::pi_fraction(1, 4);
+        let s = CalculatorRational::pi_fraction(1, 2);
+        assert_eq!(t.clone() + &t, s);
+        assert_eq!(t.clone() * 2, s);
+        assert_eq!(CalculatorRational::pi() * 3 / 8 * 2, s.clone() + &t);
+        assert_eq!(s.clone() - &t - &t, CalculatorRational::default());
+        assert!(!(s.clone() - &t).is_zero());
+        let mut x = CalculatorRational::pi_fraction(3, 8);
+        x += CalculatorRational::pi_fraction(5, 8);
+        assert_eq!(x, CalculatorRational::pi());
+        x -= CalculatorRational::pi();
+        assert!(x.is_zero());
+    }
+
+    // Test products and quotients of pi polynomials
+    #[test]
+    fn mul_div() {
+        let a = CalculatorRational::new(1, 2) + CalculatorRational::pi();
+        let sq = a.clone() * &a;
+        assert_eq!(sq.coefficient(0), (1, 4));
+        assert_eq!(sq.coefficient(1), (1, 1));
+        assert_eq!(sq.coefficient(2), (1, 1));
+        assert_eq!(sq.coefficient(3), (0, 1));
+        let b = a.clone() / CalculatorRational::pi_fraction(-2, 3);
+        assert_eq!(b.coefficient(-1), (-3, 4));
+        assert_eq!(b.coefficient(0), (-3, 2));
+        let mut c = b.clone();
+        c *= CalculatorRational::pi_fraction(-2, 3);
+        assert_eq!(c, a);
+        c /= 2;
+        assert_eq!(c.coefficient(0), (1, 4));
+        assert_eq!(
+            CalculatorRational::new(6, -4),
+            CalculatorRational::new(-3, 2)
+        );
+    }
+
+    // Test checked division errors
+    #[test]
+    fn checked_div() {
+        let a = CalculatorRational::new(1, 2) + CalculatorRational::pi();
+        assert_eq!(
+            CalculatorRational::from(1).checked_div(0),
+            Err(CalculatorError::DivisionByZero)
+        );
+        assert_eq!(
+            CalculatorRational::from(1).checked_div(&a),
+            Err(CalculatorError::NotExactlyRepresentable {
+                val: "(1) / ((1/2) + 1*pi)".to_string()
+            })
+        );
+    }
+
+    // Test sums and products of large denominators stay exact
+    #[test]
+    fn large_denominators() {
+        let x = CalculatorRational::new(1, 4000000007) + CalculatorRational::new(1, 4000000009);
+        assert_eq!(x.coefficient(0), (8000000016, 4000000007i128 * 4000000009));
+        let y = CalculatorRational::new(i64::MAX, 3) * CalculatorRational::new(i64::MAX, 5);
+        assert_eq!(y.coefficient(0), (i64::MAX as i128 * i64::MAX as i128, 15));
+        assert_eq!(
+            (y.clone() - &y).checked_add(CalculatorRational::pi()),
+            Ok(CalculatorRational::pi())
+        );
+        assert_eq!(
+            (y.clone() / CalculatorRational::new(i64::MAX, 1)).coefficient(0),
+            (i64::MAX as i128, 15)
+        );
+    }
+
+    // Test checked operations report overflow instead of panicking
+    #[test]
+    fn checked_overflow() {
+        let x = CalculatorRational::new(1, i64::MAX - 1) * CalculatorRational::new(1, i64::MAX);
+        let y = CalculatorRational::new(1, i64::MAX - 3) * CalculatorRational::new(1, i64::MAX - 5);
+        assert!(matches!(
+            x.checked_add(&y),
+            Err(CalculatorError::NotExactlyRepresentable { .. })
+        ));
+        assert!(matches!(
+            x.checked_sub(&y),
+            Err(CalculatorError::NotExactlyRepresentable { .. })
+        ));
+        assert!(matches!(
+            x.checked_mul(&y),
+            Err(CalculatorError::NotExactlyRepresentable { .. })
+        ));
+        assert!(matches!(
+            x.checked_div(CalculatorRational::new(i64::MAX - 3, 1) * (i64::MAX - 5)),
+            Err(CalculatorError::NotExactlyRepresentable { .. })
+        ));
+        assert_eq!(x.checked_mul(2), Ok(x.clone() * 2));
+        assert_eq!(x.checked_sub(&x), Ok(CalculatorRational::default()));
+    }
+
+    // Test panic of operators on overflow
+    #[test]
+    #[should_panic(expected = "Overflow in CalculatorRational arithmetic")]
+    fn mul_overflow() {
+        let x = CalculatorRational::new(1, i64::MAX - 1) * CalculatorRational::new(1, i64::MAX);
+        let _ = x.clone() * x;
+    }
+
+    // Test panic of division by zero
+    #[test]
+    #[should_panic]
+    fn div_zero() {
+        let _ = CalculatorRational::pi() / 0;
+    }
+
+    // Test rounding only happens on conversion
+    #[test]
+    fn float_conversion() {
+        let x = CalculatorRational::pi_fraction(3, 8) + CalculatorRational::new(1, 3);
+        assert!((x.float() - (3.0 * PI / 8.0 + 1.0 / 3.0)).abs() < 1e-15);
+        let f = CalculatorFloat::from(&x);
+        assert!(f.isclose(3.0 * PI / 8.0 + 1.0 / 3.0));
+        assert_eq!(
+            CalculatorFloat::from(CalculatorRational::new(1, 4)),
+            CalculatorFloat::Float(0.25)
+        );
+        assert_eq!(CalculatorRational::default().float(), 0.0);
+    }
+
+    // Test conversion to f64 of numerators and denominators beyond the f64 mantissa
+    #[test]
+    fn float_large_values() {
+        let large = (1i64 << 60) + 1;
+        let x = CalculatorRational::new(large, 3);
+        let expected = 384307168202282325.0 + 2.0 / 3.0;
+        assert!((x.float() - expected).abs() <= 4.0 * f64::EPSILON * expected);
+        let y = CalculatorRational::new(3, large);
+        assert!((y.float() - 1.0 / expected).abs() <= 4.0 * f64::EPSILON / expected);
+        let z = CalculatorRational::pi_fraction(large, 3) - CalculatorRational::pi_fraction(1, 3);
+        assert_eq!(z.coefficient(1), (1i128 << 60, 3));
+        assert!(
+            (z.float() - PI * (1u64 << 60) as f64 / 3.0).abs() <= 4.0 * f64::EPSILON * z.float()
+        );
+    }
+
+    // Test Display
+    #[test]
+    fn display() {
+        assert_eq!(format!("{}", CalculatorRational::default()), "0");
+        let x = CalculatorRational::new(1, 3)
+            + CalculatorRational::pi_fraction(-3, 8)
+            + CalculatorRational::pi() * CalculatorRational::pi() * 2;
+        assert_eq!(format!("{}", x), "(1/3) + (-3/8)*pi + 2*pi^(2)");
+        assert_eq!(
+            format!("{}", CalculatorRational::from(1) / CalculatorRational::pi()),
+            "1*pi^(-1)"
+        );
+    }
+
+    // Test equality of cloned values
+    #[test]
+    fn clone_eq() {
+        let x = CalculatorRational::pi_fraction(1, 4) + 1u32;
+        let y = x.clone();
+        assert_eq!(x, y);
+        assert_ne!(x, CalculatorRational::from(1i64));
+    }
+}
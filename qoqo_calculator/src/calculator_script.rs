@@ -0,0 +1,218 @@
+// Copyright © 2020-2021 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! calculator_script module
+//!
+//! Provides execution of multi-statement scripts with a result for every statement.
+
+use crate::calculator_cache::{execute, Instruction};
+use crate::{Calculator, CalculatorError};
+use std::ops::Range;
+
+/// Result of a single statement of a script.
+///
+/// # Fields
+///
+/// * `span` - Byte range of the statement in the script, without surrounding whitespace and comments
+/// * `line` - Line the statement starts in, starting at 1
+/// * `assigned` - Name of the variable the statement assigned, None when it is no assignment or failed
+/// * `value` - Value of the statement or the error of its evaluation
+///
+#[derive(Debug, PartialEq)]
+pub struct StatementResult {
+    /// Byte range of the statement in the script
+    pub span: Range<usize>,
+    /// Line of the statement in the script, starting at 1
+    pub line: usize,
+    /// Name of the variable assigned by the statement
+    pub assigned: Option<String>,
+    /// Value of the statement or the error of its evaluation
+    pub value: Result<f64, CalculatorError>,
+}
+
+/// Return byte ranges and lines of the statements in a script.
+///
+/// Statements are separated by `;` or line breaks outside of parentheses,
+/// `#` ends the statement and starts a comment until the end of the line.
+/// Empty statements are skipped. The line of a statement is the line it starts in.
+fn split_statements(script: &str) -> Vec<(Range<usize>, usize)> {
+    let mut statements = Vec::new();
+    let mut push = |start: usize, end: usize, line: usize| {
+        let text = &script[start..end];
+        let trimmed = text.trim();
+        if !trimmed.is_empty() {
+            let begin = start + text.len() - text.trim_start().len();
+            statements.push((begin..begin + trimmed.len(), line));
+        }
+    };
+    let mut start = 0;
+    let mut start_line = 1;
+    let mut line = 1;
+    let mut depth: usize = 0;
+    let mut in_comment = false;
+    for (index, c) in script.char_indices() {
+        match c {
+            '\n' => {
+                line += 1;
+                if depth > 0 && !in_comment {
+                    continue;
+                }
+                if !in_comment {
+                    push(start, index, start_line);
+                }
+                in_comment = false;
+                start = index + 1;
+                start_line = line;
+            }
+            _ if in_comment => (),
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            ';' if depth == 0 => {
+                push(start, index, start_line);
+                start = index + 1;
+                start_line = line;
+            }
+            '#' => {
+                push(start, index, start_line);
+                in_comment = true;
+                depth = 0;
+            }
+            _ => (),
+        }
+    }
+    if !in_comment {
+        push(start, script.len(), start_line);
+    }
+    statements
+}
+
+impl Calculator {
+    /// Execute a script and return the result of every statement.
+    ///
+    /// Statements are separated by `;` or line breaks outside of parentheses and `#`
+    /// starts a comment until the end of the line. A failing statement does not stop the script, later
+    /// statements are still executed, so all problems can be reported at once.
+    ///
+    /// # Arguments
+    ///
+    /// * `script` - Script that is executed
+    ///
+    /// # Returns
+    ///
+    /// `Vec<StatementResult>` - Results of the non-empty statements in script order
+    ///
+    pub fn run_script(&mut self, script: &str) -> Vec<StatementResult> {
+        split_statements(script)
+            .into_iter()
+            .map(|(span, line)| {
+                let (assigned, value) = match self.compiled(&script[span.clone()]) {
+                    Ok(program) => {
+                        let value = execute(self, &program);
+                        let assigned = match (program.last(), &value) {
+                            (Some(Instruction::Assign(name)), Ok(_)) => Some(name.clone()),
+                            _ => None,
+                        };
+                        (assigned, value)
+                    }
+                    Err(err) => (None, Err(err)),
+                };
+                StatementResult {
+                    span,
+                    line,
+                    assigned,
+                    value,
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{split_statements, StatementResult};
+    use crate::{Calculator, CalculatorError};
+
+    // Test splitting of scripts into statements
+    #[test]
+    fn split() {
+        let script = "a = 1; b=2 # comment; c=3\n\n  d = a + b  \n# only comment\ne";
+        let statements: Vec<(&str, usize)> = split_statements(script)
+            .into_iter()
+            .map(|(span, line)| (&script[span], line))
+            .collect();
+        assert_eq!(
+            statements,
+            vec![("a = 1", 1), ("b=2", 1), ("d = a + b", 3), ("e", 5)]
+        );
+        assert!(split_statements(" ; \n # nothing").is_empty());
+        let script = "x = (1 +\n 2); y = max(1;\n 2)\nz = 3)\nw";
+        let statements: Vec<(&str, usize)> = split_statements(script)
+            .into_iter()
+            .map(|(span, line)| (&script[span], line))
+            .collect();
+        assert_eq!(
+            statements,
+            vec![
+                ("x = (1 +\n 2)", 1),
+                ("y = max(1;\n 2)", 2),
+                ("z = 3)", 4),
+                ("w", 5)
+            ]
+        );
+    }
+
+    // Test per-statement results continue after errors
+    #[test]
+    fn run_script() {
+        let script =
+            "# parameters\ntheta = 0.5\nphi = 2*theta; psi = x + 1\nx = (1 +\n 2)\ntheta + phi\nsin(\ntheta";
+        let mut calculator = Calculator::new();
+        let results = calculator.run_script(script);
+        assert_eq!(results.len(), 6);
+        assert_eq!(
+            results[0],
+            StatementResult {
+                span: 13..24,
+                line: 2,
+                assigned: Some("theta".to_string()),
+                value: Ok(0.5)
+            }
+        );
+        assert_eq!(&script[results[1].span.clone()], "phi = 2*theta");
+        assert_eq!(results[1].value, Ok(1.0));
+        assert_eq!(
+            results[2],
+            StatementResult {
+                span: 40..51,
+                line: 3,
+                assigned: None,
+                value: Err(CalculatorError::VariableNotSet {
+                    name: "x".to_string()
+                })
+            }
+        );
+        // Line breaks inside parentheses continue the statement
+        assert_eq!(&script[results[3].span.clone()], "x = (1 +\n 2)");
+        assert_eq!(results[3].line, 4);
+        assert_eq!(results[3].assigned, Some("x".to_string()));
+        assert_eq!(results[3].value, Ok(3.0));
+        assert_eq!(results[4].line, 6);
+        assert_eq!(results[4].assigned, None);
+        assert_eq!(results[4].value, Ok(1.5));
+        // An unclosed parenthesis continues the statement until the end of the script
+        assert_eq!(&script[results[5].span.clone()], "sin(\ntheta");
+        assert_eq!(results[5].line, 7);
+        assert!(results[5].value.is_err());
+        assert!(!calculator.contains("psi"));
+        assert_eq!(calculator.get_variable("phi"), Ok(1.0));
+    }
+}
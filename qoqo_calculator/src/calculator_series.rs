@@ -0,0 +1,599 @@
+// Copyright © 2020-2021 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! calculator_series module
+//!
+//! Provides truncated Taylor series expansion of symbolic CalculatorFloat expressions.
+
+use crate::calculator::{function_1_argument, function_2_arguments, Evaluator, Parser};
+use crate::{CalculatorError, CalculatorExpr, CalculatorFloat};
+use std::collections::HashMap;
+use std::f64::consts::{LN_10, LN_2};
+
+/// Taylor coefficients c_0, ..., c_order of an expression in (variable - point).
+type Series = Vec<CalculatorFloat>;
+
+/// Return true when CalculatorFloat is numerically zero.
+fn is_zero(value: &CalculatorFloat) -> bool {
+    matches!(value, CalculatorFloat::Float(x) if *x == 0.0)
+}
+
+/// Apply function with one argument of the Calculator function table to a CalculatorFloat.
+///
+/// Symbolic arguments return the symbolic function call that is understood by the Calculator.
+pub(crate) fn apply_function_1_argument(
+    fct: &str,
+    arg0: &CalculatorFloat,
+) -> Result<CalculatorFloat, CalculatorError> {
+    match arg0 {
+        CalculatorFloat::Float(x) => Ok(CalculatorFloat::Float(function_1_argument(fct, *x)?)),
+        CalculatorFloat::Str(x) => Ok(CalculatorFloat::Str(x.function_1_argument(fct.to_string()))),
+    }
+}
+
+/// Apply function with two arguments of the Calculator function table to CalculatorFloats.
+///
+/// Symbolic arguments return the symbolic function call that is understood by the Calculator.
+pub(crate) fn apply_function_2_arguments(
+    fct: &str,
+    arg0: &CalculatorFloat,
+    arg1: &CalculatorFloat,
+) -> Result<CalculatorFloat, CalculatorError> {
+    match (arg0, arg1) {
+        (CalculatorFloat::Float(x), CalculatorFloat::Float(y)) => {
+            Ok(CalculatorFloat::Float(function_2_arguments(fct, *x, *y)?))
+        }
+        _ => Ok(CalculatorFloat::from(
+            CalculatorExpr::from(arg0)
+                .function_2_arguments(fct.to_string(), CalculatorExpr::from(arg1)),
+        )),
+    }
+}
+
+/// Return DomainError for function expanded at a point where it is not analytic.
+fn not_analytic(fct: &str, arg0: &CalculatorFloat) -> CalculatorError {
+    CalculatorError::DomainError {
+        fct: fct.to_string(),
+        arguments: vec![*arg0.float().unwrap_or(&f64::NAN)],
+    }
+}
+
+/// Return series with every coefficient multiplied by factor.
+fn scale<T>(series: &[CalculatorFloat], factor: T) -> Series
+where
+    CalculatorFloat: From<T>,
+    T: Copy,
+{
+    series.iter().map(|c| c.clone() * factor).collect()
+}
+
+/// Return truncated Cauchy product of two series.
+fn mul(lhs: &[CalculatorFloat], rhs: &[CalculatorFloat]) -> Series {
+    (0..lhs.len())
+        .map(|k| {
+            (0..=k)
+                .map(|j| lhs[j].clone() * &rhs[k - j])
+                .fold(CalculatorFloat::ZERO, |sum, term| sum + term)
+        })
+        .collect()
+}
+
+/// Return quotient of two series.
+fn div(lhs: &[CalculatorFloat], rhs: &[CalculatorFloat]) -> Result<Series, CalculatorError> {
+    if is_zero(&rhs[0]) {
+        return Err(CalculatorError::DivisionByZero);
+    }
+    let mut quotient: Series = Vec::with_capacity(lhs.len());
+    for k in 0..lhs.len() {
+        let coefficient = (1..=k)
+            .map(|j| rhs[j].clone() * &quotient[k - j])
+            .fold(lhs[k].clone(), |sum, term| sum - term);
+        quotient.push(coefficient / &rhs[0]);
+    }
+    Ok(quotient)
+}
+
+/// Return derivative of series with respect to the expansion variable.
+///
+/// The last coefficient is padded with zero to keep the order of the series.
+fn derivative(series: &[CalculatorFloat]) -> Series {
+    let mut derivative: Series = (1..series.len())
+        .map(|k| series[k].clone() * (k as f64))
+        .collect();
+    derivative.push(CalculatorFloat::ZERO);
+    derivative
+}
+
+/// Return integral of series with the given constant coefficient.
+fn integrate(constant: CalculatorFloat, series: &[CalculatorFloat]) -> Series {
+    let mut integral = vec![constant];
+    integral.extend((1..series.len()).map(|k| series[k - 1].clone() / (k as f64)));
+    integral
+}
+
+/// Return function f(a) from f(a_0) and the series of the derivative f'(a).
+fn from_derivative(
+    constant: CalculatorFloat,
+    series: &[CalculatorFloat],
+    outer_derivative: &[CalculatorFloat],
+) -> Series {
+    integrate(constant, &mul(outer_derivative, &derivative(series)))
+}
+
+/// Return exp(a) for the constant coefficient exp(a_0).
+///
+/// Uses the recurrence e_k = 1/k sum_{j=1}^{k} j a_j e_{k-j}.
+fn exp(series: &[CalculatorFloat], constant: CalculatorFloat) -> Series {
+    let mut result = vec![constant];
+    for k in 1..series.len() {
+        let coefficient = (1..=k)
+            .map(|j| series[j].clone() * (j as f64) * &result[k - j])
+            .fold(CalculatorFloat::ZERO, |sum, term| sum + term);
+        result.push(coefficient / (k as f64));
+    }
+    result
+}
+
+/// Return (sin(a), cos(a)) or (sinh(a), cosh(a)) when hyperbolic.
+fn sin_cos(
+    series: &[CalculatorFloat],
+    hyperbolic: bool,
+) -> Result<(Series, Series), CalculatorError> {
+    let (name_sin, name_cos) = if hyperbolic {
+        ("sinh", "cosh")
+    } else {
+        ("sin", "cos")
+    };
+    let sign = if hyperbolic { 1.0 } else { -1.0 };
+    let mut sin = vec![apply_function_1_argument(name_sin, &series[0])?];
+    let mut cos = vec![apply_function_1_argument(name_cos, &series[0])?];
+    for k in 1..series.len() {
+        let (sin_k, cos_k) = (1..=k).fold(
+            (CalculatorFloat::ZERO, CalculatorFloat::ZERO),
+            |(sin_k, cos_k), j| {
+                let factor = series[j].clone() * (j as f64);
+                (
+                    sin_k + factor.clone() * &cos[k - j],
+                    cos_k + factor * &sin[k - j],
+                )
+            },
+        );
+        sin.push(sin_k / (k as f64));
+        cos.push(cos_k * sign / (k as f64));
+    }
+    Ok((sin, cos))
+}
+
+/// Return a^r for constant exponent r and the constant coefficient a_0^r.
+///
+/// Uses the recurrence p_k = 1/(k a_0) sum_{j=1}^{k} ((r + 1) j - k) a_j p_{k-j}.
+/// Non-negative integer exponents are expanded by multiplication so that a_0 may vanish.
+fn power(
+    fct: &str,
+    series: &[CalculatorFloat],
+    exponent: &CalculatorFloat,
+    constant: CalculatorFloat,
+) -> Result<Series, CalculatorError> {
+    if is_zero(&series[0]) {
+        return match exponent {
+            CalculatorFloat::Float(r) if r.fract() == 0.0 && *r >= 0.0 => {
+                let mut result = constant_series(CalculatorFloat::ONE, series.len());
+                for _ in 0..(*r as usize).min(series.len()) {
+                    result = mul(&result, series);
+                }
+                Ok(result)
+            }
+            _ => Err(not_analytic(fct, &series[0])),
+        };
+    }
+    let mut result = vec![constant];
+    for k in 1..series.len() {
+        let coefficient = (1..=k)
+            .map(|j| {
+                let factor = (exponent.clone() + 1.0) * (j as f64) - (k as f64);
+                factor * &series[j] * &result[k - j]
+            })
+            .fold(CalculatorFloat::ZERO, |sum, term| sum + term);
+        result.push(coefficient / (series[0].clone() * (k as f64)));
+    }
+    Ok(result)
+}
+
+/// Return series of a constant value.
+fn constant_series(value: CalculatorFloat, length: usize) -> Series {
+    let mut series = vec![CalculatorFloat::ZERO; length];
+    series[0] = value;
+    series
+}
+
+/// Return square_factor * a^2 + constant for inverse trigonometric and hyperbolic functions.
+fn quadratic(series: &[CalculatorFloat], square_factor: f64, constant: f64) -> Series {
+    let mut result = scale(&mul(series, series), square_factor);
+    result[0] = result[0].clone() + constant;
+    result
+}
+
+/// Return true when all non-constant coefficients of the series vanish.
+fn is_constant(series: &[CalculatorFloat]) -> bool {
+    series.iter().skip(1).all(is_zero)
+}
+
+/// Evaluator for truncated Taylor series expansion.
+///
+/// # Fields
+///
+/// * `variable` - Variable the expression is expanded in
+/// * `point` - Point the expression is expanded around
+/// * `length` - Number of Taylor coefficients (order + 1)
+/// * `assigned` - Series of variables assigned during the evaluation
+///
+struct SeriesEvaluator<'a> {
+    variable: &'a str,
+    point: CalculatorFloat,
+    length: usize,
+    assigned: HashMap<String, Series>,
+}
+
+impl<'a> Evaluator for SeriesEvaluator<'a> {
+    type Value = Series;
+
+    fn number(&self, value: f64) -> Result<Series, CalculatorError> {
+        Ok(constant_series(CalculatorFloat::Float(value), self.length))
+    }
+
+    fn variable(&self, name: &str) -> Result<Series, CalculatorError> {
+        if let Some(value) = self.assigned.get(name) {
+            return Ok(value.clone());
+        }
+        if name == self.variable {
+            let mut series = constant_series(self.point.clone(), self.length);
+            if self.length > 1 {
+                series[1] = CalculatorFloat::ONE;
+            }
+            Ok(series)
+        } else {
+            Ok(constant_series(
+                CalculatorFloat::Str(CalculatorExpr::symbol(name.to_string())),
+                self.length,
+            ))
+        }
+    }
+
+    fn assign(&mut self, name: &str, value: Series) -> Result<Series, CalculatorError> {
+        self.assigned.insert(name.to_string(), value.clone());
+        Ok(value)
+    }
+
+    fn add(&self, lhs: Series, rhs: Series) -> Result<Series, CalculatorError> {
+        Ok(lhs.into_iter().zip(rhs).map(|(l, r)| l + r).collect())
+    }
+
+    fn sub(&self, lhs: Series, rhs: Series) -> Result<Series, CalculatorError> {
+        Ok(lhs.into_iter().zip(rhs).map(|(l, r)| l - r).collect())
+    }
+
+    fn mul(&self, lhs: Series, rhs: Series) -> Result<Series, CalculatorError> {
+        Ok(mul(&lhs, &rhs))
+    }
+
+    fn div(&self, lhs: Series, rhs: Series) -> Result<Series, CalculatorError> {
+        div(&lhs, &rhs)
+    }
+
+    fn pow(&self, lhs: Series, rhs: Series) -> Result<Series, CalculatorError> {
+        let constant = lhs[0].powf(&rhs[0]);
+        if is_constant(&rhs) {
+            power("pow", &lhs, &rhs[0], constant)
+        } else {
+            let log = self.function_1_argument("log", lhs)?;
+            Ok(exp(&mul(&rhs, &log), constant))
+        }
+    }
+
+    fn neg(&self, value: Series) -> Result<Series, CalculatorError> {
+        Ok(value.into_iter().map(|c| -c).collect())
+    }
+
+    fn function_1_argument(&self, fct: &str, arg0: Series) -> Result<Series, CalculatorError> {
+        let constant = apply_function_1_argument(fct, &arg0[0])?;
+        match fct {
+            "sin" => Ok(sin_cos(&arg0, false)?.0),
+            "cos" => Ok(sin_cos(&arg0, false)?.1),
+            "tan" => {
+                let (sin, cos) = sin_cos(&arg0, false)?;
+                div(&sin, &cos)
+            }
+            "sinh" => Ok(sin_cos(&arg0, true)?.0),
+            "cosh" => Ok(sin_cos(&arg0, true)?.1),
+            "tanh" => {
+                let (sinh, cosh) = sin_cos(&arg0, true)?;
+                div(&sinh, &cosh)
+            }
+            "exp" => Ok(exp(&arg0, constant)),
+            "exp2" => Ok(exp(&scale(&arg0, LN_2), constant)),
+            "expm1" => {
+                let mut result = exp(&arg0, arg0[0].exp());
+                result[0] = constant;
+                Ok(result)
+            }
+            "log" | "log10" => {
+                if is_zero(&arg0[0]) {
+                    return Err(not_analytic(fct, &arg0[0]));
+                }
+                let mut result = integrate(constant, &div(&derivative(&arg0), &arg0)?);
+                if fct == "log10" {
+                    for coefficient in result.iter_mut().skip(1) {
+                        *coefficient = coefficient.clone() / LN_10;
+                    }
+                }
+                Ok(result)
+            }
+            "sqrt" => power(fct, &arg0, &CalculatorFloat::Float(0.5), constant),
+            "cbrt" => power(fct, &arg0, &CalculatorFloat::Float(1.0 / 3.0), constant),
+            "asin" | "acos" | "asinh" | "arsinh" | "acosh" | "arcosh" => {
+                let (square_factor, shift, sign) = match fct {
+                    "asin" => (-1.0, 1.0, 1.0),
+                    "acos" => (-1.0, 1.0, -1.0),
+                    "acosh" | "arcosh" => (1.0, -1.0, 1.0),
+                    _ => (1.0, 1.0, 1.0),
+                };
+                let inner = quadratic(&arg0, square_factor, shift);
+                let exponent = CalculatorFloat::Float(-0.5);
+                let outer = power(fct, &inner, &exponent, inner[0].powf(-0.5))?;
+                Ok(from_derivative(constant, &arg0, &scale(&outer, sign)))
+            }
+            "atan" | "atanh" | "artanh" => {
+                let square_factor = if fct == "atan" { 1.0 } else { -1.0 };
+                let inner = quadratic(&arg0, square_factor, 1.0);
+                let one = constant_series(CalculatorFloat::ONE, self.length);
+                Ok(from_derivative(constant, &arg0, &div(&one, &inner)?))
+            }
+            "abs" => {
+                if is_zero(&arg0[0]) {
+                    return Err(not_analytic(fct, &arg0[0]));
+                }
+                let sign = apply_function_1_argument("sign", &arg0[0])?;
+                Ok(arg0.into_iter().map(|c| c * &sign).collect())
+            }
+            "fract" => {
+                let mut result = arg0;
+                result[0] = constant;
+                Ok(result)
+            }
+            "ceil" | "floor" | "round" | "sign" | "delta" | "theta" => {
+                Ok(constant_series(constant, self.length))
+            }
+            _ => Err(CalculatorError::FunctionNotFound {
+                fct: fct.to_string(),
+            }),
+        }
+    }
+
+    fn function_2_arguments(
+        &self,
+        fct: &str,
+        arg0: Series,
+        arg1: Series,
+    ) -> Result<Series, CalculatorError> {
+        let constant = apply_function_2_arguments(fct, &arg0[0], &arg1[0])?;
+        match fct {
+            "pow" => self.pow(arg0, arg1),
+            "atan2" => {
+                let numerator = self.sub(
+                    mul(&arg1, &derivative(&arg0)),
+                    mul(&arg0, &derivative(&arg1)),
+                )?;
+                let denominator = self.add(mul(&arg0, &arg0), mul(&arg1, &arg1))?;
+                Ok(integrate(constant, &div(&numerator, &denominator)?))
+            }
+            "hypot" => {
+                let square = self.add(mul(&arg0, &arg0), mul(&arg1, &arg1))?;
+                power(fct, &square, &CalculatorFloat::Float(0.5), constant)
+            }
+            "max" | "min" => {
+                let lhs = *arg0[0].float()?;
+                let rhs = *arg1[0].float()?;
+                if (fct == "max") == (lhs >= rhs) {
+                    Ok(arg0)
+                } else {
+                    Ok(arg1)
+                }
+            }
+            _ => Err(CalculatorError::FunctionNotFound {
+                fct: fct.to_string(),
+            }),
+        }
+    }
+}
+
+impl CalculatorFloat {
+    /// Return truncated Taylor series of CalculatorFloat as symbolic expression.
+    ///
+    /// The expression is expanded in `variable` around `point` up to (variable - point)^order.
+    /// All other variables are treated as symbolic constants.
+    ///
+    /// # Arguments
+    ///
+    /// * `variable` - Name of the variable the expression is expanded in
+    /// * `point` - Point of the expansion, any type T for which CalculatorFloat::From<T> trait is implemented
+    /// * `order` - Highest power of (variable - point) in the series
+    ///
+    /// # Returns
+    ///
+    /// `CalculatorFloat` - Taylor polynomial of the expression
+    ///
+    pub fn series<T>(
+        &self,
+        variable: &str,
+        point: T,
+        order: usize,
+    ) -> Result<CalculatorFloat, CalculatorError>
+    where
+        CalculatorFloat: From<T>,
+    {
+        self.series_around(variable, CalculatorFloat::from(point), order)
+    }
+
+    /// Return truncated Taylor series of CalculatorFloat around a CalculatorFloat point.
+    fn series_around(
+        &self,
+        variable: &str,
+        point: CalculatorFloat,
+        order: usize,
+    ) -> Result<CalculatorFloat, CalculatorError> {
+        let expression = match self {
+            CalculatorFloat::Float(_) => return Ok(self.clone()),
+            CalculatorFloat::Str(x) => x.to_string(),
+        };
+        let mut evaluator = SeriesEvaluator {
+            variable,
+            point: point.clone(),
+            length: order + 1,
+            assigned: HashMap::new(),
+        };
+        let mut parser = Parser::new(&expression, &mut evaluator);
+        let coefficients = match parser.evaluate_all_tokens()? {
+            None => return Err(CalculatorError::NoValueReturnedParsing),
+            Some(x) => x,
+        };
+        let displacement = CalculatorFloat::from(variable) - point;
+        let mut result = CalculatorFloat::ZERO;
+        for (k, coefficient) in coefficients.into_iter().enumerate() {
+            if is_zero(&coefficient) {
+                continue;
+            }
+            result = match k {
+                0 => result + coefficient,
+                1 => result + coefficient * &displacement,
+                _ => result + coefficient * displacement.powf(k as f64),
+            };
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Calculator, CalculatorError, CalculatorFloat};
+
+    // Compare series evaluated close to the expansion point with the expression
+    fn assert_series_close(expression: &str, point: f64, order: usize) {
+        let series = CalculatorFloat::from(expression)
+            .series("x", point, order)
+            .unwrap();
+        for displacement in &[-0.01, 0.005, 0.01] {
+            let mut calculator = Calculator::new();
+            calculator.set_variable("x", point + displacement);
+            let exact = calculator.parse_str(expression).unwrap();
+            let approximation = calculator.parse_get(series.clone()).unwrap();
+            assert!(
+                (exact - approximation).abs() < 1e-8 * (1.0 + exact.abs()),
+                "{} = {} != {} = {}",
+                expression,
+                exact,
+                approximation,
+                series
+            );
+        }
+    }
+
+    // Test string output of simple series
+    #[test]
+    fn test_series_string() {
+        let x = CalculatorFloat::from("exp(x)");
+        assert_eq!(
+            x.series("x", 0.0, 2).unwrap(),
+            CalculatorFloat::from("((1e0 + x) + (5e-1 * (x ^ 2e0)))")
+        );
+        let x = CalculatorFloat::from("x^2 + 3");
+        assert_eq!(
+            x.series("x", 1.0, 3).unwrap(),
+            CalculatorFloat::from("((4e0 + (2e0 * (x - 1e0))) + ((x - 1e0) ^ 2e0))")
+        );
+        let x = CalculatorFloat::from("sin(a * x)");
+        assert_eq!(
+            x.series("x", 0.0, 1).unwrap(),
+            CalculatorFloat::from("(a * x)")
+        );
+        let x = CalculatorFloat::from("sin(x)");
+        assert_eq!(
+            x.series("x", "x0", 1).unwrap(),
+            CalculatorFloat::from("(sin(x0) + (cos(x0) * (x - x0)))")
+        );
+        assert_eq!(
+            CalculatorFloat::from(2.0).series("x", 0.0, 3).unwrap(),
+            CalculatorFloat::from(2.0)
+        );
+        assert_eq!(
+            CalculatorFloat::from("y").series("x", 0.0, 3).unwrap(),
+            CalculatorFloat::from("y")
+        );
+    }
+
+    // Test series of arithmetic operations and all functions of the Calculator
+    #[test]
+    fn test_series_functions() {
+        for expression in &[
+            "x * (x + 1) / (2 - x)",
+            "x^3 - 2 * x",
+            "x^x",
+            "2^x",
+            "x^(-1.5)",
+            "a=x+1; a*a",
+        ] {
+            assert_series_close(expression, 0.7, 4);
+        }
+        for function in &[
+            "sin", "cos", "tan", "abs", "atan", "cosh", "sinh", "tanh", "asinh", "arsinh", "exp",
+            "exp2", "expm1", "cbrt", "ceil", "floor", "fract", "round", "sign", "delta", "theta",
+            "acos", "asin", "atanh", "artanh",
+        ] {
+            assert_series_close(&format!("{}(x)", function), 0.3, 5);
+            assert_series_close(&format!("{}(2 * x)", function), -0.2, 5);
+        }
+        for function in &["acosh", "arcosh", "log", "log10", "sqrt"] {
+            assert_series_close(&format!("{}(x)", function), 2.3, 5);
+        }
+        for function in &["atan2", "hypot", "pow", "max", "min"] {
+            assert_series_close(&format!("{}(x, 2 - x^2)", function), 0.4, 5);
+            assert_series_close(&format!("{}(3 * x, 1 + x)", function), 0.8, 5);
+        }
+    }
+
+    // Test errors of series expansion
+    #[test]
+    fn test_series_errors() {
+        assert_eq!(
+            CalculatorFloat::from("log(x)").series("x", 0.0, 3),
+            Err(CalculatorError::DomainError {
+                fct: "log".to_string(),
+                arguments: vec![0.0]
+            })
+        );
+        assert_eq!(
+            CalculatorFloat::from("1 / x").series("x", 0.0, 3),
+            Err(CalculatorError::DivisionByZero)
+        );
+        assert_eq!(
+            CalculatorFloat::from("erf(x)").series("x", 0.0, 3),
+            Err(CalculatorError::FunctionNotFound {
+                fct: "erf".to_string()
+            })
+        );
+        assert_eq!(
+            CalculatorFloat::from("max(x, y)").series("x", 0.0, 3),
+            Err(CalculatorError::FloatSymbolicNotConvertable {
+                val: "y".to_string()
+            })
+        );
+    }
+}
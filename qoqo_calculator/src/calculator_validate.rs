@@ -0,0 +1,506 @@
+// Copyright © 2020-2021 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! calculator_validate module
+//!
+//! Provides syntax validation of string expressions without evaluating them.
+
+use crate::calculator::{function_argument_numbers, Token, TokenIterator};
+use crate::calculator_cache::compile;
+use crate::{Calculator, CalculatorError};
+use std::ops::Range;
+
+/// Syntax problem found when validating an expression.
+///
+/// # Fields
+///
+/// * `span` - Byte range of the expression the problem was found at
+/// * `error` - Description of the problem
+///
+#[derive(Debug, PartialEq)]
+pub struct SyntaxProblem {
+    /// Byte range of the expression the problem was found at
+    pub span: Range<usize>,
+    /// Description of the problem
+    pub error: CalculatorError,
+}
+
+/// Bracket that has been opened but not closed yet.
+///
+/// # Fields
+///
+/// * `span` - Byte range of the opening token
+/// * `function` - Name and number of arguments of the function, None for plain brackets
+/// * `arguments` - Number of arguments found so far
+/// * `exponent` - Whether the bracket is the exponent of a power
+///
+struct OpenBracket {
+    span: Range<usize>,
+    function: Option<(String, Option<usize>)>,
+    arguments: usize,
+    exponent: bool,
+}
+
+/// Part of the grammar the validator expects the next token to start.
+///
+/// * `Operand` - Number, variable, function or bracket; `init` when an assignment is allowed,
+///   `signed` when a unary sign has been read, `exponent` when the operand is the exponent of a power
+/// * `Operator` - Operator or end of the operand; `exponent` when the operand was the exponent of a power
+/// * `StatementEnd` - Further `;`, the end of the expression or a new statement
+///
+#[derive(Clone, Copy)]
+enum Expect {
+    Operand {
+        init: bool,
+        signed: bool,
+        exponent: bool,
+    },
+    Operator {
+        exponent: bool,
+    },
+    StatementEnd,
+}
+
+/// Start of a new expression, where assignments are allowed.
+const EXPRESSION_START: Expect = Expect::Operand {
+    init: true,
+    signed: false,
+    exponent: false,
+};
+
+/// Validator following the grammar of the Parser without evaluating.
+///
+/// Instead of stopping at the first problem, it records the problem
+/// and continues, so all problems of an expression are found.
+struct Validator {
+    expect: Expect,
+    brackets: Vec<OpenBracket>,
+    problems: Vec<SyntaxProblem>,
+}
+
+impl Validator {
+    /// Record a problem at a span.
+    fn problem(&mut self, span: &Range<usize>, error: CalculatorError) {
+        self.problems.push(SyntaxProblem {
+            span: span.clone(),
+            error,
+        });
+    }
+
+    /// Record a parsing problem at a span.
+    fn parsing_problem(&mut self, span: &Range<usize>, msg: &'static str) {
+        self.problem(span, CalculatorError::ParsingError { msg });
+    }
+
+    /// Record all brackets that are still open as problems.
+    fn close_all_brackets(&mut self) {
+        for bracket in std::mem::take(&mut self.brackets) {
+            self.parsing_problem(&bracket.span, "Bracket is never closed");
+        }
+    }
+
+    /// Handle the next token.
+    fn token(&mut self, token: Token, span: Range<usize>) {
+        match self.expect {
+            Expect::Operand {
+                init,
+                signed,
+                exponent,
+            } => self.operand(token, span, init, signed, exponent),
+            Expect::Operator { exponent } => self.operator(token, span, exponent),
+            Expect::StatementEnd => match token {
+                Token::EndOfExpression | Token::EndOfString => (),
+                _ => self.operand(token, span, true, false, false),
+            },
+        }
+    }
+
+    /// Handle a token where an operand is expected.
+    fn operand(
+        &mut self,
+        token: Token,
+        span: Range<usize>,
+        init: bool,
+        signed: bool,
+        exponent: bool,
+    ) {
+        match token {
+            Token::Number(_) | Token::Variable(_) => {
+                self.expect = Expect::Operator { exponent };
+            }
+            Token::VariableAssign(_) => {
+                if !init {
+                    self.parsing_problem(&span, "Assignment only allowed at start of expression");
+                }
+                self.expect = Expect::Operand {
+                    init: false,
+                    signed: false,
+                    exponent: false,
+                };
+            }
+            Token::Function(name) => {
+                let number_arguments = match function_argument_numbers(&name) {
+                    Ok(number) => Some(number),
+                    Err(error) => {
+                        self.problem(&span, error);
+                        None
+                    }
+                };
+                self.brackets.push(OpenBracket {
+                    span,
+                    function: Some((name, number_arguments)),
+                    arguments: 1,
+                    exponent,
+                });
+                self.expect = EXPRESSION_START;
+            }
+            Token::BracketOpen => {
+                self.brackets.push(OpenBracket {
+                    span,
+                    function: None,
+                    arguments: 1,
+                    exponent,
+                });
+                self.expect = EXPRESSION_START;
+            }
+            Token::Plus | Token::Minus if !signed => {
+                self.expect = Expect::Operand {
+                    init: false,
+                    signed: true,
+                    exponent,
+                };
+            }
+            Token::Plus | Token::Minus | Token::Multiply | Token::Divide | Token::Power => {
+                self.parsing_problem(&span, "Expected operand before operator");
+            }
+            Token::Assign => {
+                self.parsing_problem(&span, "Assignment only allowed to a variable");
+            }
+            Token::Unrecognized => {
+                self.parsing_problem(&span, "Unrecognized character");
+                self.expect = Expect::Operator { exponent };
+            }
+            Token::EndOfExpression | Token::EndOfString => {
+                self.problem(&span, CalculatorError::UnexpectedEndOfExpression);
+                self.operator(token, span, exponent);
+            }
+            Token::BracketClose | Token::Comma | Token::Factorial | Token::DoubleFactorial => {
+                self.parsing_problem(&span, "Expected operand");
+                self.operator(token, span, exponent);
+            }
+        }
+    }
+
+    /// Handle a token after a complete operand.
+    fn operator(&mut self, token: Token, span: Range<usize>, exponent: bool) {
+        self.expect = Expect::Operator { exponent };
+        match token {
+            Token::Plus | Token::Minus | Token::Multiply | Token::Divide => {
+                self.expect = Expect::Operand {
+                    init: false,
+                    signed: false,
+                    exponent: false,
+                };
+            }
+            Token::Power => {
+                if exponent {
+                    self.parsing_problem(&span, "Repeated power needs brackets");
+                }
+                self.expect = Expect::Operand {
+                    init: false,
+                    signed: false,
+                    exponent: true,
+                };
+            }
+            Token::Factorial => self.problem(
+                &span,
+                CalculatorError::NotImplementedError { fct: "Factorial" },
+            ),
+            Token::DoubleFactorial => self.problem(
+                &span,
+                CalculatorError::NotImplementedError {
+                    fct: "DoubleFactorial",
+                },
+            ),
+            Token::BracketClose => match self.brackets.pop() {
+                None => self.parsing_problem(&span, "Bracket close without bracket open"),
+                Some(bracket) => {
+                    if let Some((fct, Some(expected))) = bracket.function {
+                        if bracket.arguments != expected {
+                            self.problem(
+                                &(bracket.span.start..span.end),
+                                CalculatorError::WrongNumberOfArguments {
+                                    fct,
+                                    expected,
+                                    found: bracket.arguments,
+                                },
+                            );
+                        }
+                    }
+                    self.expect = Expect::Operator {
+                        exponent: bracket.exponent,
+                    };
+                }
+            },
+            Token::Comma => {
+                match self.brackets.last_mut() {
+                    Some(OpenBracket {
+                        function: Some(_),
+                        arguments,
+                        ..
+                    }) => *arguments += 1,
+                    _ => self.parsing_problem(&span, "Comma outside of function arguments"),
+                }
+                self.expect = EXPRESSION_START;
+            }
+            Token::Assign => {
+                self.parsing_problem(&span, "Assignment only allowed to a variable");
+                self.expect = Expect::Operand {
+                    init: false,
+                    signed: false,
+                    exponent: false,
+                };
+            }
+            Token::Unrecognized => self.parsing_problem(&span, "Unrecognized character"),
+            Token::EndOfExpression => {
+                self.close_all_brackets();
+                self.expect = Expect::StatementEnd;
+            }
+            Token::EndOfString => self.close_all_brackets(),
+            // Outside of brackets an operand starts the next statement like after `;`
+            _ => {
+                if !self.brackets.is_empty() {
+                    self.parsing_problem(&span, "Expected operator");
+                }
+                self.operand(token, span, true, false, false);
+            }
+        }
+    }
+}
+
+impl Calculator {
+    /// Validate the syntax of an expression without evaluating it.
+    ///
+    /// Checks that the expression can be tokenized, brackets are balanced, operators
+    /// have operands and functions are known and called with the right number of arguments.
+    /// Variables do not need to be set. All problems are reported, not only the first.
+    ///
+    /// # Arguments
+    ///
+    /// * `expression` - Expression that is validated
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - Expression is syntactically valid
+    /// * `Err(Vec<SyntaxProblem>)` - All syntax problems in the order they appear in the expression
+    pub fn validate(expression: &str) -> Result<(), Vec<SyntaxProblem>> {
+        let mut validator = Validator {
+            expect: EXPRESSION_START,
+            brackets: Vec::new(),
+            problems: Vec::new(),
+        };
+        let mut lexer = TokenIterator {
+            current_expression: expression,
+        };
+        loop {
+            lexer.skip_whitespace_and_comments();
+            let start = expression.len() - lexer.current_expression.len();
+            let token = lexer.next().unwrap_or(Token::EndOfString);
+            let end = expression.len() - lexer.current_expression.len();
+            let finished = token == Token::EndOfString;
+            validator.token(token, start..end);
+            if finished {
+                break;
+            }
+        }
+        let mut problems = validator.problems;
+        // The parser has the final word, so validation never accepts what cannot be parsed
+        if problems.is_empty() {
+            if let Err(error) = compile(expression) {
+                problems.push(SyntaxProblem {
+                    span: 0..expression.len(),
+                    error,
+                });
+            }
+        }
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(problems)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SyntaxProblem;
+    use crate::calculator_cache::compile;
+    use crate::{Calculator, CalculatorError, CalculatorFloat};
+
+    // Return error and text of every problem of an invalid expression
+    fn problems(expression: &str) -> Vec<(CalculatorError, &str)> {
+        Calculator::validate(expression)
+            .unwrap_err()
+            .into_iter()
+            .map(|SyntaxProblem { span, error }| (error, &expression[span]))
+            .collect()
+    }
+
+    // Test valid expressions with unset variables
+    #[test]
+    fn valid() {
+        for expression in [
+            "2 * theta + sin(phi)",
+            "x = -2; y = atan2(x, 1e-3) ^ 2;; x*y;",
+            "max (a, (b + 1)) / -c # comment",
+            "2 x",
+            "(x = 3) + 1",
+            "2 ++ y",
+        ]
+        .iter()
+        {
+            assert_eq!(Calculator::validate(expression), Ok(()), "{}", expression);
+        }
+        assert_eq!(CalculatorFloat::from("2*x + pi").validate(), Ok(()));
+        assert_eq!(CalculatorFloat::from(1.0).validate(), Ok(()));
+    }
+
+    // Test all problems of invalid expressions are reported
+    #[test]
+    fn invalid() {
+        assert_eq!(
+            problems("2 ++* y"),
+            vec![(
+                CalculatorError::ParsingError {
+                    msg: "Expected operand before operator"
+                },
+                "*"
+            )]
+        );
+        assert_eq!(
+            problems("foo(x) + atan2(1) + sin(1, 2) + (3"),
+            vec![
+                (
+                    CalculatorError::FunctionNotFound {
+                        fct: "foo".to_string()
+                    },
+                    "foo("
+                ),
+                (
+                    CalculatorError::WrongNumberOfArguments {
+                        fct: "atan2".to_string(),
+                        expected: 2,
+                        found: 1
+                    },
+                    "atan2(1)"
+                ),
+                (
+                    CalculatorError::WrongNumberOfArguments {
+                        fct: "sin".to_string(),
+                        expected: 1,
+                        found: 2
+                    },
+                    "sin(1, 2)"
+                ),
+                (
+                    CalculatorError::ParsingError {
+                        msg: "Bracket is never closed"
+                    },
+                    "("
+                )
+            ]
+        );
+        assert_eq!(
+            problems("x) ; 2 ^ 3 ^ 4 ; 1 = 2 € 3!"),
+            vec![
+                (
+                    CalculatorError::ParsingError {
+                        msg: "Bracket close without bracket open"
+                    },
+                    ")"
+                ),
+                (
+                    CalculatorError::ParsingError {
+                        msg: "Repeated power needs brackets"
+                    },
+                    "^"
+                ),
+                (
+                    CalculatorError::ParsingError {
+                        msg: "Assignment only allowed to a variable"
+                    },
+                    "="
+                ),
+                (
+                    CalculatorError::ParsingError {
+                        msg: "Unrecognized character"
+                    },
+                    "€"
+                ),
+                (
+                    CalculatorError::NotImplementedError { fct: "Factorial" },
+                    "!"
+                )
+            ]
+        );
+        assert_eq!(
+            problems(""),
+            vec![(CalculatorError::UnexpectedEndOfExpression, "")]
+        );
+        assert_eq!(
+            problems("1, 2; (y = ;"),
+            vec![
+                (
+                    CalculatorError::ParsingError {
+                        msg: "Comma outside of function arguments"
+                    },
+                    ","
+                ),
+                (CalculatorError::UnexpectedEndOfExpression, ";"),
+                (
+                    CalculatorError::ParsingError {
+                        msg: "Bracket is never closed"
+                    },
+                    "("
+                )
+            ]
+        );
+        assert!(CalculatorFloat::from("sin(x").validate().is_err());
+    }
+
+    // Test validation agrees with the parser on all short expressions
+    #[test]
+    fn agrees_with_parser() {
+        let parts = [
+            "x", "2", "=", "+", "*", "^", "(", ")", ",", ";", "atan2(", "y=",
+        ];
+        let mut expressions = vec![String::new()];
+        for _ in 0..4 {
+            expressions = expressions
+                .iter()
+                .flat_map(|expression| {
+                    parts
+                        .iter()
+                        .map(move |part| format!("{} {}", expression, part))
+                })
+                .collect();
+            for expression in expressions.iter() {
+                assert_eq!(
+                    Calculator::validate(expression).is_ok(),
+                    compile(expression).is_ok(),
+                    "{}",
+                    expression
+                );
+            }
+        }
+    }
+}
@@ -26,8 +26,36 @@ mod calculator_float;
 pub use calculator_float::CalculatorFloat;
 mod calculator;
 pub use calculator::Calculator;
+mod calculator_cache;
+pub use calculator_cache::CacheStatistics;
 mod calculator_complex;
+mod calculator_decomposition;
+pub use calculator_decomposition::zyz_decomposition;
+mod calculator_definitions;
+mod calculator_dual;
+mod calculator_expr;
+pub use calculator_expr::CalculatorExpr;
+#[cfg(feature = "parameter-files")]
+mod calculator_files;
+mod calculator_interval;
+#[cfg(feature = "ndarray")]
+mod calculator_ndarray;
+#[cfg(feature = "ndarray")]
+pub use calculator_ndarray::CalculatorArray;
+#[cfg(feature = "num-traits")]
+mod calculator_num_traits;
+#[cfg(feature = "arbitrary-precision")]
+mod calculator_precise;
+#[cfg(feature = "arbitrary-precision")]
+pub use calculator_precise::PreciseFloat;
+mod calculator_rational;
+pub use calculator_rational::CalculatorRational;
+mod calculator_script;
+pub use calculator_script::StatementResult;
+mod calculator_series;
+mod calculator_validate;
 pub use calculator_complex::CalculatorComplex;
+pub use calculator_validate::SyntaxProblem;
 use thiserror::Error;
 
 /// Define custom errors for Calculator.
@@ -84,18 +112,117 @@ pub enum CalculatorError {
     /// Trying to divide by zero
     #[error("Division by zero error")]
     DivisionByZero,
+    /// The result of an exact operation can not be represented exactly
+    #[error("Result of {val:?} can not be represented exactly")]
+    NotExactlyRepresentable {
+        /// Operation that can not be represented
+        val: String,
+    },
+    /// The shapes of two arrays do not fit together
+    #[error("Shapes {lhs:?} and {rhs:?} of arrays do not match")]
+    ShapeMismatch {
+        /// Shape of the left hand side array
+        lhs: (usize, usize),
+        /// Shape of the right hand side array
+        rhs: (usize, usize),
+    },
     /// A parsed value did not return a value.
     #[error("Parsing Expression did not return value as expected.")]
     NoValueReturnedParsing,
     /// Not enough function arguments provided in parsed expression.
     #[error("Not enough function arguments.")]
     NotEnoughFunctionArguments,
+    /// Function evaluated outside of its domain in strict mode
+    #[error("Function {fct:?} is not defined for arguments {arguments:?}")]
+    DomainError {
+        /// Name of the function or operation
+        fct: String,
+        /// Arguments the function was evaluated with
+        arguments: Vec<f64>,
+    },
+    /// Evaluation overflowed to an infinite value in strict mode
+    #[error("Evaluation of {fct:?} with arguments {arguments:?} overflowed")]
+    Overflow {
+        /// Name of the function or operation
+        fct: String,
+        /// Arguments the function was evaluated with
+        arguments: Vec<f64>,
+    },
+    /// Range of a variable in interval evaluation has lower bound above upper bound
+    #[error("Range of variable {name:?} has lower bound {lower:?} above upper bound {upper:?}")]
+    InvalidInterval {
+        /// Name of the variable
+        name: String,
+        /// Lower bound of the range
+        lower: f64,
+        /// Upper bound of the range
+        upper: f64,
+    },
+    /// Variable definitions depend on each other in a cycle
+    #[error("Variable definitions form a cycle {cycle:?}")]
+    CircularDependency {
+        /// Names of the variables in the cycle, starting and ending with the same name
+        cycle: Vec<String>,
+    },
+    /// Reading, parsing or evaluating a parameter file failed
+    #[error("Error in file {file:?} at line {line:?} for key {key:?}: {msg}")]
+    FileError {
+        /// Path of the file
+        file: String,
+        /// Line in the file, starting at 1, when known
+        line: Option<usize>,
+        /// Key of the parameter, when known
+        key: Option<String>,
+        /// Description of the error
+        msg: String,
+    },
+    /// Serialized data uses a newer format version than supported
+    #[error("Format version {version:?} is newer than the supported version {supported:?}")]
+    UnsupportedFormatVersion {
+        /// Format version of the serialized data
+        version: u32,
+        /// Newest supported format version
+        supported: u32,
+    },
+    /// Function is called with the wrong number of arguments
+    #[error("Function {fct:?} takes {expected:?} arguments but {found:?} were given")]
+    WrongNumberOfArguments {
+        /// Name of the function
+        fct: String,
+        /// Number of arguments the function takes
+        expected: usize,
+        /// Number of arguments given
+        found: usize,
+    },
+    /// String is neither a float nor a syntactically valid expression
+    #[error("Expression {expression:?} is not valid: {problems:?}")]
+    InvalidExpression {
+        /// Expression that is not valid
+        expression: String,
+        /// All syntax problems of the expression
+        problems: Vec<SyntaxProblem>,
+    },
+    /// Expression assigns a variable where assignments are not allowed
+    #[error("Assignment to variable {name:?} is not allowed")]
+    AssignmentNotAllowed {
+        /// Name of the assigned variable
+        name: String,
+    },
+    /// Gradient is requested with respect to a variable defined by an expression
+    #[error(
+        "Gradient with respect to variable {name:?} defined by an expression is not supported"
+    )]
+    DerivedVariableGradient {
+        /// Name of the derived variable
+        name: String,
+    },
 }
 
 #[cfg(test)]
 mod tests {
     use super::CalculatorComplex;
     use super::CalculatorError;
+    use super::SyntaxProblem;
 
     // Test all CalculatorErrors give the correct output (debug)
     #[test]
@@ -158,10 +285,123 @@ mod tests {
         let div_zero = CalculatorError::DivisionByZero;
         assert_eq!(format!("{:?}", div_zero), "DivisionByZero");
 
+        let not_exact = CalculatorError::NotExactlyRepresentable {
+            val: "1 / pi".to_string(),
+        };
+        assert_eq!(
+            format!("{:?}", not_exact),
+            "NotExactlyRepresentable { val: \"1 / pi\" }"
+        );
+
+        let shape = CalculatorError::ShapeMismatch {
+            lhs: (2, 2),
+            rhs: (3, 1),
+        };
+        assert_eq!(
+            format!("{:?}", shape),
+            "ShapeMismatch { lhs: (2, 2), rhs: (3, 1) }"
+        );
+
         let parsing_no_val = CalculatorError::NoValueReturnedParsing;
         assert_eq!(format!("{:?}", parsing_no_val), "NoValueReturnedParsing");
 
         let func_args = CalculatorError::NotEnoughFunctionArguments;
         assert_eq!(format!("{:?}", func_args), "NotEnoughFunctionArguments");
+
+        let domain = CalculatorError::DomainError {
+            fct: String::from("sqrt"),
+            arguments: vec![-1.0],
+        };
+        assert_eq!(
+            format!("{:?}", domain),
+            "DomainError { fct: \"sqrt\", arguments: [-1.0] }"
+        );
+
+        let overflow = CalculatorError::Overflow {
+            fct: String::from("exp"),
+            arguments: vec![1000.0],
+        };
+        assert_eq!(
+            format!("{:?}", overflow),
+            "Overflow { fct: \"exp\", arguments: [1000.0] }"
+        );
+
+        let interval = CalculatorError::InvalidInterval {
+            name: String::from("x"),
+            lower: 1.0,
+            upper: 0.0,
+        };
+        assert_eq!(
+            format!("{:?}", interval),
+            "InvalidInterval { name: \"x\", lower: 1.0, upper: 0.0 }"
+        );
+
+        let cycle = CalculatorError::CircularDependency {
+            cycle: vec![String::from("a"), String::from("b"), String::from("a")],
+        };
+        assert_eq!(
+            format!("{:?}", cycle),
+            "CircularDependency { cycle: [\"a\", \"b\", \"a\"] }"
+        );
+
+        let assignment = CalculatorError::AssignmentNotAllowed {
+            name: String::from("x"),
+        };
+        assert_eq!(
+            format!("{:?}", assignment),
+            "AssignmentNotAllowed { name: \"x\" }"
+        );
+
+        let derived = CalculatorError::DerivedVariableGradient {
+            name: String::from("phi"),
+        };
+        assert_eq!(
+            format!("{:?}", derived),
+            "DerivedVariableGradient { name: \"phi\" }"
+        );
+
+        let arguments = CalculatorError::WrongNumberOfArguments {
+            fct: String::from("atan2"),
+            expected: 2,
+            found: 1,
+        };
+        assert_eq!(
+            format!("{:?}", arguments),
+            "WrongNumberOfArguments { fct: \"atan2\", expected: 2, found: 1 }"
+        );
+
+        let invalid = CalculatorError::InvalidExpression {
+            expression: String::from("sin(x"),
+            problems: vec![SyntaxProblem {
+                span: 0..4,
+                error: CalculatorError::ParsingError {
+                    msg: "Bracket is never closed",
+                },
+            }],
+        };
+        assert_eq!(
+            format!("{:?}", invalid),
+            "InvalidExpression { expression: \"sin(x\", problems: [SyntaxProblem { span: 0..4, error: ParsingError { msg: \"Bracket is never closed\" } }] }"
+        );
+
+        let file = CalculatorError::FileError {
+            file: String::from("parameters.toml"),
+            line: Some(2),
+            key: Some(String::from("phi")),
+            msg: String::from("Variable \"x\" not set."),
+        };
+        assert_eq!(
+            format!("{:?}", file),
+            "FileError { file: \"parameters.toml\", line: Some(2), key: Some(\"phi\"), msg: \"Variable \\\"x\\\" not set.\" }"
+        );
+
+        let version = CalculatorError::UnsupportedFormatVersion {
+            version: 2,
+            supported: 1,
+        };
+        assert_eq!(
+            format!("{:?}", version),
+            "UnsupportedFormatVersion { version: 2, supported: 1 }"
+        );
     }
 }
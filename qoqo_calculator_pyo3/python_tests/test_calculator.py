@@ -16,12 +16,177 @@ import numpy.testing as npt
 import os
 from qoqo_calculator_pyo3 import Calculator
 import math
+import copy
+import pickle
 
 def test_calculator_parse():
     c = Calculator() 
     c.set("a",1)
     assert c.parse_get("sin(a+1)") == math.sin(2)
 
+def test_calculator_strict():
+    c = Calculator()
+    assert not c.is_strict()
+    assert math.isnan(c.parse_str("sqrt(-1)"))
+    c.set_strict(True)
+    assert c.is_strict()
+    with pytest.raises(ValueError):
+        c.parse_str("sqrt(-1)")
+    with pytest.raises(ValueError):
+        c.parse_str("exp(1000)")
+
+def test_calculator_expression():
+    c = Calculator()
+    c.set("theta", 0.5)
+    c.set_expression("phi", "2*theta")
+    assert c.parse_str("phi") == 1.0
+    c.set("theta", 2)
+    assert c.parse_str("phi") == 4.0
+    assert c.get_expression("phi") == "2*theta"
+    assert c.dependents("theta") == ["phi"]
+    with pytest.raises(ValueError):
+        c.set_expression("theta", "phi + 1")
+
+def test_calculator_scopes():
+    c = Calculator()
+    c.set("theta", 0.5)
+    c.push_scope()
+    c.set("theta", 2)
+    assert c.parse_str("x=theta*2") == 4.0
+    assert c.scope_depth() == 1
+    assert c.pop_scope() == {"theta": 2.0, "x": 4.0}
+    assert c.parse_str("theta") == 0.5
+    with pytest.raises(ValueError):
+        c.parse_str("x")
+    assert c.pop_scope() is None
+
+def test_calculator_evaluate_execute():
+    c = Calculator()
+    c.set("theta", 0.5)
+    assert c.evaluate("2*theta") == 1.0
+    with pytest.raises(ValueError):
+        c.evaluate("x=theta")
+    assert c.execute("x=theta; x*4") == 2.0
+    assert c.evaluate("x") == 0.5
+
+def test_calculator_run_script():
+    c = Calculator()
+    results = c.run_script("theta = 0.5\nphi = 2*theta; psi = x  # comment\ntheta")
+    assert len(results) == 4
+    assert results[0] == {"span": (0, 11), "line": 1, "assigned": "theta", "value": 0.5, "error": None}
+    assert results[1]["assigned"] == "phi"
+    assert results[1]["value"] == 1.0
+    assert results[2]["line"] == 2
+    assert results[2]["value"] is None
+    assert "VariableNotSet" in results[2]["error"]
+    assert results[3]["value"] == 0.5
+    assert not c.contains("psi")
+
+def test_calculator_validate():
+    assert Calculator.validate("x = atan2(y, 1); x^2") == []
+    problems = Calculator.validate("2 ++* y")
+    assert problems == [{"span": (4, 5), "error": "ParsingError { msg: \"Expected operand before operator\" }"}]
+
+def test_calculator_variables():
+    c = Calculator({"theta": 0.5, "x": 1})
+    variables = c.variables
+    assert dict(variables) == {"theta": 0.5, "x": 1.0}
+    variables["theta"] = 1.0
+    assert c["theta"] == 1.0
+    variables["theta"] = 0.5
+    assert "x" in variables and len(variables) == 2
+    assert sorted(variables) == ["theta", "x"]
+    del variables["x"]
+    assert "x" not in c
+    with pytest.raises(KeyError):
+        variables["x"]
+    c["x"] = 1
+    c["y"] = 2
+    c.set_variables({"z": 3, "x": 4})
+    c.set_expression("phi", "2*theta")
+    assert c["phi"] == 1.0
+    assert "phi" in c and "y" in c
+    assert "w" not in c and not c.contains("w")
+    assert len(c) == 5
+    with pytest.raises(KeyError):
+        c["w"]
+    del c["phi"]
+    assert "phi" not in c
+    with pytest.raises(KeyError):
+        del c["phi"]
+    assert c.remove("z") == 3.0
+    assert c.remove("z") is None
+    assert dict(c.variables) == {"theta": 0.5, "x": 4.0, "y": 2.0}
+    c.set_expression("phi", "2*theta")
+    assert "phi" not in c.variables
+    assert dict(c) == {"phi": 1.0, "theta": 0.5, "x": 4.0, "y": 2.0}
+    assert [name for name in c] == ["phi", "theta", "x", "y"]
+    assert c.keys() == ["phi", "theta", "x", "y"]
+    c.push_scope()
+    c["phi"] = 3
+    del c["phi"]
+    assert c["phi"] == 1.0
+    with pytest.raises(KeyError):
+        del c["phi"]
+    c.pop_scope()
+    assert c.get_expression("phi") == "2*theta"
+    c.clear()
+    assert len(c) == 0
+
+def test_calculator_json_pickle():
+    c = Calculator({"theta": 0.5})
+    c.set_expression("phi", "2*theta")
+    c.set_strict(True)
+    restored = Calculator.from_json(c.to_json())
+    assert dict(restored.variables) == {"theta": 0.5}
+    assert restored.get_expression("phi") == "2*theta"
+    assert restored.is_strict()
+    for other in [pickle.loads(pickle.dumps(c)), copy.copy(c), copy.deepcopy(c)]:
+        assert other["phi"] == 1.0
+        assert other.is_strict()
+    with pytest.raises(ValueError):
+        Calculator.from_json('{"format_version": 1}')
+
+def test_calculator_cache():
+    c = Calculator()
+    assert c.cache_statistics() is None
+    c.enable_cache(2)
+    for i in range(3):
+        c.set("theta_0", i)
+        assert c.parse_str("2*theta_0") == 2 * i
+    statistics = c.cache_statistics()
+    assert statistics["hits"] == 2
+    assert statistics["misses"] == 1
+    assert statistics["len"] == 1
+    assert statistics["capacity"] == 2
+    c.clear_cache()
+    assert c.cache_statistics()["len"] == 0
+    c.disable_cache()
+    assert c.cache_statistics() is None
+
+def test_calculator_parse_with_gradient():
+    c = Calculator()
+    c.set('theta', 0.5)
+    c.set('phi', 2)
+    (value, gradient) = c.parse_with_gradient("sin(theta) * phi", ['theta', 'phi'])
+    assert math.isclose(value, 2 * math.sin(0.5))
+    assert math.isclose(gradient[0], 2 * math.cos(0.5))
+    assert math.isclose(gradient[1], math.sin(0.5))
+    with pytest.raises(ValueError):
+        c.parse_with_gradient("sin(x)", ['x'])
+    c.set_expression('psi', '2 * theta')
+    with pytest.raises(ValueError):
+        c.parse_with_gradient("psi", ['psi'])
+
+def test_calculator_evaluate_interval():
+    c = Calculator()
+    c.set('b', 2)
+    (lower, upper) = c.evaluate_interval("2 * sin(a) + b", {'a': (0, 2 * math.pi)})
+    assert lower <= 0 and math.isclose(lower, 0, abs_tol=1e-12)
+    assert upper >= 4 and math.isclose(upper, 4)
+    with pytest.raises(ValueError):
+        c.evaluate_interval("sqrt(a)", {'a': (-1, 1)})
+
 @pytest.mark.skip()
 @pytest.mark.parametrize("initial", [
     (1, 1, ),
@@ -179,5 +179,32 @@ def test_complex_cast_fail():
     with pytest.raises(ValueError):
         assert complex(cc)
 
+@pytest.mark.parametrize("method", ['exp', 'ln', 'sqrt', 'sin', 'cos', 'sinh', 'cosh'])
+def test_complex_transcendental(method):
+    value = -0.7 + 0.4j
+    result = getattr(CalculatorComplex(value), method)()
+    expected = getattr(np, 'log' if method == 'ln' else method)(value)
+    assert result.isclose(expected)
+
+def test_complex_pow():
+    value = -0.7 + 0.4j
+    assert CalculatorComplex(value).powf(1.5).isclose(value ** 1.5)
+    assert CalculatorComplex(value).powc(0.5 + 2j).isclose(value ** (0.5 + 2j))
+
+def test_complex_exp_symbolic():
+    phase = CalculatorComplex.from_pair(0, 'phi').exp()
+    assert phase.real == CalculatorFloat('cos(phi)')
+    assert phase.imag == CalculatorFloat('sin(phi)')
+
+def test_complex_polar():
+    cc = CalculatorComplex.from_polar(2, np.pi / 2)
+    assert cc.isclose(2j)
+    (r, phi) = CalculatorComplex(3 + 4j).to_polar()
+    assert r.isclose(5)
+    assert phi.isclose(np.arctan2(4, 3))
+    cc = CalculatorComplex.from_polar(1, 'phi')
+    assert cc.real == CalculatorFloat('cos(phi)')
+    assert cc.imag == CalculatorFloat('sin(phi)')
+
 if __name__ == '__main__':
     pytest.main(sys.argv)
\ No newline at end of file
@@ -14,7 +14,7 @@ import sys
 import numpy as np
 import numpy.testing as npt
 import os
-from qoqo_calculator_pyo3 import CalculatorFloat
+from qoqo_calculator_pyo3 import CalculatorFloat, Calculator
 import math
 
 def test_number():
@@ -192,6 +192,26 @@ def test_float_acos(initial):
     assert t.isclose(initial[1])
 
 
+@pytest.mark.parametrize("method", [
+    'tan', 'asin', 'atan', 'sinh', 'cosh', 'tanh', 'asinh', 'atanh', 'ln', 'log10',
+    'exp2', 'exp_m1', 'cbrt', 'ceil', 'floor', 'fract', 'round', 'delta', 'theta',
+])
+def test_float_function_table(method):
+    calc = Calculator()
+    calc.set('x', 0.3)
+    symbolic = getattr(CalculatorFloat('x'), method)()
+    numeric = getattr(CalculatorFloat(0.3), method)()
+    assert numeric.isclose(calc.parse_get(symbolic))
+
+
+def test_float_function_table_two_arguments():
+    assert CalculatorFloat(0.3).hypot(0.4).isclose(0.5)
+    assert CalculatorFloat(0.3).max(0.4).isclose(0.4)
+    assert CalculatorFloat(0.3).min(0.4).isclose(0.3)
+    assert CalculatorFloat('x').hypot('y') == CalculatorFloat('hypot(x, y)')
+    assert CalculatorFloat('x').ln() == CalculatorFloat('log(x)')
+
+
 @pytest.mark.parametrize("initial", [
     (1, 1, True),
     ('a', 'a', True),
@@ -217,5 +237,30 @@ def test_float_cast_fail():
     with pytest.raises(ValueError):
         assert float(cc)
 
+def test_float_series():
+    cc = CalculatorFloat("exp(x)")
+    assert cc.series("x", 0, 2) == CalculatorFloat("((1e0 + x) + (5e-1 * (x ^ 2e0)))")
+    cc = CalculatorFloat("sin(x)")
+    assert cc.series("x", "x0", 1) == CalculatorFloat("(sin(x0) + (cos(x0) * (x - x0)))")
+    with pytest.raises(ValueError):
+        CalculatorFloat("log(x)").series("x", 0, 2)
+
+def test_float_validate():
+    assert CalculatorFloat("2*theta + sin(phi)").validate() == []
+    assert CalculatorFloat(1.5).validate() == []
+    problems = CalculatorFloat("foo(x) + (1").validate()
+    assert len(problems) == 2
+    assert problems[0]["span"] == (0, 4)
+    assert "FunctionNotFound" in problems[0]["error"]
+    assert problems[1]["span"] == (9, 10)
+
+def test_float_new_validate():
+    assert CalculatorFloat("2*theta", validate=True) == CalculatorFloat("2*theta")
+    assert CalculatorFloat(1.5, validate=True).value == 1.5
+    assert CalculatorFloat("sin(x").value == "sin(x"
+    for expression in ["sin(x", "2 ++* y", ""]:
+        with pytest.raises(ValueError):
+            CalculatorFloat(expression, validate=True)
+
 if __name__ == '__main__':
     pytest.main(sys.argv)
\ No newline at end of file
@@ -15,12 +15,16 @@
 //! Converts the qoqo_calculator Calculator struct for parsing string expressions to floats
 //! into a Python class.
 
-use crate::convert_into_calculator_float;
-use pyo3::exceptions::{PyTypeError, PyValueError};
+use crate::{convert_into_calculator_float, convert_syntax_problems};
+use pyo3::exceptions::{PyKeyError, PyTypeError, PyValueError};
 use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyIterator, PyList};
+use pyo3::{PyIterProtocol, PyMappingProtocol, PySequenceProtocol};
 use qoqo_calculator::Calculator;
+use std::collections::{HashMap, HashSet};
 
 #[pyclass(name = "Calculator", module = "qoqo_calculator_pyo3")]
+#[derive(Clone)]
 pub struct CalculatorWrapper {
     pub r_calculator: Calculator,
 }
@@ -28,16 +32,163 @@ pub struct CalculatorWrapper {
 impl CalculatorWrapper {
     /// Create new Python instance of CalculatorWrapper.
     ///
+    /// # Arguments
+    ///
+    /// * `variables` - Optional dictionary of variable names and values
+    ///
     /// # Returns
     ///
     /// `<Self>` - CalculatorWrapper instance of Calculator
     ///
     #[new]
-    fn new() -> Self {
-        let r_calculator = Calculator::new();
+    #[args(variables = "None")]
+    fn new(variables: Option<HashMap<String, f64>>) -> Self {
+        let mut r_calculator = Calculator::new();
+        if let Some(variables) = variables {
+            r_calculator.set_variables(variables);
+        }
         CalculatorWrapper { r_calculator }
     }
 
+    /// Create Python copy of CalculatorWrapper.
+    fn __copy__(&self) -> CalculatorWrapper {
+        self.clone()
+    }
+
+    /// Create Python deep copy of CalculatorWrapper.
+    fn __deepcopy__(&self, _memodict: Py<PyAny>) -> CalculatorWrapper {
+        self.clone()
+    }
+
+    /// Return JSON serialization of Calculator used for pickling.
+    fn __getstate__(&self) -> PyResult<String> {
+        self.to_json()
+    }
+
+    /// Restore Calculator from JSON serialization when unpickling.
+    ///
+    /// # Arguments
+    ///
+    /// * `state` - JSON serialization of Calculator
+    ///
+    fn __setstate__(&mut self, state: &str) -> PyResult<()> {
+        self.r_calculator = Self::from_json(state)?.r_calculator;
+        Ok(())
+    }
+
+    /// Return JSON serialization of Calculator.
+    ///
+    /// Contains variables, variable definitions and settings,
+    /// but not the values of open local scopes.
+    ///
+    /// # Returns
+    ///
+    /// `str` - JSON serialization of Calculator
+    ///
+    fn to_json(&self) -> PyResult<String> {
+        serde_json::to_string(&self.r_calculator)
+            .map_err(|x| PyValueError::new_err(format!("Cannot serialize Calculator: {}", x)))
+    }
+
+    /// Create Calculator from JSON serialization.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - JSON serialization of Calculator
+    ///
+    /// # Raises
+    ///
+    /// `ValueError` - Input is not a valid serialization of Calculator
+    ///
+    #[staticmethod]
+    fn from_json(input: &str) -> PyResult<CalculatorWrapper> {
+        let r_calculator: Calculator = serde_json::from_str(input)
+            .map_err(|x| PyValueError::new_err(format!("Cannot deserialize Calculator: {}", x)))?;
+        Ok(CalculatorWrapper { r_calculator })
+    }
+
+    /// Return dict view of all variables with a value.
+    ///
+    /// Changing the view changes the variables of Calculator.
+    ///
+    /// # Returns
+    ///
+    /// `VariablesView` - View of the variable names and values
+    ///
+    #[getter]
+    fn variables(slf: PyRef<Self>) -> VariablesView {
+        VariablesView {
+            calculator: slf.into(),
+        }
+    }
+
+    /// Return names of all variables with a value or definition.
+    fn keys(&self) -> Vec<String> {
+        self.names()
+    }
+
+    /// Return values of all variables with a value or definition.
+    ///
+    /// # Raises
+    ///
+    /// `ValueError` - Variable is defined by an expression that cannot be evaluated
+    ///
+    fn values(&self) -> PyResult<Vec<f64>> {
+        self.names().iter().map(|name| self.value(name)).collect()
+    }
+
+    /// Return names and values of all variables with a value or definition.
+    ///
+    /// # Raises
+    ///
+    /// `ValueError` - Variable is defined by an expression that cannot be evaluated
+    ///
+    fn items(&self) -> PyResult<Vec<(String, f64)>> {
+        self.names()
+            .into_iter()
+            .map(|name| self.value(&name).map(|value| (name, value)))
+            .collect()
+    }
+
+    /// Set several variables for Calculator.
+    ///
+    /// # Arguments
+    ///
+    /// * `variables` - Dictionary of variable names and values
+    ///
+    fn set_variables(&mut self, variables: HashMap<String, f64>) {
+        self.r_calculator.set_variables(variables);
+    }
+
+    /// Remove variable value from Calculator.
+    ///
+    /// # Arguments
+    ///
+    /// * `variable_string` - string of the variable name
+    ///
+    /// # Returns
+    ///
+    /// `Optional[float]` - Removed value or None when the variable was not set
+    ///
+    fn remove(&mut self, variable_string: &str) -> Option<f64> {
+        self.r_calculator.remove_variable(variable_string)
+    }
+
+    /// Return True when a variable has a value or is defined by an expression.
+    ///
+    /// # Arguments
+    ///
+    /// * `variable_string` - string of the variable name
+    ///
+    fn contains(&self, variable_string: &str) -> bool {
+        self.r_calculator.contains(variable_string)
+    }
+
+    /// Remove all variables, definitions and local scopes from Calculator.
+    fn clear(&mut self) {
+        self.r_calculator.clear();
+    }
+
     /// Set variable for Calculator.
     ///
     /// # Arguments
@@ -49,6 +200,122 @@ impl CalculatorWrapper {
         self.r_calculator.set_variable(variable_string, val);
     }
 
+    /// Open new local scope for variables.
+    ///
+    /// Variables set until the scope is closed with pop_scope stay local to the scope.
+    fn push_scope(&mut self) {
+        self.r_calculator.push_scope();
+    }
+
+    /// Close innermost local scope.
+    ///
+    /// # Returns
+    ///
+    /// `Optional[Dict[str, float]]` - Variables set in the closed scope or None when no scope is open
+    ///
+    fn pop_scope(&mut self) -> Option<HashMap<String, f64>> {
+        self.r_calculator.pop_scope()
+    }
+
+    /// Return number of open local scopes.
+    fn scope_depth(&self) -> usize {
+        self.r_calculator.scope_depth()
+    }
+
+    /// Define variable by an expression that is evaluated whenever the variable is used.
+    ///
+    /// # Arguments
+    ///
+    /// * `variable_string` - string of the variable name
+    /// * `expression` - Expression defining the variable
+    ///
+    /// # Raises
+    ///
+    /// `ValueError` - Expression is invalid or the definition forms a cycle
+    ///
+    fn set_expression(&mut self, variable_string: &str, expression: &str) -> PyResult<()> {
+        self.r_calculator
+            .set_expression(variable_string, expression)
+            .map_err(|x| PyValueError::new_err(format!("{:?}; expression: {}", x, expression)))
+    }
+
+    /// Return expression defining a variable or None.
+    ///
+    /// # Arguments
+    ///
+    /// * `variable_string` - string of the variable name
+    ///
+    fn get_expression(&self, variable_string: &str) -> Option<String> {
+        self.r_calculator
+            .get_expression(variable_string)
+            .map(|x| x.to_string())
+    }
+
+    /// Return names of all variables whose definitions depend on a variable.
+    ///
+    /// # Arguments
+    ///
+    /// * `variable_string` - string of the variable name
+    ///
+    fn dependents(&self, variable_string: &str) -> Vec<String> {
+        self.r_calculator.dependents(variable_string)
+    }
+
+    /// Enable or disable strict mode for Calculator.
+    ///
+    /// In strict mode NaN or infinite intermediate values raise a ValueError.
+    ///
+    /// # Arguments
+    ///
+    /// * `strict` - Whether strict mode is enabled
+    ///
+    fn set_strict(&mut self, strict: bool) {
+        self.r_calculator.set_strict(strict);
+    }
+
+    /// Return True when strict mode is enabled for Calculator.
+    fn is_strict(&self) -> bool {
+        self.r_calculator.is_strict()
+    }
+
+    /// Enable cache of compiled expressions for Calculator.
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - Maximum number of cached expressions
+    ///
+    fn enable_cache(&mut self, capacity: usize) {
+        self.r_calculator.enable_cache(capacity);
+    }
+
+    /// Disable and drop cache of compiled expressions.
+    fn disable_cache(&mut self) {
+        self.r_calculator.disable_cache();
+    }
+
+    /// Remove all compiled expressions from cache, keeping its statistics.
+    fn clear_cache(&mut self) {
+        self.r_calculator.clear_cache();
+    }
+
+    /// Return statistics of the expression cache.
+    ///
+    /// # Returns
+    ///
+    /// `Optional[Dict[str, int]]` - hits, misses, evictions, len and capacity or None when the cache is disabled
+    ///
+    fn cache_statistics(&self) -> Option<HashMap<&'static str, u64>> {
+        self.r_calculator.cache_statistics().map(|statistics| {
+            let mut dict = HashMap::new();
+            dict.insert("hits", statistics.hits);
+            dict.insert("misses", statistics.misses);
+            dict.insert("evictions", statistics.evictions);
+            dict.insert("len", statistics.len as u64);
+            dict.insert("capacity", statistics.capacity as u64);
+            dict
+        })
+    }
+
     ///  Parse a string expression.
     ///
     /// # Arguments
@@ -65,6 +332,101 @@ impl CalculatorWrapper {
         }
     }
 
+    /// Evaluate a string expression without changing any variables.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - Expression that is evaluated
+    ///
+    /// # Raises
+    ///
+    /// `ValueError` - Expression is invalid, assigns a variable or cannot be evaluated
+    ///
+    pub fn evaluate(&self, input: &str) -> PyResult<f64> {
+        match self.r_calculator.evaluate(input) {
+            Ok(x) => Ok(x),
+            Err(x) => Err(PyValueError::new_err(format!(
+                "{:?}; expression: {}",
+                x, input
+            ))),
+        }
+    }
+
+    /// Execute a script of statements that may assign variables.
+    ///
+    /// # Arguments
+    ///
+    /// * `script` - Statements separated by `;`
+    ///
+    /// # Returns
+    ///
+    /// `float` - Value of the last statement
+    ///
+    pub fn execute(&mut self, script: &str) -> PyResult<f64> {
+        match self.r_calculator.execute(script) {
+            Ok(x) => Ok(x),
+            Err(x) => Err(PyValueError::new_err(format!(
+                "{:?}; script: {}",
+                x, script
+            ))),
+        }
+    }
+
+    /// Execute a script and return the result of every statement.
+    ///
+    /// Statements are separated by `;` or line breaks outside of parentheses and `#` starts a comment.
+    /// Failing statements do not stop the script.
+    ///
+    /// # Arguments
+    ///
+    /// * `script` - Script that is executed
+    ///
+    /// # Returns
+    ///
+    /// `List[Dict]` - span, line, assigned, value and error of every statement
+    ///
+    pub fn run_script(&mut self, py: Python, script: &str) -> PyResult<Vec<PyObject>> {
+        let mut results = Vec::new();
+        for statement in self.r_calculator.run_script(script) {
+            let dict = PyDict::new(py);
+            dict.set_item("span", (statement.span.start, statement.span.end))?;
+            dict.set_item("line", statement.line)?;
+            dict.set_item("assigned", statement.assigned)?;
+            match statement.value {
+                Ok(value) => {
+                    dict.set_item("value", value)?;
+                    dict.set_item("error", py.None())?;
+                }
+                Err(err) => {
+                    dict.set_item("value", py.None())?;
+                    dict.set_item("error", format!("{:?}", err))?;
+                }
+            }
+            results.push(dict.to_object(py));
+        }
+        Ok(results)
+    }
+
+    /// Validate the syntax of an expression without evaluating it.
+    ///
+    /// Variables used in the expression do not need to be set.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - Expression that is validated
+    ///
+    /// # Returns
+    ///
+    /// `List[Dict]` - span and error of all syntax problems, empty when the expression is valid
+    ///
+    #[staticmethod]
+    pub fn validate(py: Python, input: &str) -> PyResult<Vec<PyObject>> {
+        match Calculator::validate(input) {
+            Ok(()) => Ok(Vec::new()),
+            Err(problems) => convert_syntax_problems(py, problems),
+        }
+    }
+
     /// Parse an input to float.
     ///
     /// # Arguments
@@ -80,6 +442,289 @@ impl CalculatorWrapper {
             Err(x) => Err(PyValueError::new_err(format!("{:?}", x))),
         }
     }
+
+    /// Parse a string expression and compute its gradient.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - Expression that is parsed
+    /// * `variables` - List of variable names the gradient is computed for
+    ///
+    /// # Returns
+    ///
+    /// `(float, List[float])` - Value of the expression and partial derivatives
+    ///
+    /// # Raises
+    ///
+    /// `ValueError` - Parsing failed or one of the variables is defined by an expression
+    ///
+    pub fn parse_with_gradient(
+        &mut self,
+        input: &str,
+        variables: Vec<String>,
+    ) -> PyResult<(f64, Vec<f64>)> {
+        let variables: Vec<&str> = variables.iter().map(|x| x.as_str()).collect();
+        match self.r_calculator.parse_with_gradient(input, &variables) {
+            Ok(x) => Ok(x),
+            Err(x) => Err(PyValueError::new_err(format!(
+                "{:?}; expression: {}",
+                x, input
+            ))),
+        }
+    }
+
+    /// Compute guaranteed bounds of a string expression using interval arithmetic.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - Expression that is evaluated
+    /// * `ranges` - Dictionary of variable names and (lower, upper) bounds
+    ///
+    /// # Returns
+    ///
+    /// `(float, float)` - Lower and upper bound of the expression
+    ///
+    pub fn evaluate_interval(
+        &self,
+        input: &str,
+        ranges: HashMap<String, (f64, f64)>,
+    ) -> PyResult<(f64, f64)> {
+        match self.r_calculator.evaluate_interval(input, &ranges) {
+            Ok(x) => Ok(x),
+            Err(x) => Err(PyValueError::new_err(format!(
+                "{:?}; expression: {}",
+                x, input
+            ))),
+        }
+    }
+}
+
+impl CalculatorWrapper {
+    /// Return sorted names of all variables with a value or definition.
+    fn names(&self) -> Vec<String> {
+        let mut names: HashSet<&str> = self.r_calculator.iter().map(|(name, _)| name).collect();
+        names.extend(self.r_calculator.iter_expressions().map(|(name, _)| name));
+        let mut names: Vec<String> = names.into_iter().map(String::from).collect();
+        names.sort();
+        names
+    }
+
+    /// Return value of a variable with a value or definition.
+    fn value(&self, key: &str) -> PyResult<f64> {
+        self.r_calculator
+            .get_variable(key)
+            .map_err(|x| PyValueError::new_err(format!("{:?}; variable: {}", x, key)))
+    }
+}
+
+#[pyproto]
+impl PyMappingProtocol for CalculatorWrapper {
+    /// Return number of variables with a value or definition.
+    fn __len__(&self) -> usize {
+        self.names().len()
+    }
+
+    /// Return value of a variable.
+    ///
+    /// # Raises
+    ///
+    /// `KeyError` - Variable is not set
+    /// `ValueError` - Variable is defined by an expression that cannot be evaluated
+    ///
+    fn __getitem__(&self, key: &str) -> PyResult<f64> {
+        if !self.r_calculator.contains(key) {
+            return Err(PyKeyError::new_err(key.to_string()));
+        }
+        self.value(key)
+    }
+
+    /// Set value of a variable.
+    fn __setitem__(&mut self, key: &str, value: f64) {
+        self.r_calculator.set_variable(key, value);
+    }
+
+    /// Remove value or definition of a variable.
+    ///
+    /// When a local scope is open only the value in the innermost scope is removed,
+    /// definitions are only removed when no scope is open.
+    ///
+    /// # Raises
+    ///
+    /// `KeyError` - Variable is not set
+    ///
+    fn __delitem__(&mut self, key: &str) -> PyResult<()> {
+        let value = self.r_calculator.remove_variable(key);
+        let expression = if self.r_calculator.scope_depth() == 0 {
+            self.r_calculator.remove_expression(key)
+        } else {
+            None
+        };
+        match (value, expression) {
+            (None, None) => Err(PyKeyError::new_err(key.to_string())),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[pyproto]
+impl PySequenceProtocol for CalculatorWrapper {
+    /// Return True when a variable has a value or is defined by an expression.
+    fn __contains__(&self, key: &str) -> bool {
+        self.r_calculator.contains(key)
+    }
+}
+
+#[pyproto]
+impl PyIterProtocol for CalculatorWrapper {
+    /// Return iterator over the names of all variables with a value or definition.
+    fn __iter__(slf: PyRef<Self>) -> PyResult<PyObject> {
+        names_iterator(slf.py(), slf.names())
+    }
+}
+
+/// Return Python iterator over variable names.
+fn names_iterator(py: Python, names: Vec<String>) -> PyResult<PyObject> {
+    let list = PyList::new(py, names);
+    Ok(PyIterator::from_object(py, list)?.into())
+}
+
+/// Dict view of the variables with a value of a Calculator.
+///
+/// Reading and writing through the view reads and writes the variables of the Calculator.
+/// Variables defined by expressions are not part of the view.
+#[pyclass(name = "VariablesView", module = "qoqo_calculator_pyo3")]
+pub struct VariablesView {
+    calculator: Py<CalculatorWrapper>,
+}
+
+impl VariablesView {
+    /// Return value of a variable with a value.
+    fn get(&self, py: Python, key: &str) -> Option<f64> {
+        let calculator = self.calculator.borrow(py);
+        let value = calculator
+            .r_calculator
+            .iter()
+            .find(|(name, _)| *name == key)
+            .map(|(_, value)| value);
+        value
+    }
+
+    /// Return sorted names and values of all variables with a value.
+    fn sorted_items(&self, py: Python) -> Vec<(String, f64)> {
+        let calculator = self.calculator.borrow(py);
+        let mut items: Vec<(String, f64)> = calculator
+            .r_calculator
+            .iter()
+            .map(|(name, value)| (name.to_string(), value))
+            .collect();
+        items.sort_by(|a, b| a.0.cmp(&b.0));
+        items
+    }
+}
+
+#[pymethods]
+impl VariablesView {
+    /// Return names of all variables with a value.
+    fn keys(&self, py: Python) -> Vec<String> {
+        self.sorted_items(py)
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect()
+    }
+
+    /// Return values of all variables with a value.
+    fn values(&self, py: Python) -> Vec<f64> {
+        self.sorted_items(py)
+            .into_iter()
+            .map(|(_, value)| value)
+            .collect()
+    }
+
+    /// Return names and values of all variables with a value.
+    fn items(&self, py: Python) -> Vec<(String, f64)> {
+        self.sorted_items(py)
+    }
+
+    /// Return string representation of the variables.
+    fn __repr__(&self, py: Python) -> String {
+        let items: Vec<String> = self
+            .sorted_items(py)
+            .iter()
+            .map(|(name, value)| format!("'{}': {:?}", name, value))
+            .collect();
+        format!("VariablesView({{{}}})", items.join(", "))
+    }
+}
+
+#[pyproto]
+impl PyMappingProtocol for VariablesView {
+    /// Return number of variables with a value.
+    fn __len__(&self) -> usize {
+        let gil = pyo3::Python::acquire_gil();
+        let py = gil.python();
+        let calculator = self.calculator.borrow(py);
+        calculator.r_calculator.iter().count()
+    }
+
+    /// Return value of a variable.
+    ///
+    /// # Raises
+    ///
+    /// `KeyError` - Variable has no value
+    ///
+    fn __getitem__(&self, key: &str) -> PyResult<f64> {
+        let gil = pyo3::Python::acquire_gil();
+        let py = gil.python();
+        self.get(py, key)
+            .ok_or_else(|| PyKeyError::new_err(key.to_string()))
+    }
+
+    /// Set value of a variable.
+    fn __setitem__(&mut self, key: &str, value: f64) {
+        let gil = pyo3::Python::acquire_gil();
+        let py = gil.python();
+        self.calculator
+            .borrow_mut(py)
+            .r_calculator
+            .set_variable(key, value);
+    }
+
+    /// Remove value of a variable.
+    ///
+    /// When a local scope is open only the value in the innermost scope is removed.
+    ///
+    /// # Raises
+    ///
+    /// `KeyError` - Variable has no value
+    ///
+    fn __delitem__(&mut self, key: &str) -> PyResult<()> {
+        let gil = pyo3::Python::acquire_gil();
+        let py = gil.python();
+        let mut calculator = self.calculator.borrow_mut(py);
+        match calculator.r_calculator.remove_variable(key) {
+            Some(_) => Ok(()),
+            None => Err(PyKeyError::new_err(key.to_string())),
+        }
+    }
+}
+
+#[pyproto]
+impl PySequenceProtocol for VariablesView {
+    /// Return True when a variable has a value.
+    fn __contains__(&self, key: &str) -> bool {
+        let gil = pyo3::Python::acquire_gil();
+        let py = gil.python();
+        self.get(py, key).is_some()
+    }
+}
+
+#[pyproto]
+impl PyIterProtocol for VariablesView {
+    /// Return iterator over the names of all variables with a value.
+    fn __iter__(slf: PyRef<Self>) -> PyResult<PyObject> {
+        let py = slf.py();
+        names_iterator(py, slf.keys(py))
+    }
 }
 
 ///  Parse a string expression.
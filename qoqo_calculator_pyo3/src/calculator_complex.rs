@@ -148,11 +148,11 @@ impl CalculatorComplexWrapper {
         let py = gil.python();
         let object_real = match self.cc_internal.re {
             CalculatorFloat::Float(ref x) => x.to_object(py),
-            CalculatorFloat::Str(ref x) => x.to_object(py),
+            CalculatorFloat::Str(ref x) => x.to_string().to_object(py),
         };
         let object_imag = match self.cc_internal.im {
             CalculatorFloat::Float(ref x) => x.to_object(py),
-            CalculatorFloat::Str(ref x) => x.to_object(py),
+            CalculatorFloat::Str(ref x) => x.to_string().to_object(py),
         };
         (object_real, object_imag)
     }
@@ -174,7 +174,7 @@ impl CalculatorComplexWrapper {
                 dict.insert("real".to_string(), x.to_object(py));
             }
             CalculatorFloat::Str(x) => {
-                dict.insert("real".to_string(), x.to_object(py));
+                dict.insert("real".to_string(), x.to_string().to_object(py));
             }
         }
         match &self.cc_internal.im {
@@ -182,7 +182,7 @@ impl CalculatorComplexWrapper {
                 dict.insert("imag".to_string(), x.to_object(py));
             }
             CalculatorFloat::Str(x) => {
-                dict.insert("imag".to_string(), x.to_object(py));
+                dict.insert("imag".to_string(), x.to_string().to_object(py));
             }
         }
         dict
@@ -222,6 +222,33 @@ impl CalculatorComplexWrapper {
         })
     }
 
+    /// Create a new instance of CalculatorComplex from polar coordinates r * exp(i * phi).
+    #[staticmethod]
+    fn from_polar(r: Py<PyAny>, phi: Py<PyAny>) -> PyResult<CalculatorComplexWrapper> {
+        let gil = pyo3::Python::acquire_gil();
+        let py = gil.python();
+        let r_ref = r.as_ref(py);
+        let phi_ref = phi.as_ref(py);
+        let r_cf = convert_into_calculator_float(r_ref).map_err(|_| {
+            PyTypeError::new_err("Modulus input can not be converted to Calculator Float")
+        })?;
+        let phi_cf = convert_into_calculator_float(phi_ref).map_err(|_| {
+            PyTypeError::new_err("Phase input can not be converted to Calculator Float")
+        })?;
+        Ok(CalculatorComplexWrapper {
+            cc_internal: CalculatorComplex::from_polar(r_cf, phi_cf),
+        })
+    }
+
+    /// Return polar coordinates (r, phi) of complex number x = r * exp(i * phi).
+    fn to_polar(&self) -> (CalculatorFloatWrapper, CalculatorFloatWrapper) {
+        let (r, phi) = self.cc_internal.to_polar();
+        (
+            CalculatorFloatWrapper { cf_internal: r },
+            CalculatorFloatWrapper { cf_internal: phi },
+        )
+    }
+
     /// Return complex conjugate of x: x*=x.re-i*x.im.
     fn conj(&self) -> CalculatorComplexWrapper {
         Self {
@@ -236,6 +263,91 @@ impl CalculatorComplexWrapper {
         }
     }
 
+    /// Return exponential function exp(x) for CalculatorComplex.
+    fn exp(&self) -> CalculatorComplexWrapper {
+        Self {
+            cc_internal: self.cc_internal.exp(),
+        }
+    }
+
+    /// Return principal natural logarithm ln(x) for CalculatorComplex.
+    fn ln(&self) -> CalculatorComplexWrapper {
+        Self {
+            cc_internal: self.cc_internal.ln(),
+        }
+    }
+
+    /// Return principal square root sqrt(x) for CalculatorComplex.
+    fn sqrt(&self) -> CalculatorComplexWrapper {
+        Self {
+            cc_internal: self.cc_internal.sqrt(),
+        }
+    }
+
+    /// Return sine function sin(x) for CalculatorComplex.
+    fn sin(&self) -> CalculatorComplexWrapper {
+        Self {
+            cc_internal: self.cc_internal.sin(),
+        }
+    }
+
+    /// Return cosine function cos(x) for CalculatorComplex.
+    fn cos(&self) -> CalculatorComplexWrapper {
+        Self {
+            cc_internal: self.cc_internal.cos(),
+        }
+    }
+
+    /// Return hyperbolic sine function sinh(x) for CalculatorComplex.
+    fn sinh(&self) -> CalculatorComplexWrapper {
+        Self {
+            cc_internal: self.cc_internal.sinh(),
+        }
+    }
+
+    /// Return hyperbolic cosine function cosh(x) for CalculatorComplex.
+    fn cosh(&self) -> CalculatorComplexWrapper {
+        Self {
+            cc_internal: self.cc_internal.cosh(),
+        }
+    }
+
+    /// Return power x^y of CalculatorComplex with a real exponent.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - Any Python object that can be converted to CalculatorFloat
+    ///
+    fn powf(&self, other: Py<PyAny>) -> PyResult<CalculatorComplexWrapper> {
+        let gil = pyo3::Python::acquire_gil();
+        let py = gil.python();
+        let other_ref = other.as_ref(py);
+        let other_cf = convert_into_calculator_float(other_ref).map_err(|_| {
+            PyTypeError::new_err("Exponent can not be converted to Calculator Float")
+        })?;
+        Ok(Self {
+            cc_internal: self.cc_internal.powf(other_cf),
+        })
+    }
+
+    /// Return power x^y of CalculatorComplex with a complex exponent.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - Any Python object that can be converted to CalculatorComplex
+    ///
+    fn powc(&self, other: Py<PyAny>) -> PyResult<CalculatorComplexWrapper> {
+        let gil = pyo3::Python::acquire_gil();
+        let py = gil.python();
+        let other_ref = other.as_ref(py);
+        let other_cc = convert_into_calculator_complex(other_ref).map_err(|_| {
+            PyTypeError::new_err("Exponent can not be converted to Calculator Complex")
+        })?;
+        Ok(Self {
+            cc_internal: self.cc_internal.powc(other_cc),
+        })
+    }
+
     /// Return true when x is close to y.
     fn isclose(&self, other: Py<PyAny>) -> PyResult<bool> {
         let gil = pyo3::Python::acquire_gil();
@@ -19,11 +19,13 @@ use num_complex::Complex;
 use pyo3::class::basic::CompareOp;
 use pyo3::exceptions::{PyNotImplementedError, PyTypeError, PyValueError, PyZeroDivisionError};
 use pyo3::prelude::*;
+use pyo3::types::PyDict;
 use pyo3::{PyNumberProtocol, PyObjectProtocol};
-use qoqo_calculator::{CalculatorError, CalculatorFloat};
+use qoqo_calculator::{CalculatorError, CalculatorFloat, SyntaxProblem};
 use std::collections::HashMap;
 use std::convert::From;
 use std::panic::catch_unwind;
+use std::str::FromStr;
 
 /// Convert an f64 float (or any input that can be cast to float) or a string to CalculatorFloat.
 ///
@@ -63,6 +65,31 @@ pub fn convert_into_calculator_float(input: &PyAny) -> Result<CalculatorFloat, C
     }
 }
 
+/// Convert syntax problems of an expression to a list of Python dictionaries.
+///
+/// # Arguments
+///
+/// * `py` - Python GIL token
+/// * `problems` - Syntax problems found by validation
+///
+/// # Returns
+///
+/// `Vec<PyObject>` - span and error of every problem
+///
+pub fn convert_syntax_problems(
+    py: Python,
+    problems: Vec<SyntaxProblem>,
+) -> PyResult<Vec<PyObject>> {
+    let mut converted = Vec::new();
+    for problem in problems {
+        let dict = PyDict::new(py);
+        dict.set_item("span", (problem.span.start, problem.span.end))?;
+        dict.set_item("error", format!("{:?}", problem.error))?;
+        converted.push(dict.to_object(py));
+    }
+    Ok(converted)
+}
+
 #[pyclass(name = "CalculatorFloat", module = "qoqo_calculator_pyo3")]
 #[derive(Clone, Debug)]
 pub struct CalculatorFloatWrapper {
@@ -76,15 +103,21 @@ impl CalculatorFloatWrapper {
     /// # Arguments
     ///
     /// * `input` - input to instantiate the CalculatorFloat with
+    /// * `validate` - Reject symbolic inputs that are not syntactically valid expressions
     ///
     /// # Returns
     ///
     /// `PyResult<Self>` - CalculatorFloatWrapper of converted input or corresponding Python error
     ///
     #[new]
-    fn new(input: &PyAny) -> PyResult<Self> {
+    #[args(validate = "false")]
+    fn new(input: &PyAny, validate: bool) -> PyResult<Self> {
         let converted = convert_into_calculator_float(input)
             .map_err(|_| PyTypeError::new_err("Input can not be converted to Calculator Float"))?;
+        if let (true, CalculatorFloat::Str(expression)) = (validate, &converted) {
+            CalculatorFloat::from_str(&expression.to_string())
+                .map_err(|x| PyValueError::new_err(format!("{:?}", x)))?;
+        }
         Ok(CalculatorFloatWrapper {
             cf_internal: converted,
         })
@@ -126,11 +159,24 @@ impl CalculatorFloatWrapper {
         let py = gil.python();
         let object = match self.cf_internal {
             CalculatorFloat::Float(ref x) => x.to_object(py),
-            CalculatorFloat::Str(ref x) => x.to_object(py),
+            CalculatorFloat::Str(ref x) => x.to_string().to_object(py),
         };
         ((object,), HashMap::new())
     }
 
+    /// Validate the syntax of a symbolic CalculatorFloat without evaluating it.
+    ///
+    /// # Returns
+    ///
+    /// `List[Dict]` - span and error of all syntax problems, empty when the value is valid
+    ///
+    fn validate(&self, py: Python) -> PyResult<Vec<PyObject>> {
+        match self.cf_internal.validate() {
+            Ok(()) => Ok(Vec::new()),
+            Err(problems) => convert_syntax_problems(py, problems),
+        }
+    }
+
     /// Python getter function which returns True when
     /// CalculatorFloat does not contain symbolic expression.
     #[getter]
@@ -232,6 +278,226 @@ impl CalculatorFloatWrapper {
         }
     }
 
+    /// Return tangent function tan(x) for CalculatorFloat.
+    fn tan(&self) -> CalculatorFloatWrapper {
+        CalculatorFloatWrapper {
+            cf_internal: self.cf_internal.tan(),
+        }
+    }
+
+    /// Return arcsine function asin(x) for CalculatorFloat.
+    fn asin(&self) -> CalculatorFloatWrapper {
+        CalculatorFloatWrapper {
+            cf_internal: self.cf_internal.asin(),
+        }
+    }
+
+    /// Return arctangent function atan(x) for CalculatorFloat.
+    fn atan(&self) -> CalculatorFloatWrapper {
+        CalculatorFloatWrapper {
+            cf_internal: self.cf_internal.atan(),
+        }
+    }
+
+    /// Return hyperbolic sine function sinh(x) for CalculatorFloat.
+    fn sinh(&self) -> CalculatorFloatWrapper {
+        CalculatorFloatWrapper {
+            cf_internal: self.cf_internal.sinh(),
+        }
+    }
+
+    /// Return hyperbolic cosine function cosh(x) for CalculatorFloat.
+    fn cosh(&self) -> CalculatorFloatWrapper {
+        CalculatorFloatWrapper {
+            cf_internal: self.cf_internal.cosh(),
+        }
+    }
+
+    /// Return hyperbolic tangent function tanh(x) for CalculatorFloat.
+    fn tanh(&self) -> CalculatorFloatWrapper {
+        CalculatorFloatWrapper {
+            cf_internal: self.cf_internal.tanh(),
+        }
+    }
+
+    /// Return inverse hyperbolic sine function asinh(x) for CalculatorFloat.
+    fn asinh(&self) -> CalculatorFloatWrapper {
+        CalculatorFloatWrapper {
+            cf_internal: self.cf_internal.asinh(),
+        }
+    }
+
+    /// Return inverse hyperbolic cosine function acosh(x) for CalculatorFloat.
+    fn acosh(&self) -> CalculatorFloatWrapper {
+        CalculatorFloatWrapper {
+            cf_internal: self.cf_internal.acosh(),
+        }
+    }
+
+    /// Return inverse hyperbolic tangent function atanh(x) for CalculatorFloat.
+    fn atanh(&self) -> CalculatorFloatWrapper {
+        CalculatorFloatWrapper {
+            cf_internal: self.cf_internal.atanh(),
+        }
+    }
+
+    /// Return natural logarithm log(x) for CalculatorFloat.
+    fn ln(&self) -> CalculatorFloatWrapper {
+        CalculatorFloatWrapper {
+            cf_internal: self.cf_internal.ln(),
+        }
+    }
+
+    /// Return decimal logarithm log10(x) for CalculatorFloat.
+    fn log10(&self) -> CalculatorFloatWrapper {
+        CalculatorFloatWrapper {
+            cf_internal: self.cf_internal.log10(),
+        }
+    }
+
+    /// Return base 2 exponential function exp2(x) for CalculatorFloat.
+    fn exp2(&self) -> CalculatorFloatWrapper {
+        CalculatorFloatWrapper {
+            cf_internal: self.cf_internal.exp2(),
+        }
+    }
+
+    /// Return exponential function minus one expm1(x) for CalculatorFloat.
+    fn exp_m1(&self) -> CalculatorFloatWrapper {
+        CalculatorFloatWrapper {
+            cf_internal: self.cf_internal.exp_m1(),
+        }
+    }
+
+    /// Return cubic root cbrt(x) for CalculatorFloat.
+    fn cbrt(&self) -> CalculatorFloatWrapper {
+        CalculatorFloatWrapper {
+            cf_internal: self.cf_internal.cbrt(),
+        }
+    }
+
+    /// Return smallest integer greater or equal ceil(x) for CalculatorFloat.
+    fn ceil(&self) -> CalculatorFloatWrapper {
+        CalculatorFloatWrapper {
+            cf_internal: self.cf_internal.ceil(),
+        }
+    }
+
+    /// Return largest integer less or equal floor(x) for CalculatorFloat.
+    fn floor(&self) -> CalculatorFloatWrapper {
+        CalculatorFloatWrapper {
+            cf_internal: self.cf_internal.floor(),
+        }
+    }
+
+    /// Return fractional part fract(x) for CalculatorFloat.
+    fn fract(&self) -> CalculatorFloatWrapper {
+        CalculatorFloatWrapper {
+            cf_internal: self.cf_internal.fract(),
+        }
+    }
+
+    /// Return nearest integer round(x) for CalculatorFloat.
+    fn round(&self) -> CalculatorFloatWrapper {
+        CalculatorFloatWrapper {
+            cf_internal: self.cf_internal.round(),
+        }
+    }
+
+    /// Return Kronecker delta function delta(x) for CalculatorFloat.
+    fn delta(&self) -> CalculatorFloatWrapper {
+        CalculatorFloatWrapper {
+            cf_internal: self.cf_internal.delta(),
+        }
+    }
+
+    /// Return Heaviside step function theta(x) for CalculatorFloat.
+    fn theta(&self) -> CalculatorFloatWrapper {
+        CalculatorFloatWrapper {
+            cf_internal: self.cf_internal.theta(),
+        }
+    }
+
+    /// Return euclidean norm hypot(x, y) for CalculatorFloat and generic type `Py<PyAny>`.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - Any Python object that can be converted to CalculatorFloat
+    ///
+    fn hypot(&self, other: Py<PyAny>) -> PyResult<CalculatorFloatWrapper> {
+        let gil = pyo3::Python::acquire_gil();
+        let py = gil.python();
+        let other_ref = other.as_ref(py);
+        let other_cf = convert_into_calculator_float(other_ref).map_err(|_| {
+            PyTypeError::new_err("Right hand side can not be converted to Calculator Float")
+        })?;
+        Ok(CalculatorFloatWrapper {
+            cf_internal: self.cf_internal.hypot(other_cf),
+        })
+    }
+
+    /// Return maximum max(x, y) for CalculatorFloat and generic type `Py<PyAny>`.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - Any Python object that can be converted to CalculatorFloat
+    ///
+    fn max(&self, other: Py<PyAny>) -> PyResult<CalculatorFloatWrapper> {
+        let gil = pyo3::Python::acquire_gil();
+        let py = gil.python();
+        let other_ref = other.as_ref(py);
+        let other_cf = convert_into_calculator_float(other_ref).map_err(|_| {
+            PyTypeError::new_err("Right hand side can not be converted to Calculator Float")
+        })?;
+        Ok(CalculatorFloatWrapper {
+            cf_internal: self.cf_internal.max(other_cf),
+        })
+    }
+
+    /// Return minimum min(x, y) for CalculatorFloat and generic type `Py<PyAny>`.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - Any Python object that can be converted to CalculatorFloat
+    ///
+    fn min(&self, other: Py<PyAny>) -> PyResult<CalculatorFloatWrapper> {
+        let gil = pyo3::Python::acquire_gil();
+        let py = gil.python();
+        let other_ref = other.as_ref(py);
+        let other_cf = convert_into_calculator_float(other_ref).map_err(|_| {
+            PyTypeError::new_err("Right hand side can not be converted to Calculator Float")
+        })?;
+        Ok(CalculatorFloatWrapper {
+            cf_internal: self.cf_internal.min(other_cf),
+        })
+    }
+
+    /// Return truncated Taylor series of CalculatorFloat as symbolic expression.
+    ///
+    /// # Arguments
+    ///
+    /// * `variable` - Name of the variable the expression is expanded in
+    /// * `point` - Point of the expansion, float or str
+    /// * `order` - Highest power of (variable - point) in the series
+    ///
+    fn series(
+        &self,
+        variable: &str,
+        point: Py<PyAny>,
+        order: usize,
+    ) -> PyResult<CalculatorFloatWrapper> {
+        let gil = pyo3::Python::acquire_gil();
+        let py = gil.python();
+        let point_ref = point.as_ref(py);
+        let point_cf = convert_into_calculator_float(point_ref)
+            .map_err(|_| PyTypeError::new_err("Point can not be converted to Calculator Float"))?;
+        let cf_internal = self
+            .cf_internal
+            .series(variable, point_cf, order)
+            .map_err(|x| PyValueError::new_err(format!("{:?}", x)))?;
+        Ok(CalculatorFloatWrapper { cf_internal })
+    }
+
     /// Python getter function which returns the value stored in CalculatorFloat.
     #[getter]
     fn value(&self) -> PyObject {
@@ -239,7 +505,7 @@ impl CalculatorFloatWrapper {
         let py = gil.python();
         match self.cf_internal {
             CalculatorFloat::Float(ref x) => x.to_object(py),
-            CalculatorFloat::Str(ref x) => x.to_object(py),
+            CalculatorFloat::Str(ref x) => x.to_string().to_object(py),
         }
     }
 
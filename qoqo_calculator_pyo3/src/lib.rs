@@ -18,6 +18,7 @@ use pyo3::prelude::*;
 use pyo3::wrap_pyfunction;
 mod calculator_float;
 pub use calculator_float::convert_into_calculator_float;
+pub use calculator_float::convert_syntax_problems;
 pub use calculator_float::CalculatorFloatWrapper;
 mod calculator_complex;
 pub use calculator_complex::convert_into_calculator_complex;
@@ -25,6 +26,7 @@ pub use calculator_complex::CalculatorComplexWrapper;
 mod calculator;
 pub use calculator::parse_str;
 pub use calculator::CalculatorWrapper;
+pub use calculator::VariablesView;
 
 #[pyfunction]
 fn parse_string(expression: &str) -> PyResult<f64> {
@@ -45,6 +47,7 @@ fn parse_string(expression: &str) -> PyResult<f64> {
 #[pymodule]
 fn qoqo_calculator_pyo3(_py: Python, m: &PyModule) -> PyResult<()> {
     m.add_class::<CalculatorWrapper>()?;
+    m.add_class::<VariablesView>()?;
     m.add_class::<CalculatorFloatWrapper>()?;
     m.add_class::<CalculatorComplexWrapper>()?;
     m.add_function(wrap_pyfunction!(parse_string, m)?).unwrap();